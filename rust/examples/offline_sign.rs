@@ -0,0 +1,73 @@
+//! Sign an offline signing request on an air-gapped machine
+//!
+//! Usage: offline_sign <request.json> <key-file|-> [response.json]
+//!
+//! The key file (or stdin, for `-`) holds a private key in any format supported by
+//! `MemorySigner::from_private_key_string`, such as a JSON keypair file. The response is
+//! written to stdout unless an output path is given.
+
+use std::io::Read;
+use std::process::ExitCode;
+use trezoa_keychain::offline::{OfflineSigningRequest, TransactionSummary};
+use trezoa_keychain::{MemorySigner, SignerError, TrezoaSigner};
+
+fn read_private_key(path: &str) -> Result<String, SignerError> {
+    let mut private_key = String::new();
+    if path == "-" {
+        std::io::stdin().read_to_string(&mut private_key)?;
+    } else {
+        private_key = std::fs::read_to_string(path)?;
+    }
+    Ok(private_key.trim().to_string())
+}
+
+async fn run(args: &[String]) -> Result<(), SignerError> {
+    let request = OfflineSigningRequest::from_json(&std::fs::read_to_string(&args[0])?)?;
+    // Decode up front so nothing is shown for a request that fails its checks, and show
+    // the summary of the message itself rather than the one supplied with the request
+    let transaction = request.decode_transaction()?;
+
+    let signer = MemorySigner::from_private_key_string(&read_private_key(&args[1])?)?;
+
+    let summary = TransactionSummary::from_transaction(&transaction);
+    eprintln!("Fee payer:        {}", summary.fee_payer);
+    eprintln!("Recent blockhash: {}", summary.recent_blockhash);
+    if let Some(nonce_account) = &summary.durable_nonce_account {
+        eprintln!("Durable nonce:    {nonce_account}");
+    }
+    for (index, instruction) in summary.instructions.iter().enumerate() {
+        eprintln!("Instruction {index}: program {}", instruction.program_id);
+        for account in &instruction.accounts {
+            eprintln!("  account {account}");
+        }
+        eprintln!("  data {}", instruction.data);
+    }
+    eprintln!("Signing as:       {}", signer.pubkey());
+    eprintln!("Checksum:         {}", request.checksum);
+
+    let response = request.sign(&[&signer]).await?.to_json()?;
+
+    match args.get(2) {
+        Some(path) => std::fs::write(path, response)?,
+        None => println!("{response}"),
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() < 2 {
+        eprintln!("Usage: offline_sign <request.json> <key-file|-> [response.json]");
+        return ExitCode::FAILURE;
+    }
+
+    match run(&args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,662 @@
+//! Human-in-the-loop approval of signing requests
+//!
+//! [`ApprovalSigner`] parks every request in an [`ApprovalQueue`] until an operator
+//! approves or rejects it. The queue is cloneable, so an admin API or CLI can list
+//! and decide requests while the signing calls are waiting.
+//!
+//! A queue created with a [`Quorum`] instead needs signed approvals from M of N
+//! approver keys, so no single operator can release a signature.
+
+mod quorum;
+mod store;
+
+pub use quorum::{approval_message, Approval, Quorum, APPROVAL_DOMAIN};
+pub use store::{ApprovalStore, FileApprovalStore, MemoryApprovalStore};
+
+use crate::error::SignerError;
+use crate::offline::TransactionSummary;
+use crate::sdk_adapter::{hashv, Hash, Pubkey, Signature, Transaction, VersionedTransaction};
+use crate::store_util::{self, now_secs};
+use crate::traits::{SigningRequest, SigningResponse, TrezoaSigner};
+use crate::transaction_util::TransactionUtil;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+/// What an approval request asks to sign
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApprovalKind {
+    Transaction,
+    PartialTransaction,
+    VersionedTransaction,
+    Message,
+    /// Message bytes that skip the transaction message check, which may be a transaction
+    UncheckedMessage,
+}
+
+/// Lifecycle of an approval request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum ApprovalStatus {
+    Pending,
+    Approved,
+    Rejected {
+        reason: Option<String>,
+    },
+    /// Timed out, or the signing call went away before a decision
+    Expired,
+}
+
+/// A signing request waiting for (or resolved by) an operator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRequest {
+    pub id: String,
+    /// Base58-encoded public key of the signer
+    pub signer: String,
+    pub kind: ApprovalKind,
+    /// Decoded transaction, for transaction requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<TransactionSummary>,
+    /// Base64-encoded bytes to be signed (the message data for transactions)
+    pub payload: String,
+    /// Base58-encoded hash of the payload, covered by approver signatures
+    pub message_hash: String,
+    /// Unix timestamp (seconds) when the request was queued
+    pub created_at: u64,
+    /// Unix timestamp (seconds) after which the request times out
+    pub expires_at: u64,
+    pub status: ApprovalStatus,
+    /// Approver signatures collected so far (quorum queues only)
+    pub approvals: Vec<Approval>,
+    /// Base58-encoded signature produced once the request was approved
+    pub signature: Option<String>,
+}
+
+impl ApprovalRequest {
+    /// Whether the request is pending and has not timed out
+    pub fn is_pending(&self) -> bool {
+        self.status == ApprovalStatus::Pending && self.expires_at > now_secs()
+    }
+
+    /// The bytes an approver signs to approve this request
+    pub fn approval_message(&self) -> Result<Vec<u8>, SignerError> {
+        let signer = Pubkey::from_str(&self.signer).map_err(|e| {
+            SignerError::SerializationError(format!("Invalid signer public key: {e}"))
+        })?;
+        let message_hash = Hash::from_str(&self.message_hash)
+            .map_err(|e| SignerError::SerializationError(format!("Invalid message hash: {e}")))?;
+
+        Ok(approval_message(
+            &self.id,
+            &signer,
+            &message_hash,
+            self.expires_at,
+        ))
+    }
+}
+
+/// Configuration for [`ApprovalSigner`]
+#[derive(Debug, Clone)]
+pub struct ApprovalConfig {
+    /// How long a request waits for a decision (default: 15 minutes)
+    pub timeout: Duration,
+}
+
+impl Default for ApprovalConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(15 * 60),
+        }
+    }
+}
+
+type Waiters = HashMap<String, oneshot::Sender<ApprovalStatus>>;
+
+/// Queue of signing requests awaiting a decision
+///
+/// Requests are persisted in an [`ApprovalStore`]; the waiting signing calls live in
+/// this process, so requests left pending by a restart can no longer be approved.
+#[derive(Clone)]
+pub struct ApprovalQueue {
+    store: Arc<dyn ApprovalStore>,
+    quorum: Option<Arc<Quorum>>,
+    waiters: Arc<Mutex<Waiters>>,
+}
+
+impl std::fmt::Debug for ApprovalQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApprovalQueue")
+            .field("quorum", &self.quorum)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ApprovalQueue {
+    /// Queue where any operator can approve a request with [`approve`](Self::approve)
+    pub fn new(store: Arc<dyn ApprovalStore>) -> Self {
+        Self {
+            store,
+            quorum: None,
+            waiters: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Queue where requests need signed approvals via [`add_approval`](Self::add_approval)
+    pub fn with_quorum(store: Arc<dyn ApprovalStore>, quorum: Quorum) -> Self {
+        Self {
+            quorum: Some(Arc::new(quorum)),
+            ..Self::new(store)
+        }
+    }
+
+    fn waiters(&self) -> std::sync::MutexGuard<'_, Waiters> {
+        store_util::lock(&self.waiters)
+    }
+
+    /// Requests waiting for a decision, oldest first
+    pub async fn list_pending(&self) -> Result<Vec<ApprovalRequest>, SignerError> {
+        Ok(self
+            .store
+            .list()
+            .await?
+            .into_iter()
+            .filter(ApprovalRequest::is_pending)
+            .collect())
+    }
+
+    /// Look up a request by id, whatever its status
+    pub async fn get(&self, id: &str) -> Result<Option<ApprovalRequest>, SignerError> {
+        self.store.get(id).await
+    }
+
+    /// Approve a pending request, letting its signing call proceed
+    ///
+    /// Not available on quorum queues, use [`add_approval`](Self::add_approval).
+    pub async fn approve(&self, id: &str) -> Result<ApprovalRequest, SignerError> {
+        if let Some(quorum) = &self.quorum {
+            return Err(SignerError::PolicyViolation(format!(
+                "Approval request {id} needs {} approver signatures",
+                quorum.threshold()
+            )));
+        }
+        self.decide(id, ApprovalStatus::Approved).await
+    }
+
+    /// Add an approver's signature over [`ApprovalRequest::approval_message`]
+    ///
+    /// The signing call proceeds once the quorum threshold is reached.
+    pub async fn add_approval(
+        &self,
+        id: &str,
+        approver: &Pubkey,
+        signature: &Signature,
+    ) -> Result<ApprovalRequest, SignerError> {
+        let quorum = self.quorum.as_ref().ok_or_else(|| {
+            SignerError::ConfigError("Approval queue has no approver quorum".to_string())
+        })?;
+
+        let request = self
+            .store
+            .get(id)
+            .await?
+            .filter(ApprovalRequest::is_pending)
+            .ok_or_else(|| SignerError::Other(format!("Approval request {id} is not pending")))?;
+        quorum.verify(approver, signature, &request.approval_message()?)?;
+
+        let approval = Approval {
+            approver: approver.to_string(),
+            signature: signature.to_string(),
+        };
+        let request = self
+            .store
+            .add_approval(id, approval)
+            .await?
+            .ok_or_else(|| SignerError::Other(format!("Approval request {id} is not pending")))?;
+
+        if request.approvals.len() < quorum.threshold() {
+            return Ok(request);
+        }
+        self.decide(id, ApprovalStatus::Approved).await
+    }
+
+    /// Reject a pending request, failing its signing call
+    pub async fn reject(
+        &self,
+        id: &str,
+        reason: Option<String>,
+    ) -> Result<ApprovalRequest, SignerError> {
+        self.decide(id, ApprovalStatus::Rejected { reason }).await
+    }
+
+    async fn decide(
+        &self,
+        id: &str,
+        status: ApprovalStatus,
+    ) -> Result<ApprovalRequest, SignerError> {
+        let waiter = self.waiters().remove(id);
+
+        let Some(waiter) = waiter.filter(|waiter| !waiter.is_closed()) else {
+            // Nobody is waiting any more: record that instead of the decision
+            self.store.resolve(id, ApprovalStatus::Expired).await?;
+            return Err(SignerError::Other(format!(
+                "Approval request {id} is not waiting for a decision"
+            )));
+        };
+
+        let request = self
+            .store
+            .resolve(id, status.clone())
+            .await?
+            .ok_or_else(|| SignerError::Other(format!("Approval request {id} is not pending")))?;
+
+        // The signing call may time out in between; the stored decision still stands
+        let _ = waiter.send(status);
+        Ok(request)
+    }
+
+    /// Queue `request` and wait until it is decided or `timeout` elapses
+    async fn submit(&self, request: ApprovalRequest, timeout: Duration) -> Result<(), SignerError> {
+        let id = request.id.clone();
+        let (sender, mut receiver) = oneshot::channel();
+
+        // Register the waiter first, so the request can be decided as soon as it is listed
+        self.waiters().insert(id.clone(), sender);
+        if let Err(e) = self.store.insert(request).await {
+            self.waiters().remove(&id);
+            return Err(e);
+        }
+        log::info!("Signing request {id} is waiting for approval");
+
+        let status = match tokio::time::timeout(timeout, &mut receiver).await {
+            Ok(status) => status.ok(),
+            Err(_) => {
+                if self.waiters().remove(&id).is_some() {
+                    self.store.resolve(&id, ApprovalStatus::Expired).await?;
+                    None
+                } else {
+                    // A decision raced the timeout and is already on its way
+                    receiver.await.ok()
+                }
+            }
+        };
+
+        match status {
+            Some(ApprovalStatus::Approved) => Ok(()),
+            Some(ApprovalStatus::Rejected { reason }) => {
+                Err(SignerError::PolicyViolation(match reason {
+                    Some(reason) => format!("Signing request {id} was rejected: {reason}"),
+                    None => format!("Signing request {id} was rejected"),
+                }))
+            }
+            _ => Err(SignerError::PolicyViolation(format!(
+                "Signing request {id} was not approved within {}s",
+                timeout.as_secs()
+            ))),
+        }
+    }
+}
+
+/// Wraps a signer so every request needs operator approval
+pub struct ApprovalSigner<S> {
+    inner: S,
+    queue: ApprovalQueue,
+    config: ApprovalConfig,
+}
+
+impl<S: TrezoaSigner> std::fmt::Debug for ApprovalSigner<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApprovalSigner")
+            .field("pubkey", &self.inner.pubkey())
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: TrezoaSigner> ApprovalSigner<S> {
+    pub fn new(inner: S, queue: ApprovalQueue, config: ApprovalConfig) -> Self {
+        Self {
+            inner,
+            queue,
+            config,
+        }
+    }
+
+    /// The wrapped signer
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// The queue operators decide requests on
+    pub fn queue(&self) -> &ApprovalQueue {
+        &self.queue
+    }
+
+    /// Queue a request and wait for its approval
+    ///
+    /// # Returns
+    ///
+    /// The request id, to record the resulting signature against
+    async fn await_approval(
+        &self,
+        kind: ApprovalKind,
+        summary: Option<TransactionSummary>,
+        payload: &[u8],
+    ) -> Result<String, SignerError> {
+        let created_at = now_secs();
+        let request = ApprovalRequest {
+            id: uuid::Uuid::new_v4().to_string(),
+            signer: self.inner.pubkey().to_string(),
+            kind,
+            summary,
+            payload: STANDARD.encode(payload),
+            message_hash: hashv(&[payload]).to_string(),
+            created_at,
+            expires_at: created_at.saturating_add(self.config.timeout.as_secs()),
+            status: ApprovalStatus::Pending,
+            approvals: vec![],
+            signature: None,
+        };
+        let id = request.id.clone();
+
+        self.queue.submit(request, self.config.timeout).await?;
+        Ok(id)
+    }
+
+    /// Store the signature next to the approvals that released it
+    async fn record_signature(&self, id: &str, signature: &Signature) -> Result<(), SignerError> {
+        self.queue
+            .store
+            .set_signature(id, signature.to_string())
+            .await
+    }
+
+    async fn sign_request(&self, request: SigningRequest) -> Result<SigningResponse, SignerError> {
+        let (kind, summary) = match &request {
+            SigningRequest::Transaction(tx) => (
+                ApprovalKind::Transaction,
+                Some(TransactionSummary::from_transaction(tx)),
+            ),
+            SigningRequest::PartialTransaction(tx) => (
+                ApprovalKind::PartialTransaction,
+                Some(TransactionSummary::from_transaction(tx)),
+            ),
+            SigningRequest::VersionedTransaction(tx) => (
+                ApprovalKind::VersionedTransaction,
+                Some(TransactionSummary::from_versioned_transaction(tx)),
+            ),
+            SigningRequest::Message(message) => {
+                // Don't ask an operator to approve what the signer will refuse anyway
+                TransactionUtil::ensure_not_transaction_message(message, &self.pubkey())?;
+                (ApprovalKind::Message, None)
+            }
+            SigningRequest::UncheckedMessage(message) => (
+                ApprovalKind::UncheckedMessage,
+                // Show raw transaction message bytes decoded, so they aren't approved blind
+                TransactionUtil::parse_transaction_message(message).map(|message| {
+                    TransactionSummary::from_versioned_transaction(&VersionedTransaction {
+                        signatures: vec![],
+                        message,
+                    })
+                }),
+            ),
+            SigningRequest::OffchainMessage(_) => (ApprovalKind::Message, None),
+            SigningRequest::SerializedTransaction(_) => unreachable!("decoded above"),
+        };
+
+        let id = self
+            .await_approval(kind, summary, &request.message_bytes()?)
+            .await?;
+        let response = self.inner.sign(request).await?;
+        self.record_signature(&id, &response.signature()).await?;
+        Ok(response)
+    }
+}
+
+crate::traits::forward_to_sign!(ApprovalSigner);
+
+#[cfg(all(test, feature = "memory"))]
+mod tests {
+    use super::*;
+    use crate::memory::MemorySigner;
+    use crate::sdk_adapter::{Keypair, Signer};
+    use crate::test_util::create_test_transaction;
+
+    fn approval_signer(timeout: Duration) -> Arc<ApprovalSigner<MemorySigner>> {
+        Arc::new(ApprovalSigner::new(
+            MemorySigner::new(Keypair::new()),
+            ApprovalQueue::new(Arc::new(MemoryApprovalStore::new())),
+            ApprovalConfig { timeout },
+        ))
+    }
+
+    /// Wait until the signer has queued a request
+    async fn next_pending(queue: &ApprovalQueue) -> ApprovalRequest {
+        loop {
+            if let Some(request) = queue.list_pending().await.unwrap().pop() {
+                return request;
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction_after_approval() {
+        let signer = approval_signer(Duration::from_secs(60));
+        let mut tx = create_test_transaction(&signer.pubkey());
+
+        let pending = tokio::spawn({
+            let signer = signer.clone();
+            async move { signer.sign_transaction(&mut tx).await }
+        });
+
+        let request = next_pending(signer.queue()).await;
+        assert_eq!(request.kind, ApprovalKind::Transaction);
+        assert_eq!(request.signer, signer.pubkey().to_string());
+        let summary = request.summary.as_ref().unwrap();
+        assert_eq!(summary.fee_payer, signer.pubkey().to_string());
+        assert_eq!(summary.instructions.len(), 1);
+
+        let approved = signer.queue().approve(&request.id).await.unwrap();
+        assert_eq!(approved.status, ApprovalStatus::Approved);
+
+        let (_, signature) = pending.await.unwrap().unwrap();
+        assert_ne!(signature, Signature::default());
+        assert!(signer.queue().list_pending().await.unwrap().is_empty());
+        let stored = signer.queue().get(&request.id).await.unwrap().unwrap();
+        assert_eq!(stored.signature, Some(signature.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_quorum_releases_after_threshold() {
+        let approvers: Vec<Keypair> = (0..3).map(|_| Keypair::new()).collect();
+        let quorum = Quorum::new(approvers.iter().map(|k| k.pubkey()).collect(), 2).unwrap();
+        let signer = Arc::new(ApprovalSigner::new(
+            MemorySigner::new(Keypair::new()),
+            ApprovalQueue::with_quorum(Arc::new(MemoryApprovalStore::new()), quorum),
+            ApprovalConfig::default(),
+        ));
+
+        let pending = tokio::spawn({
+            let signer = signer.clone();
+            async move { signer.sign_message(b"withdraw").await }
+        });
+
+        let queue = signer.queue();
+        let request = next_pending(queue).await;
+        let approval_message = request.approval_message().unwrap();
+        assert!(queue.approve(&request.id).await.is_err());
+
+        // Signatures from outsiders or over other bytes don't count
+        let outsider = Keypair::new();
+        let outsider_signature = outsider.sign_message(&approval_message);
+        assert!(queue
+            .add_approval(&request.id, &outsider.pubkey(), &outsider_signature)
+            .await
+            .is_err());
+        let wrong_signature = approvers[0].sign_message(b"withdraw");
+        assert!(queue
+            .add_approval(&request.id, &approvers[0].pubkey(), &wrong_signature)
+            .await
+            .is_err());
+
+        // The same approver twice is one approval
+        let first = approvers[0].sign_message(&approval_message);
+        for _ in 0..2 {
+            let updated = queue
+                .add_approval(&request.id, &approvers[0].pubkey(), &first)
+                .await
+                .unwrap();
+            assert_eq!(updated.status, ApprovalStatus::Pending);
+            assert_eq!(updated.approvals.len(), 1);
+        }
+
+        let second = approvers[2].sign_message(&approval_message);
+        let approved = queue
+            .add_approval(&request.id, &approvers[2].pubkey(), &second)
+            .await
+            .unwrap();
+        assert_eq!(approved.status, ApprovalStatus::Approved);
+
+        let signature = pending.await.unwrap().unwrap();
+        let evidence = queue.get(&request.id).await.unwrap().unwrap();
+        assert_eq!(evidence.signature, Some(signature.to_string()));
+        assert_eq!(
+            evidence.approvals,
+            [
+                Approval {
+                    approver: approvers[0].pubkey().to_string(),
+                    signature: first.to_string(),
+                },
+                Approval {
+                    approver: approvers[2].pubkey().to_string(),
+                    signature: second.to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quorum_rejects_approval_for_another_request() {
+        let approver = Keypair::new();
+        let quorum = Quorum::new(vec![approver.pubkey()], 1).unwrap();
+        let signer = Arc::new(ApprovalSigner::new(
+            MemorySigner::new(Keypair::new()),
+            ApprovalQueue::with_quorum(Arc::new(MemoryApprovalStore::new()), quorum),
+            ApprovalConfig::default(),
+        ));
+
+        // Two requests for the same message
+        for _ in 0..2 {
+            tokio::spawn({
+                let signer = signer.clone();
+                async move { signer.sign_message(b"withdraw").await }
+            });
+        }
+        let queue = signer.queue();
+        let requests = loop {
+            let pending = queue.list_pending().await.unwrap();
+            if pending.len() == 2 {
+                break pending;
+            }
+            tokio::task::yield_now().await;
+        };
+        assert_eq!(requests[0].message_hash, requests[1].message_hash);
+
+        let approval = approver.sign_message(&requests[0].approval_message().unwrap());
+        assert!(matches!(
+            queue
+                .add_approval(&requests[1].id, &approver.pubkey(), &approval)
+                .await,
+            Err(SignerError::PolicyViolation(_))
+        ));
+
+        let approved = queue
+            .add_approval(&requests[0].id, &approver.pubkey(), &approval)
+            .await
+            .unwrap();
+        assert_eq!(approved.status, ApprovalStatus::Approved);
+        assert!(queue
+            .get(&requests[1].id)
+            .await
+            .unwrap()
+            .unwrap()
+            .is_pending());
+    }
+
+    #[tokio::test]
+    async fn test_rejection_fails_signing() {
+        let signer = approval_signer(Duration::from_secs(60));
+
+        let pending = tokio::spawn({
+            let signer = signer.clone();
+            async move { signer.sign_message(b"hello").await }
+        });
+
+        let request = next_pending(signer.queue()).await;
+        assert_eq!(request.payload, STANDARD.encode(b"hello"));
+        signer
+            .queue()
+            .reject(&request.id, Some("unknown recipient".to_string()))
+            .await
+            .unwrap();
+
+        match pending.await.unwrap() {
+            Err(SignerError::PolicyViolation(message)) => {
+                assert!(message.contains("unknown recipient"))
+            }
+            other => panic!("Expected rejection, got {other:?}"),
+        }
+        // A decided request cannot be decided again
+        assert!(signer.queue().approve(&request.id).await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_timeout_fails_signing() {
+        let signer = approval_signer(Duration::from_secs(30));
+
+        let result = signer.sign_message(b"hello").await;
+
+        assert!(matches!(result, Err(SignerError::PolicyViolation(_))));
+        let requests = signer.queue().store.list().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].status, ApprovalStatus::Expired);
+        assert!(signer.queue().approve(&requests[0].id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unchecked_transaction_message_is_summarised() {
+        let signer = approval_signer(Duration::from_secs(60));
+        let message = create_test_transaction(&signer.pubkey()).message_data();
+
+        let pending = tokio::spawn({
+            let signer = signer.clone();
+            let message = message.clone();
+            async move { signer.sign_message_unchecked(&message).await }
+        });
+
+        let request = next_pending(signer.queue()).await;
+        assert_eq!(request.kind, ApprovalKind::UncheckedMessage);
+        let summary = request.summary.as_ref().unwrap();
+        assert_eq!(summary.fee_payer, signer.pubkey().to_string());
+        assert_eq!(summary.instructions.len(), 1);
+
+        signer.queue().approve(&request.id).await.unwrap();
+        let signature = pending.await.unwrap().unwrap();
+        assert!(signature.verify(signer.pubkey().as_ref(), &message));
+    }
+
+    #[tokio::test]
+    async fn test_transaction_message_is_refused_before_queueing() {
+        let signer = approval_signer(Duration::from_secs(60));
+        let tx = create_test_transaction(&signer.pubkey());
+
+        assert!(signer.sign_message(&tx.message_data()).await.is_err());
+        assert!(signer.queue().store.list().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+//! M-of-N approver quorum
+
+use crate::error::SignerError;
+use crate::sdk_adapter::{Hash, Pubkey, Signature};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Domain prepended to every approval message, so approver keys never sign
+/// anything that could be mistaken for a transaction or off-chain message
+pub const APPROVAL_DOMAIN: &[u8] = b"trezoa-keychain approval v2";
+
+/// Bytes an approver signs to approve a request
+///
+/// Layout: [`APPROVAL_DOMAIN`], 32-byte signer public key, 32-byte message hash,
+/// little-endian u64 expiry, then the UTF-8 request id. Binding the request id and
+/// signer keeps an approval from being replayed for another request or signer.
+pub fn approval_message(
+    request_id: &str,
+    signer: &Pubkey,
+    message_hash: &Hash,
+    expires_at: u64,
+) -> Vec<u8> {
+    let mut data = Vec::with_capacity(APPROVAL_DOMAIN.len() + 32 + 32 + 8 + request_id.len());
+    data.extend_from_slice(APPROVAL_DOMAIN);
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(message_hash.as_ref());
+    data.extend_from_slice(&expires_at.to_le_bytes());
+    data.extend_from_slice(request_id.as_bytes());
+    data
+}
+
+/// An approver's signature over a request, kept as evidence
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Approval {
+    /// Base58-encoded approver public key
+    pub approver: String,
+    /// Base58-encoded Ed25519 signature over the approval message
+    pub signature: String,
+}
+
+/// Approver keys and the number of them that must approve each request
+#[derive(Debug, Clone)]
+pub struct Quorum {
+    approvers: Vec<Pubkey>,
+    threshold: usize,
+}
+
+impl Quorum {
+    /// Require `threshold` distinct approvals out of `approvers`
+    pub fn new(approvers: Vec<Pubkey>, threshold: usize) -> Result<Self, SignerError> {
+        let unique: HashSet<_> = approvers.iter().collect();
+        if unique.len() != approvers.len() {
+            return Err(SignerError::ConfigError(
+                "Duplicate approver public key".to_string(),
+            ));
+        }
+        if threshold == 0 || threshold > approvers.len() {
+            return Err(SignerError::ConfigError(format!(
+                "Approval threshold must be between 1 and {}",
+                approvers.len()
+            )));
+        }
+
+        Ok(Self {
+            approvers,
+            threshold,
+        })
+    }
+
+    pub fn approvers(&self) -> &[Pubkey] {
+        &self.approvers
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Check that `approver` is in the quorum and signed `approval_message`
+    pub fn verify(
+        &self,
+        approver: &Pubkey,
+        signature: &Signature,
+        approval_message: &[u8],
+    ) -> Result<(), SignerError> {
+        if !self.approvers.contains(approver) {
+            return Err(SignerError::PolicyViolation(format!(
+                "{approver} is not an approver"
+            )));
+        }
+        if !signature.verify(approver.as_ref(), approval_message) {
+            return Err(SignerError::PolicyViolation(format!(
+                "Invalid approval signature from {approver}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quorum_rejects_invalid_config() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+
+        assert!(Quorum::new(vec![a, b], 0).is_err());
+        assert!(Quorum::new(vec![a, b], 3).is_err());
+        assert!(Quorum::new(vec![a, a], 1).is_err());
+        assert_eq!(Quorum::new(vec![a, b], 2).unwrap().threshold(), 2);
+    }
+
+    #[test]
+    fn test_approval_message_layout() {
+        let signer = Pubkey::new_from_array([5u8; 32]);
+        let hash = Hash::new_from_array([7u8; 32]);
+        let data = approval_message("req-1", &signer, &hash, 1);
+
+        let data = data.strip_prefix(APPROVAL_DOMAIN).unwrap();
+        assert_eq!(&data[..32], &[5u8; 32]);
+        assert_eq!(&data[32..64], &[7u8; 32]);
+        assert_eq!(&data[64..72], &1u64.to_le_bytes());
+        assert_eq!(&data[72..], b"req-1");
+    }
+
+    #[test]
+    fn test_approval_message_binds_request_and_signer() {
+        let signer = Pubkey::new_unique();
+        let hash = Hash::new_from_array([7u8; 32]);
+        let message = approval_message("req-1", &signer, &hash, 1);
+
+        assert_ne!(message, approval_message("req-2", &signer, &hash, 1));
+        assert_ne!(
+            message,
+            approval_message("req-1", &Pubkey::new_unique(), &hash, 1)
+        );
+    }
+}
@@ -0,0 +1,337 @@
+//! Persistence for approval requests
+
+use super::{Approval, ApprovalRequest, ApprovalStatus};
+use crate::error::SignerError;
+use crate::store_util;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Storage for approval requests
+///
+/// Resolved requests are kept with their final status as an audit trail.
+#[async_trait]
+pub trait ApprovalStore: Send + Sync {
+    /// Store a new pending request
+    async fn insert(&self, request: ApprovalRequest) -> Result<(), SignerError>;
+
+    /// Look up a request by id
+    async fn get(&self, id: &str) -> Result<Option<ApprovalRequest>, SignerError>;
+
+    /// All stored requests, oldest first
+    async fn list(&self) -> Result<Vec<ApprovalRequest>, SignerError>;
+
+    /// Move a pending request to `status`
+    ///
+    /// Implementations must make this atomic, so a request is resolved at most once.
+    ///
+    /// # Returns
+    ///
+    /// The updated request, or `None` if no pending request has this id
+    async fn resolve(
+        &self,
+        id: &str,
+        status: ApprovalStatus,
+    ) -> Result<Option<ApprovalRequest>, SignerError>;
+
+    /// Add an approver signature to a pending request
+    ///
+    /// Implementations must make this atomic and keep one approval per approver.
+    ///
+    /// # Returns
+    ///
+    /// The updated request, or `None` if no pending request has this id
+    async fn add_approval(
+        &self,
+        id: &str,
+        approval: Approval,
+    ) -> Result<Option<ApprovalRequest>, SignerError>;
+
+    /// Record the signature produced after the request was approved
+    async fn set_signature(&self, id: &str, signature: String) -> Result<(), SignerError>;
+}
+
+fn resolve_in(
+    requests: &mut HashMap<String, ApprovalRequest>,
+    id: &str,
+    status: ApprovalStatus,
+) -> Option<ApprovalRequest> {
+    let request = requests.get_mut(id)?;
+    if request.status != ApprovalStatus::Pending {
+        return None;
+    }
+    request.status = status;
+    Some(request.clone())
+}
+
+fn add_approval_in(
+    requests: &mut HashMap<String, ApprovalRequest>,
+    id: &str,
+    approval: Approval,
+) -> Option<ApprovalRequest> {
+    let request = requests.get_mut(id)?;
+    if request.status != ApprovalStatus::Pending {
+        return None;
+    }
+    if !request
+        .approvals
+        .iter()
+        .any(|existing| existing.approver == approval.approver)
+    {
+        request.approvals.push(approval);
+    }
+    Some(request.clone())
+}
+
+fn set_signature_in(requests: &mut HashMap<String, ApprovalRequest>, id: &str, signature: String) {
+    if let Some(request) = requests.get_mut(id) {
+        request.signature = Some(signature);
+    }
+}
+
+fn sorted(requests: impl Iterator<Item = ApprovalRequest>) -> Vec<ApprovalRequest> {
+    let mut requests: Vec<_> = requests.collect();
+    requests.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+    requests
+}
+
+/// In-memory approval store, lost on restart
+#[derive(Debug, Default)]
+pub struct MemoryApprovalStore {
+    requests: Mutex<HashMap<String, ApprovalRequest>>,
+}
+
+impl MemoryApprovalStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn requests(&self) -> std::sync::MutexGuard<'_, HashMap<String, ApprovalRequest>> {
+        store_util::lock(&self.requests)
+    }
+}
+
+#[async_trait]
+impl ApprovalStore for MemoryApprovalStore {
+    async fn insert(&self, request: ApprovalRequest) -> Result<(), SignerError> {
+        self.requests().insert(request.id.clone(), request);
+        Ok(())
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<ApprovalRequest>, SignerError> {
+        Ok(self.requests().get(id).cloned())
+    }
+
+    async fn list(&self) -> Result<Vec<ApprovalRequest>, SignerError> {
+        Ok(sorted(self.requests().values().cloned()))
+    }
+
+    async fn resolve(
+        &self,
+        id: &str,
+        status: ApprovalStatus,
+    ) -> Result<Option<ApprovalRequest>, SignerError> {
+        Ok(resolve_in(&mut self.requests(), id, status))
+    }
+
+    async fn add_approval(
+        &self,
+        id: &str,
+        approval: Approval,
+    ) -> Result<Option<ApprovalRequest>, SignerError> {
+        Ok(add_approval_in(&mut self.requests(), id, approval))
+    }
+
+    async fn set_signature(&self, id: &str, signature: String) -> Result<(), SignerError> {
+        set_signature_in(&mut self.requests(), id, signature);
+        Ok(())
+    }
+}
+
+/// Approval store persisted to a JSON file
+#[derive(Debug)]
+pub struct FileApprovalStore {
+    path: PathBuf,
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl FileApprovalStore {
+    /// Create a store backed by `path`; the file is created on first write
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    async fn load(&self) -> Result<HashMap<String, ApprovalRequest>, SignerError> {
+        store_util::read_json(&self.path).await
+    }
+
+    async fn save(&self, requests: &HashMap<String, ApprovalRequest>) -> Result<(), SignerError> {
+        store_util::write_json(&self.path, requests).await
+    }
+}
+
+#[async_trait]
+impl ApprovalStore for FileApprovalStore {
+    async fn insert(&self, request: ApprovalRequest) -> Result<(), SignerError> {
+        let _guard = self.lock.lock().await;
+        let mut requests = self.load().await?;
+        requests.insert(request.id.clone(), request);
+        self.save(&requests).await
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<ApprovalRequest>, SignerError> {
+        let _guard = self.lock.lock().await;
+        Ok(self.load().await?.remove(id))
+    }
+
+    async fn list(&self) -> Result<Vec<ApprovalRequest>, SignerError> {
+        let _guard = self.lock.lock().await;
+        Ok(sorted(self.load().await?.into_values()))
+    }
+
+    async fn resolve(
+        &self,
+        id: &str,
+        status: ApprovalStatus,
+    ) -> Result<Option<ApprovalRequest>, SignerError> {
+        let _guard = self.lock.lock().await;
+        let mut requests = self.load().await?;
+        let resolved = resolve_in(&mut requests, id, status);
+        if resolved.is_some() {
+            self.save(&requests).await?;
+        }
+        Ok(resolved)
+    }
+
+    async fn add_approval(
+        &self,
+        id: &str,
+        approval: Approval,
+    ) -> Result<Option<ApprovalRequest>, SignerError> {
+        let _guard = self.lock.lock().await;
+        let mut requests = self.load().await?;
+        let updated = add_approval_in(&mut requests, id, approval);
+        if updated.is_some() {
+            self.save(&requests).await?;
+        }
+        Ok(updated)
+    }
+
+    async fn set_signature(&self, id: &str, signature: String) -> Result<(), SignerError> {
+        let _guard = self.lock.lock().await;
+        let mut requests = self.load().await?;
+        set_signature_in(&mut requests, id, signature);
+        self.save(&requests).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approval::ApprovalKind;
+
+    fn request(id: &str, created_at: u64) -> ApprovalRequest {
+        ApprovalRequest {
+            id: id.to_string(),
+            signer: "signer".to_string(),
+            kind: ApprovalKind::Message,
+            summary: None,
+            payload: "aGk=".to_string(),
+            message_hash: "hash".to_string(),
+            created_at,
+            expires_at: created_at + 60,
+            status: ApprovalStatus::Pending,
+            approvals: vec![],
+            signature: None,
+        }
+    }
+
+    fn approval(approver: &str) -> Approval {
+        Approval {
+            approver: approver.to_string(),
+            signature: "sig".to_string(),
+        }
+    }
+
+    async fn exercise_store(store: &dyn ApprovalStore) {
+        store.insert(request("b", 2)).await.unwrap();
+        store.insert(request("a", 1)).await.unwrap();
+
+        let ids: Vec<_> = store
+            .list()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(ids, ["a", "b"]);
+
+        store.add_approval("a", approval("x")).await.unwrap();
+        let approved_once = store
+            .add_approval("a", approval("x"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(approved_once.approvals, [approval("x")]);
+
+        let approved = store
+            .resolve("a", ApprovalStatus::Approved)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(approved.status, ApprovalStatus::Approved);
+
+        // A request is resolved only once
+        assert!(store
+            .resolve("a", ApprovalStatus::Expired)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(store
+            .resolve("missing", ApprovalStatus::Approved)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(store
+            .add_approval("a", approval("y"))
+            .await
+            .unwrap()
+            .is_none());
+
+        store
+            .set_signature("a", "result".to_string())
+            .await
+            .unwrap();
+        let stored = store.get("a").await.unwrap().unwrap();
+        assert_eq!(stored.status, ApprovalStatus::Approved);
+        assert_eq!(stored.signature.as_deref(), Some("result"));
+    }
+
+    #[tokio::test]
+    async fn test_memory_store() {
+        exercise_store(&MemoryApprovalStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_file_store_persists() {
+        let path = std::env::temp_dir().join(format!(
+            "trezoa-keychain-approvals-{}.json",
+            rand::random::<u64>()
+        ));
+
+        exercise_store(&FileApprovalStore::new(&path)).await;
+
+        let reopened = FileApprovalStore::new(&path);
+        assert_eq!(reopened.list().await.unwrap().len(), 2);
+        assert_eq!(
+            reopened.get("b").await.unwrap().unwrap().status,
+            ApprovalStatus::Pending
+        );
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,6 +1,6 @@
 //! AWS KMS signer integration using EdDSA (Ed25519) signing
 
-use crate::sdk_adapter::{Pubkey, Signature, Transaction};
+use crate::sdk_adapter::{Pubkey, Signature, Transaction, VersionedTransaction};
 use crate::traits::SignedTransaction;
 use crate::{error::SignerError, traits::TrezoaSigner, transaction_util::TransactionUtil};
 use aws_config::Region;
@@ -30,6 +30,7 @@ pub struct KmsSigner {
     key_id: String,
     public_key: Pubkey,
     region: Option<String>,
+    validate_transactions: bool,
 }
 
 impl std::fmt::Debug for KmsSigner {
@@ -77,6 +78,7 @@ impl KmsSigner {
             key_id,
             public_key: pubkey,
             region,
+            validate_transactions: true,
         })
     }
 
@@ -102,6 +104,7 @@ impl KmsSigner {
             key_id,
             public_key: pubkey,
             region: None,
+            validate_transactions: true,
         })
     }
 
@@ -110,6 +113,12 @@ impl KmsSigner {
         &self.key_id
     }
 
+    /// Enable or disable [`TransactionUtil::validate_for_signing`] (enabled by default)
+    pub fn with_transaction_validation(mut self, enabled: bool) -> Self {
+        self.validate_transactions = enabled;
+        self
+    }
+
     /// Sign message bytes using AWS KMS EdDSA signing
     async fn sign_bytes(&self, message: &[u8]) -> Result<Signature, SignerError> {
         // AWS KMS Sign operation for EdDSA
@@ -162,6 +171,10 @@ impl KmsSigner {
         &self,
         transaction: &mut Transaction,
     ) -> Result<SignedTransaction, SignerError> {
+        if self.validate_transactions {
+            TransactionUtil::validate_for_signing(transaction, &self.public_key)?;
+        }
+
         let signature = self.sign_bytes(&transaction.message_data()).await?;
 
         TransactionUtil::add_signature_to_transaction(transaction, &self.public_key, signature)?;
@@ -172,6 +185,28 @@ impl KmsSigner {
         ))
     }
 
+    async fn sign_versioned_and_serialize(
+        &self,
+        transaction: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        if self.validate_transactions {
+            TransactionUtil::validate_versioned_for_signing(transaction, &self.public_key)?;
+        }
+
+        let signature = self.sign_bytes(&transaction.message.serialize()).await?;
+
+        TransactionUtil::add_signature_to_versioned_transaction(
+            transaction,
+            &self.public_key,
+            signature,
+        )?;
+
+        Ok((
+            TransactionUtil::serialize_versioned_transaction(transaction)?,
+            signature,
+        ))
+    }
+
     /// Check if AWS KMS is available and the key is accessible
     async fn check_availability(&self) -> bool {
         // Try to describe the key as a health check
@@ -209,6 +244,11 @@ impl TrezoaSigner for KmsSigner {
     }
 
     async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        TransactionUtil::ensure_not_transaction_message(message, &self.pubkey())?;
+        self.sign_bytes(message).await
+    }
+
+    async fn sign_message_unchecked(&self, message: &[u8]) -> Result<Signature, SignerError> {
         self.sign_bytes(message).await
     }
 
@@ -219,6 +259,13 @@ impl TrezoaSigner for KmsSigner {
         self.sign_and_serialize(tx).await
     }
 
+    async fn sign_versioned_transaction(
+        &self,
+        tx: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        self.sign_versioned_and_serialize(tx).await
+    }
+
     async fn is_available(&self) -> bool {
         self.check_availability().await
     }
@@ -0,0 +1,197 @@
+//! Signature result cache
+//!
+//! Remote backends can take seconds per signature and consume API quota. The
+//! cache returns a signature obtained earlier for the same (pubkey, message)
+//! without calling the backend. Cached signatures are verified before use, so a
+//! corrupted store can never produce an invalid signature.
+
+mod store;
+
+pub use store::{
+    FileSignatureCache, LruSignatureCache, SignatureCacheStore, DEFAULT_FILE_CACHE_TTL,
+};
+
+use crate::error::SignerError;
+use crate::sdk_adapter::hashv;
+use crate::traits::{SigningRequest, SigningResponse, TrezoaSigner};
+use crate::transaction_util::TransactionUtil;
+use std::sync::Arc;
+
+/// Wraps a signer with a [`SignatureCacheStore`]
+pub struct CachedSigner<S> {
+    inner: S,
+    store: Arc<dyn SignatureCacheStore>,
+}
+
+impl<S: TrezoaSigner> std::fmt::Debug for CachedSigner<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedSigner")
+            .field("pubkey", &self.inner.pubkey())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: TrezoaSigner> CachedSigner<S> {
+    pub fn new(inner: S, store: Arc<dyn SignatureCacheStore>) -> Self {
+        Self { inner, store }
+    }
+
+    /// The wrapped signer
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Return a verified cached signature for the request, or sign it with the wrapped
+    /// signer
+    async fn sign_request(&self, request: SigningRequest) -> Result<SigningResponse, SignerError> {
+        let pubkey = self.inner.pubkey();
+
+        // A cache hit must not skip the blind-signing guard of the wrapped signer
+        if let SigningRequest::Message(message) = &request {
+            TransactionUtil::ensure_not_transaction_message(message, &pubkey)?;
+        }
+
+        let message = request.message_bytes()?;
+        let message_hash = hashv(&[&message]);
+
+        if let Some(signature) = self.store.get(&pubkey, &message_hash).await? {
+            if signature.verify(pubkey.as_ref(), &message) {
+                return request.into_response(&pubkey, signature);
+            }
+            log::warn!("Discarding cached signature that does not verify for {pubkey}");
+            self.store.remove(&pubkey, &message_hash).await?;
+        }
+
+        let response = self.inner.sign(request).await?;
+        self.store
+            .insert(&pubkey, &message_hash, response.signature())
+            .await?;
+        Ok(response)
+    }
+}
+
+crate::traits::forward_to_sign!(CachedSigner);
+
+#[cfg(all(test, feature = "memory"))]
+mod tests {
+    use super::*;
+    use crate::memory::MemorySigner;
+    use crate::sdk_adapter::{Hash, Keypair, Pubkey, Signature, Transaction};
+    use crate::test_util::create_test_transaction;
+    use crate::traits::SignedTransaction;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Memory signer that counts backend calls
+    struct CountingSigner {
+        inner: MemorySigner,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl TrezoaSigner for CountingSigner {
+        fn pubkey(&self) -> Pubkey {
+            self.inner.pubkey()
+        }
+
+        async fn sign_transaction(
+            &self,
+            tx: &mut Transaction,
+        ) -> Result<SignedTransaction, SignerError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.inner.sign_transaction(tx).await
+        }
+
+        async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.inner.sign_message(message).await
+        }
+
+        async fn sign_partial_transaction(
+            &self,
+            tx: &mut Transaction,
+        ) -> Result<SignedTransaction, SignerError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.inner.sign_partial_transaction(tx).await
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    fn cached(store: Arc<dyn SignatureCacheStore>) -> CachedSigner<CountingSigner> {
+        CachedSigner::new(
+            CountingSigner {
+                inner: MemorySigner::new(Keypair::new()),
+                calls: AtomicUsize::new(0),
+            },
+            store,
+        )
+    }
+
+    fn calls(signer: &CachedSigner<CountingSigner>) -> usize {
+        signer.inner().calls.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn test_returns_cached_transaction_signature() {
+        let signer = cached(Arc::new(LruSignatureCache::new(16)));
+        let mut tx = create_test_transaction(&signer.pubkey());
+        let mut retry = tx.clone();
+
+        let (serialized, signature) = signer.sign_transaction(&mut tx).await.unwrap();
+        let (cached_serialized, cached_signature) =
+            signer.sign_transaction(&mut retry).await.unwrap();
+
+        assert_eq!(calls(&signer), 1);
+        assert_eq!(cached_signature, signature);
+        assert_eq!(cached_serialized, serialized);
+        assert_eq!(retry, tx);
+    }
+
+    #[tokio::test]
+    async fn test_returns_cached_message_signature() {
+        let signer = cached(Arc::new(LruSignatureCache::new(16)));
+
+        let signature = signer.sign_message(b"hello").await.unwrap();
+
+        assert_eq!(signer.sign_message(b"hello").await.unwrap(), signature);
+        assert_eq!(calls(&signer), 1);
+        signer.sign_message(b"world").await.unwrap();
+        assert_eq!(calls(&signer), 2);
+    }
+
+    #[tokio::test]
+    async fn test_discards_invalid_cached_signature() {
+        let store = Arc::new(LruSignatureCache::new(16));
+        let signer = cached(store.clone());
+        let message_hash = hashv(&[b"hello".as_slice()]);
+        store
+            .insert(&signer.pubkey(), &message_hash, Signature::from([7u8; 64]))
+            .await
+            .unwrap();
+
+        let signature = signer.sign_message(b"hello").await.unwrap();
+
+        assert_eq!(calls(&signer), 1);
+        assert!(signature.verify(signer.pubkey().as_ref(), b"hello"));
+        assert_eq!(
+            store.get(&signer.pubkey(), &message_hash).await.unwrap(),
+            Some(signature)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache_hit_keeps_blind_signing_guard() {
+        let signer = cached(Arc::new(LruSignatureCache::new(16)));
+        let mut tx = create_test_transaction(&signer.pubkey());
+        tx.message.recent_blockhash = Hash::new_from_array([1u8; 32]);
+        let message = tx.message_data();
+
+        signer.sign_transaction(&mut tx).await.unwrap();
+
+        assert!(signer.sign_message(&message).await.is_err());
+        assert!(signer.sign_message_unchecked(&message).await.is_ok());
+        assert_eq!(calls(&signer), 1);
+    }
+}
@@ -0,0 +1,337 @@
+//! Signature cache stores
+
+use crate::error::SignerError;
+use crate::sdk_adapter::{Hash, Pubkey, Signature};
+use crate::store_util::{self, now_secs};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Storage for signatures keyed by (pubkey, message hash)
+#[async_trait]
+pub trait SignatureCacheStore: Send + Sync {
+    async fn get(
+        &self,
+        pubkey: &Pubkey,
+        message_hash: &Hash,
+    ) -> Result<Option<Signature>, SignerError>;
+
+    async fn insert(
+        &self,
+        pubkey: &Pubkey,
+        message_hash: &Hash,
+        signature: Signature,
+    ) -> Result<(), SignerError>;
+
+    async fn remove(&self, pubkey: &Pubkey, message_hash: &Hash) -> Result<(), SignerError>;
+}
+
+fn cache_key(pubkey: &Pubkey, message_hash: &Hash) -> String {
+    format!("{pubkey}:{message_hash}")
+}
+
+#[derive(Debug, Default)]
+struct LruState {
+    entries: HashMap<String, (Signature, u64)>,
+    /// Last-use tick to key, oldest first
+    order: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+impl LruState {
+    fn touch(&mut self, key: &str) -> Option<Signature> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (signature, last_used) = self.entries.get_mut(key)?;
+        self.order.remove(last_used);
+        *last_used = tick;
+        self.order.insert(tick, key.to_string());
+        Some(*signature)
+    }
+
+    fn insert(&mut self, key: String, signature: Signature, capacity: usize) {
+        self.remove(&key);
+        self.tick += 1;
+        self.order.insert(self.tick, key.clone());
+        self.entries.insert(key, (signature, self.tick));
+
+        while self.entries.len() > capacity {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some((_, last_used)) = self.entries.remove(key) {
+            self.order.remove(&last_used);
+        }
+    }
+}
+
+/// Bounded in-memory cache that evicts the least recently used signature
+#[derive(Debug)]
+pub struct LruSignatureCache {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+impl LruSignatureCache {
+    /// Create a cache holding at most `capacity` signatures
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    /// Number of cached signatures
+    pub fn len(&self) -> usize {
+        self.state().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, LruState> {
+        store_util::lock(&self.state)
+    }
+}
+
+#[async_trait]
+impl SignatureCacheStore for LruSignatureCache {
+    async fn get(
+        &self,
+        pubkey: &Pubkey,
+        message_hash: &Hash,
+    ) -> Result<Option<Signature>, SignerError> {
+        Ok(self.state().touch(&cache_key(pubkey, message_hash)))
+    }
+
+    async fn insert(
+        &self,
+        pubkey: &Pubkey,
+        message_hash: &Hash,
+        signature: Signature,
+    ) -> Result<(), SignerError> {
+        self.state()
+            .insert(cache_key(pubkey, message_hash), signature, self.capacity);
+        Ok(())
+    }
+
+    async fn remove(&self, pubkey: &Pubkey, message_hash: &Hash) -> Result<(), SignerError> {
+        self.state().remove(&cache_key(pubkey, message_hash));
+        Ok(())
+    }
+}
+
+/// How long [`FileSignatureCache`] keeps an entry by default (24 hours)
+pub const DEFAULT_FILE_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileCacheEntry {
+    /// Base58-encoded signature
+    signature: String,
+    /// Unix timestamp (seconds) after which the entry is dropped
+    expires_at: u64,
+}
+
+type FileCacheEntries = HashMap<String, FileCacheEntry>;
+
+fn prune_expired(entries: &mut FileCacheEntries) {
+    let now = now_secs();
+    entries.retain(|_, entry| entry.expires_at > now);
+}
+
+/// Cache persisted to a JSON file, so signatures survive a crash
+///
+/// The file is read once on open and rewritten on every change. Entries expire after
+/// a TTL and are pruned on open and on every write.
+#[derive(Debug)]
+pub struct FileSignatureCache {
+    path: PathBuf,
+    ttl: Duration,
+    entries: tokio::sync::Mutex<FileCacheEntries>,
+}
+
+impl FileSignatureCache {
+    /// Open the cache at `path` with [`DEFAULT_FILE_CACHE_TTL`]
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self, SignerError> {
+        Self::open_with_ttl(path, DEFAULT_FILE_CACHE_TTL).await
+    }
+
+    /// Open the cache at `path`, loading unexpired entries if the file exists
+    pub async fn open_with_ttl(
+        path: impl Into<PathBuf>,
+        ttl: Duration,
+    ) -> Result<Self, SignerError> {
+        let path = path.into();
+        let mut entries: FileCacheEntries = store_util::read_json(&path).await?;
+        prune_expired(&mut entries);
+
+        Ok(Self {
+            path,
+            ttl,
+            entries: tokio::sync::Mutex::new(entries),
+        })
+    }
+
+    async fn save(&self, entries: &mut FileCacheEntries) -> Result<(), SignerError> {
+        prune_expired(entries);
+        store_util::write_json(&self.path, entries).await
+    }
+}
+
+#[async_trait]
+impl SignatureCacheStore for FileSignatureCache {
+    async fn get(
+        &self,
+        pubkey: &Pubkey,
+        message_hash: &Hash,
+    ) -> Result<Option<Signature>, SignerError> {
+        let entries = self.entries.lock().await;
+        entries
+            .get(&cache_key(pubkey, message_hash))
+            .filter(|entry| entry.expires_at > now_secs())
+            .map(|entry| {
+                Signature::from_str(&entry.signature).map_err(|e| {
+                    SignerError::SerializationError(format!("Invalid cached signature: {e}"))
+                })
+            })
+            .transpose()
+    }
+
+    async fn insert(
+        &self,
+        pubkey: &Pubkey,
+        message_hash: &Hash,
+        signature: Signature,
+    ) -> Result<(), SignerError> {
+        let mut entries = self.entries.lock().await;
+        entries.insert(
+            cache_key(pubkey, message_hash),
+            FileCacheEntry {
+                signature: signature.to_string(),
+                expires_at: now_secs().saturating_add(self.ttl.as_secs().max(1)),
+            },
+        );
+        self.save(&mut entries).await
+    }
+
+    async fn remove(&self, pubkey: &Pubkey, message_hash: &Hash) -> Result<(), SignerError> {
+        let mut entries = self.entries.lock().await;
+        if entries.remove(&cache_key(pubkey, message_hash)).is_some() {
+            self.save(&mut entries).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(byte: u8) -> Hash {
+        Hash::new_from_array([byte; 32])
+    }
+
+    fn signature(byte: u8) -> Signature {
+        Signature::from([byte; 64])
+    }
+
+    #[tokio::test]
+    async fn test_lru_evicts_least_recently_used() {
+        let cache = LruSignatureCache::new(2);
+        let pubkey = Pubkey::new_unique();
+
+        cache.insert(&pubkey, &hash(1), signature(1)).await.unwrap();
+        cache.insert(&pubkey, &hash(2), signature(2)).await.unwrap();
+        // Touch 1 so that 2 becomes the oldest
+        assert_eq!(
+            cache.get(&pubkey, &hash(1)).await.unwrap(),
+            Some(signature(1))
+        );
+        cache.insert(&pubkey, &hash(3), signature(3)).await.unwrap();
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&pubkey, &hash(2)).await.unwrap(), None);
+        assert!(cache.get(&pubkey, &hash(1)).await.unwrap().is_some());
+        assert!(cache.get(&pubkey, &hash(3)).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_lru_keys_by_pubkey() {
+        let cache = LruSignatureCache::new(4);
+        let pubkey = Pubkey::new_unique();
+
+        cache.insert(&pubkey, &hash(1), signature(1)).await.unwrap();
+
+        assert_eq!(
+            cache.get(&Pubkey::new_unique(), &hash(1)).await.unwrap(),
+            None
+        );
+        cache.remove(&pubkey, &hash(1)).await.unwrap();
+        assert!(cache.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_file_cache_persists() {
+        let path = std::env::temp_dir().join(format!(
+            "trezoa-keychain-signatures-{}.json",
+            rand::random::<u64>()
+        ));
+        let pubkey = Pubkey::new_unique();
+
+        let cache = FileSignatureCache::open(&path).await.unwrap();
+        cache.insert(&pubkey, &hash(1), signature(1)).await.unwrap();
+        cache.insert(&pubkey, &hash(2), signature(2)).await.unwrap();
+        cache.remove(&pubkey, &hash(2)).await.unwrap();
+
+        let reopened = FileSignatureCache::open(&path).await.unwrap();
+        assert_eq!(
+            reopened.get(&pubkey, &hash(1)).await.unwrap(),
+            Some(signature(1))
+        );
+        assert_eq!(reopened.get(&pubkey, &hash(2)).await.unwrap(), None);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_file_cache_prunes_expired_entries() {
+        let path = std::env::temp_dir().join(format!(
+            "trezoa-keychain-signatures-{}.json",
+            rand::random::<u64>()
+        ));
+        let pubkey = Pubkey::new_unique();
+        let expired = cache_key(&pubkey, &hash(1));
+        std::fs::write(
+            &path,
+            serde_json::json!({
+                expired.clone(): { "signature": signature(1).to_string(), "expiresAt": 1 }
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let cache = FileSignatureCache::open_with_ttl(&path, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(cache.get(&pubkey, &hash(1)).await.unwrap(), None);
+
+        cache.insert(&pubkey, &hash(2), signature(2)).await.unwrap();
+        let stored: FileCacheEntries =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert!(!stored.contains_key(&expired));
+        assert!(stored.contains_key(&cache_key(&pubkey, &hash(2))));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
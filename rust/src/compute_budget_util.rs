@@ -0,0 +1,269 @@
+//! Compute Budget program helpers
+//!
+//! Builds Compute Budget instructions and reads the limits and priority fee a
+//! message requests, so signers can check them before signing.
+
+use crate::error::SignerError;
+use crate::sdk_adapter::{CompiledInstruction, Instruction, Message, Pubkey, VersionedMessage};
+
+/// Compute Budget program ID
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("ComputeBudget111111111111111111111111111111");
+
+/// Compute units granted per instruction when no limit is set
+pub const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+
+/// Maximum compute unit limit of a transaction
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Micro-lamports per lamport, the unit of the compute unit price
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+// Compute Budget instruction discriminants (borsh-encoded u8)
+const REQUEST_HEAP_FRAME_IX: u8 = 1;
+const SET_COMPUTE_UNIT_LIMIT_IX: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE_IX: u8 = 3;
+const SET_LOADED_ACCOUNTS_DATA_SIZE_LIMIT_IX: u8 = 4;
+
+/// Compute budget requested by a message
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ComputeBudgetLimits {
+    /// Explicit `SetComputeUnitLimit` value
+    pub unit_limit: Option<u32>,
+    /// Explicit `SetComputeUnitPrice` value, in micro-lamports per compute unit
+    pub unit_price: Option<u64>,
+    /// Explicit `RequestHeapFrame` size in bytes
+    pub heap_frame_bytes: Option<u32>,
+    /// Explicit `SetLoadedAccountsDataSizeLimit` value in bytes
+    pub loaded_accounts_data_size_limit: Option<u32>,
+    /// Compute unit limit the runtime will apply
+    pub effective_unit_limit: u32,
+}
+
+impl ComputeBudgetLimits {
+    /// Whether the message has any Compute Budget instruction
+    pub fn has_compute_budget_instructions(&self) -> bool {
+        self.unit_limit.is_some()
+            || self.unit_price.is_some()
+            || self.heap_frame_bytes.is_some()
+            || self.loaded_accounts_data_size_limit.is_some()
+    }
+
+    /// Priority fee in lamports: unit price times the effective unit limit, rounded up
+    pub fn priority_fee_lamports(&self) -> u64 {
+        let micro_lamports =
+            self.unit_price.unwrap_or(0) as u128 * self.effective_unit_limit as u128;
+        micro_lamports
+            .div_ceil(MICRO_LAMPORTS_PER_LAMPORT)
+            .try_into()
+            .unwrap_or(u64::MAX)
+    }
+}
+
+pub struct ComputeBudgetUtil;
+
+impl ComputeBudgetUtil {
+    /// Build a `SetComputeUnitLimit` instruction
+    pub fn set_compute_unit_limit_instruction(units: u32) -> Instruction {
+        Self::instruction(SET_COMPUTE_UNIT_LIMIT_IX, &units.to_le_bytes())
+    }
+
+    /// Build a `SetComputeUnitPrice` instruction (micro-lamports per compute unit)
+    pub fn set_compute_unit_price_instruction(micro_lamports: u64) -> Instruction {
+        Self::instruction(SET_COMPUTE_UNIT_PRICE_IX, &micro_lamports.to_le_bytes())
+    }
+
+    /// Build a `RequestHeapFrame` instruction
+    pub fn request_heap_frame_instruction(bytes: u32) -> Instruction {
+        Self::instruction(REQUEST_HEAP_FRAME_IX, &bytes.to_le_bytes())
+    }
+
+    fn instruction(discriminant: u8, args: &[u8]) -> Instruction {
+        let mut data = Vec::with_capacity(1 + args.len());
+        data.push(discriminant);
+        data.extend_from_slice(args);
+        Instruction {
+            program_id: COMPUTE_BUDGET_PROGRAM_ID,
+            accounts: vec![],
+            data,
+        }
+    }
+
+    /// Read the compute budget requested by a message
+    ///
+    /// Fails on malformed or duplicate Compute Budget instructions, which the
+    /// runtime would reject as well.
+    pub fn parse(message: &Message) -> Result<ComputeBudgetLimits, SignerError> {
+        Self::parse_instructions(&message.account_keys, &message.instructions)
+    }
+
+    /// [`parse`](Self::parse) for a versioned message
+    ///
+    /// Program ids are always static account keys, so address lookup tables need not
+    /// be resolved.
+    pub fn parse_versioned(message: &VersionedMessage) -> Result<ComputeBudgetLimits, SignerError> {
+        Self::parse_instructions(message.static_account_keys(), message.instructions())
+    }
+
+    fn parse_instructions(
+        account_keys: &[Pubkey],
+        instructions: &[CompiledInstruction],
+    ) -> Result<ComputeBudgetLimits, SignerError> {
+        let mut limits = ComputeBudgetLimits::default();
+        let mut other_instructions: u32 = 0;
+
+        for ix in instructions {
+            let program_id = account_keys
+                .get(ix.program_id_index as usize)
+                .ok_or_else(|| {
+                    SignerError::SerializationError(
+                        "Invalid program id index in instruction".to_string(),
+                    )
+                })?;
+
+            if *program_id != COMPUTE_BUDGET_PROGRAM_ID {
+                other_instructions = other_instructions.saturating_add(1);
+                continue;
+            }
+
+            let (discriminant, args) = ix.data.split_first().ok_or_else(|| {
+                SignerError::SerializationError("Empty Compute Budget instruction".to_string())
+            })?;
+
+            match *discriminant {
+                REQUEST_HEAP_FRAME_IX => {
+                    Self::set_once(&mut limits.heap_frame_bytes, Self::read_u32(args)?)?
+                }
+                SET_COMPUTE_UNIT_LIMIT_IX => {
+                    Self::set_once(&mut limits.unit_limit, Self::read_u32(args)?)?
+                }
+                SET_COMPUTE_UNIT_PRICE_IX => {
+                    let bytes: [u8; 8] = args.try_into().map_err(|_| {
+                        SignerError::SerializationError(
+                            "Invalid SetComputeUnitPrice instruction data".to_string(),
+                        )
+                    })?;
+                    Self::set_once(&mut limits.unit_price, u64::from_le_bytes(bytes))?
+                }
+                SET_LOADED_ACCOUNTS_DATA_SIZE_LIMIT_IX => Self::set_once(
+                    &mut limits.loaded_accounts_data_size_limit,
+                    Self::read_u32(args)?,
+                )?,
+                other => {
+                    return Err(SignerError::SerializationError(format!(
+                        "Unsupported Compute Budget instruction {other}"
+                    )))
+                }
+            }
+        }
+
+        limits.effective_unit_limit = limits
+            .unit_limit
+            .unwrap_or_else(|| {
+                other_instructions.saturating_mul(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT)
+            })
+            .min(MAX_COMPUTE_UNIT_LIMIT);
+
+        Ok(limits)
+    }
+
+    fn read_u32(args: &[u8]) -> Result<u32, SignerError> {
+        let bytes: [u8; 4] = args.try_into().map_err(|_| {
+            SignerError::SerializationError("Invalid Compute Budget instruction data".to_string())
+        })?;
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn set_once<T>(slot: &mut Option<T>, value: T) -> Result<(), SignerError> {
+        if slot.is_some() {
+            return Err(SignerError::SerializationError(
+                "Duplicate Compute Budget instruction".to_string(),
+            ));
+        }
+        *slot = Some(value);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_transfer_instruction;
+
+    fn message_with(instructions: &[Instruction]) -> Message {
+        let payer = Pubkey::new_unique();
+        let mut all = instructions.to_vec();
+        all.push(create_transfer_instruction(
+            &payer,
+            &Pubkey::new_unique(),
+            1,
+        ));
+        Message::new(&all, Some(&payer))
+    }
+
+    #[test]
+    fn test_parse_without_compute_budget() {
+        let limits = ComputeBudgetUtil::parse(&message_with(&[])).unwrap();
+
+        assert!(!limits.has_compute_budget_instructions());
+        assert_eq!(
+            limits.effective_unit_limit,
+            DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT
+        );
+        assert_eq!(limits.priority_fee_lamports(), 0);
+    }
+
+    #[test]
+    fn test_parse_compute_budget_instructions() {
+        let message = message_with(&[
+            ComputeBudgetUtil::set_compute_unit_limit_instruction(300_000),
+            ComputeBudgetUtil::set_compute_unit_price_instruction(10_000),
+            ComputeBudgetUtil::request_heap_frame_instruction(64 * 1024),
+        ]);
+
+        let limits = ComputeBudgetUtil::parse(&message).unwrap();
+
+        assert_eq!(limits.unit_limit, Some(300_000));
+        assert_eq!(limits.unit_price, Some(10_000));
+        assert_eq!(limits.heap_frame_bytes, Some(64 * 1024));
+        assert_eq!(limits.effective_unit_limit, 300_000);
+        // 300_000 CU * 10_000 micro-lamports = 3_000 lamports
+        assert_eq!(limits.priority_fee_lamports(), 3_000);
+    }
+
+    #[test]
+    fn test_priority_fee_rounds_up() {
+        let message = message_with(&[
+            ComputeBudgetUtil::set_compute_unit_limit_instruction(1),
+            ComputeBudgetUtil::set_compute_unit_price_instruction(1),
+        ]);
+
+        let limits = ComputeBudgetUtil::parse(&message).unwrap();
+
+        assert_eq!(limits.priority_fee_lamports(), 1);
+    }
+
+    #[test]
+    fn test_effective_limit_is_capped() {
+        let message = message_with(&[ComputeBudgetUtil::set_compute_unit_limit_instruction(
+            u32::MAX,
+        )]);
+
+        let limits = ComputeBudgetUtil::parse(&message).unwrap();
+
+        assert_eq!(limits.effective_unit_limit, MAX_COMPUTE_UNIT_LIMIT);
+    }
+
+    #[test]
+    fn test_parse_rejects_duplicates_and_malformed_data() {
+        let duplicate = message_with(&[
+            ComputeBudgetUtil::set_compute_unit_price_instruction(1),
+            ComputeBudgetUtil::set_compute_unit_price_instruction(2),
+        ]);
+        assert!(ComputeBudgetUtil::parse(&duplicate).is_err());
+
+        let mut malformed = ComputeBudgetUtil::set_compute_unit_price_instruction(1);
+        malformed.data.pop();
+        assert!(ComputeBudgetUtil::parse(&message_with(&[malformed])).is_err());
+    }
+}
@@ -0,0 +1,368 @@
+//! Ed25519 program instruction helpers
+//!
+//! The Ed25519 precompile verifies signatures embedded in its instruction data before
+//! any program runs, so on-chain programs can check off-chain attestations by
+//! inspecting the instructions sysvar.
+
+use crate::error::SignerError;
+use crate::sdk_adapter::{Instruction, Pubkey, Signature};
+use crate::traits::TrezoaSigner;
+
+/// Ed25519 signature verification program ID
+pub const ED25519_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("Ed25519SigVerify111111111111111111111111111");
+
+const PUBKEY_SERIALIZED_SIZE: usize = 32;
+const SIGNATURE_SERIALIZED_SIZE: usize = 64;
+const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+// num_signatures (u8) + padding (u8)
+const SIGNATURE_OFFSETS_START: usize = 2;
+// Instruction index meaning "the data lives in this instruction"
+const CURRENT_INSTRUCTION_INDEX: u16 = u16::MAX;
+
+/// A single signature checked by an Ed25519 program instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ed25519SignatureEntry {
+    pub pubkey: Pubkey,
+    pub signature: Signature,
+    pub message: Vec<u8>,
+}
+
+/// Offsets of one signature within the instruction data
+struct SignatureOffsets {
+    signature_offset: u16,
+    signature_instruction_index: u16,
+    public_key_offset: u16,
+    public_key_instruction_index: u16,
+    message_data_offset: u16,
+    message_data_size: u16,
+    message_instruction_index: u16,
+}
+
+impl SignatureOffsets {
+    fn write(&self, data: &mut Vec<u8>) {
+        for value in [
+            self.signature_offset,
+            self.signature_instruction_index,
+            self.public_key_offset,
+            self.public_key_instruction_index,
+            self.message_data_offset,
+            self.message_data_size,
+            self.message_instruction_index,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    fn read(data: &[u8]) -> Self {
+        let field = |i: usize| u16::from_le_bytes([data[i * 2], data[i * 2 + 1]]);
+        Self {
+            signature_offset: field(0),
+            signature_instruction_index: field(1),
+            public_key_offset: field(2),
+            public_key_instruction_index: field(3),
+            message_data_offset: field(4),
+            message_data_size: field(5),
+            message_instruction_index: field(6),
+        }
+    }
+}
+
+pub struct Ed25519Util;
+
+impl Ed25519Util {
+    /// Build an Ed25519 program instruction that verifies the given signatures
+    ///
+    /// All public keys, signatures and messages are stored inline in the instruction.
+    pub fn new_verify_instruction(
+        entries: &[Ed25519SignatureEntry],
+    ) -> Result<Instruction, SignerError> {
+        if entries.is_empty() || entries.len() > u8::MAX as usize {
+            return Err(SignerError::ConfigError(format!(
+                "Ed25519 instruction must contain between 1 and {} signatures, got {}",
+                u8::MAX,
+                entries.len()
+            )));
+        }
+
+        let offsets_size =
+            SIGNATURE_OFFSETS_START + entries.len() * SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+        let payload_size: usize = entries
+            .iter()
+            .map(|e| PUBKEY_SERIALIZED_SIZE + SIGNATURE_SERIALIZED_SIZE + e.message.len())
+            .sum();
+
+        if offsets_size + payload_size > u16::MAX as usize {
+            return Err(SignerError::ConfigError(
+                "Ed25519 instruction data exceeds the maximum offset".to_string(),
+            ));
+        }
+
+        let mut data = Vec::with_capacity(offsets_size + payload_size);
+        data.push(entries.len() as u8);
+        data.push(0);
+
+        let mut payload = Vec::with_capacity(payload_size);
+        for entry in entries {
+            let public_key_offset = offsets_size + payload.len();
+            let signature_offset = public_key_offset + PUBKEY_SERIALIZED_SIZE;
+            let message_data_offset = signature_offset + SIGNATURE_SERIALIZED_SIZE;
+
+            SignatureOffsets {
+                signature_offset: signature_offset as u16,
+                signature_instruction_index: CURRENT_INSTRUCTION_INDEX,
+                public_key_offset: public_key_offset as u16,
+                public_key_instruction_index: CURRENT_INSTRUCTION_INDEX,
+                message_data_offset: message_data_offset as u16,
+                message_data_size: entry.message.len() as u16,
+                message_instruction_index: CURRENT_INSTRUCTION_INDEX,
+            }
+            .write(&mut data);
+
+            payload.extend_from_slice(entry.pubkey.as_ref());
+            payload.extend_from_slice(entry.signature.as_ref());
+            payload.extend_from_slice(&entry.message);
+        }
+        data.extend_from_slice(&payload);
+
+        Ok(Instruction {
+            program_id: ED25519_PROGRAM_ID,
+            accounts: vec![],
+            data,
+        })
+    }
+
+    /// Sign `message` with `signer` and build the matching Ed25519 program instruction
+    pub async fn sign_and_build_instruction(
+        signer: &dyn TrezoaSigner,
+        message: &[u8],
+    ) -> Result<Instruction, SignerError> {
+        Self::sign_and_build_multi_instruction(&[(signer, message)]).await
+    }
+
+    /// Sign each message with its signer and build one Ed25519 program instruction
+    /// verifying all of them
+    pub async fn sign_and_build_multi_instruction(
+        requests: &[(&dyn TrezoaSigner, &[u8])],
+    ) -> Result<Instruction, SignerError> {
+        let mut entries = Vec::with_capacity(requests.len());
+        for (signer, message) in requests {
+            let signature = signer.sign_message(message).await?;
+            let pubkey = signer.pubkey();
+
+            if !signature.verify(pubkey.as_ref(), message) {
+                return Err(SignerError::SigningFailed(format!(
+                    "Signature from {pubkey} does not verify"
+                )));
+            }
+
+            entries.push(Ed25519SignatureEntry {
+                pubkey,
+                signature,
+                message: message.to_vec(),
+            });
+        }
+
+        Self::new_verify_instruction(&entries)
+    }
+
+    /// Parse the signatures checked by an Ed25519 program instruction
+    ///
+    /// Only instructions that keep their data inline are supported; offsets that
+    /// reference other instructions in the transaction are rejected.
+    pub fn parse_verify_instruction(
+        instruction: &Instruction,
+    ) -> Result<Vec<Ed25519SignatureEntry>, SignerError> {
+        if instruction.program_id != ED25519_PROGRAM_ID {
+            return Err(SignerError::SerializationError(
+                "Not an Ed25519 program instruction".to_string(),
+            ));
+        }
+
+        let data = &instruction.data;
+        let num_signatures = *data.first().ok_or_else(|| {
+            SignerError::SerializationError("Empty Ed25519 instruction data".to_string())
+        })? as usize;
+
+        let offsets_end =
+            SIGNATURE_OFFSETS_START + num_signatures * SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+        if num_signatures == 0 || data.len() < offsets_end {
+            return Err(SignerError::SerializationError(
+                "Invalid Ed25519 instruction data length".to_string(),
+            ));
+        }
+
+        (0..num_signatures)
+            .map(|i| {
+                let start = SIGNATURE_OFFSETS_START + i * SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+                let offsets =
+                    SignatureOffsets::read(&data[start..start + SIGNATURE_OFFSETS_SERIALIZED_SIZE]);
+
+                if offsets.signature_instruction_index != CURRENT_INSTRUCTION_INDEX
+                    || offsets.public_key_instruction_index != CURRENT_INSTRUCTION_INDEX
+                    || offsets.message_instruction_index != CURRENT_INSTRUCTION_INDEX
+                {
+                    return Err(SignerError::SerializationError(
+                        "Ed25519 instruction references data in another instruction".to_string(),
+                    ));
+                }
+
+                let pubkey =
+                    Self::read_slice(data, offsets.public_key_offset, PUBKEY_SERIALIZED_SIZE)?;
+                let signature =
+                    Self::read_slice(data, offsets.signature_offset, SIGNATURE_SERIALIZED_SIZE)?;
+                let message = Self::read_slice(
+                    data,
+                    offsets.message_data_offset,
+                    offsets.message_data_size as usize,
+                )?;
+
+                Ok(Ed25519SignatureEntry {
+                    pubkey: Pubkey::try_from(pubkey).map_err(|_| {
+                        SignerError::SerializationError("Invalid public key".to_string())
+                    })?,
+                    signature: Signature::try_from(signature).map_err(|_| {
+                        SignerError::SerializationError("Invalid signature".to_string())
+                    })?,
+                    message: message.to_vec(),
+                })
+            })
+            .collect()
+    }
+
+    /// Parse an Ed25519 program instruction and verify every signature it contains
+    pub fn verify_instruction(
+        instruction: &Instruction,
+    ) -> Result<Vec<Ed25519SignatureEntry>, SignerError> {
+        let entries = Self::parse_verify_instruction(instruction)?;
+
+        for entry in &entries {
+            if !entry
+                .signature
+                .verify(entry.pubkey.as_ref(), &entry.message)
+            {
+                return Err(SignerError::SigningFailed(format!(
+                    "Invalid Ed25519 signature for {}",
+                    entry.pubkey
+                )));
+            }
+        }
+
+        Ok(entries)
+    }
+
+    fn read_slice(data: &[u8], offset: u16, len: usize) -> Result<&[u8], SignerError> {
+        let start = offset as usize;
+        data.get(start..start + len).ok_or_else(|| {
+            SignerError::SerializationError("Ed25519 offset out of bounds".to_string())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdk_adapter::{keypair_pubkey, keypair_sign_message, Keypair};
+
+    fn create_entry(message: &[u8]) -> Ed25519SignatureEntry {
+        let keypair = Keypair::new();
+        Ed25519SignatureEntry {
+            pubkey: keypair_pubkey(&keypair),
+            signature: keypair_sign_message(&keypair, message),
+            message: message.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_new_verify_instruction_layout() {
+        let entry = create_entry(b"attestation");
+
+        let ix = Ed25519Util::new_verify_instruction(std::slice::from_ref(&entry)).unwrap();
+
+        assert_eq!(ix.program_id, ED25519_PROGRAM_ID);
+        assert!(ix.accounts.is_empty());
+        assert_eq!(ix.data[0], 1);
+        assert_eq!(ix.data.len(), 16 + 32 + 64 + entry.message.len());
+        // public key is stored directly after the offsets
+        assert_eq!(&ix.data[16..48], entry.pubkey.as_ref());
+        assert_eq!(&ix.data[48..112], entry.signature.as_ref());
+        assert_eq!(&ix.data[112..], b"attestation");
+    }
+
+    #[test]
+    fn test_round_trip_multiple_signatures() {
+        let entries = vec![create_entry(b"first"), create_entry(b"second message")];
+
+        let ix = Ed25519Util::new_verify_instruction(&entries).unwrap();
+        let parsed = Ed25519Util::verify_instruction(&ix).unwrap();
+
+        assert_eq!(parsed, entries);
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_message() {
+        let entry = create_entry(b"pay 1 TRZ");
+        let mut ix = Ed25519Util::new_verify_instruction(&[entry]).unwrap();
+        let last = ix.data.len() - 1;
+        ix.data[last] ^= 0xFF;
+
+        assert!(Ed25519Util::parse_verify_instruction(&ix).is_ok());
+        assert!(matches!(
+            Ed25519Util::verify_instruction(&ix),
+            Err(SignerError::SigningFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_out_of_bounds() {
+        let mut ix = Ed25519Util::new_verify_instruction(&[create_entry(b"msg")]).unwrap();
+        ix.data.truncate(40);
+
+        assert!(matches!(
+            Ed25519Util::parse_verify_instruction(&ix),
+            Err(SignerError::SerializationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_external_references() {
+        let mut ix = Ed25519Util::new_verify_instruction(&[create_entry(b"msg")]).unwrap();
+        // signature_instruction_index -> instruction 0
+        ix.data[4..6].copy_from_slice(&0u16.to_le_bytes());
+
+        assert!(matches!(
+            Ed25519Util::parse_verify_instruction(&ix),
+            Err(SignerError::SerializationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_new_verify_instruction_empty() {
+        assert!(matches!(
+            Ed25519Util::new_verify_instruction(&[]),
+            Err(SignerError::ConfigError(_))
+        ));
+    }
+
+    #[cfg(feature = "memory")]
+    #[tokio::test]
+    async fn test_sign_and_build_instruction() {
+        use crate::memory::MemorySigner;
+
+        let signer = MemorySigner::new(Keypair::new());
+        let other = MemorySigner::new(Keypair::new());
+
+        let ix = Ed25519Util::sign_and_build_multi_instruction(&[
+            (&signer, b"from signer".as_slice()),
+            (&other, b"from other".as_slice()),
+        ])
+        .await
+        .unwrap();
+
+        let parsed = Ed25519Util::verify_instruction(&ix).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].pubkey, signer.pubkey());
+        assert_eq!(parsed[1].pubkey, other.pubkey());
+        assert_eq!(parsed[1].message, b"from other");
+    }
+}
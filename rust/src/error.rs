@@ -42,11 +42,78 @@ pub enum SignerError {
     #[error("IO error: {0}")]
     IoError(String),
 
+    /// Transaction failed pre-signing sanity checks
+    #[error("Invalid transaction: {0}")]
+    InvalidTransaction(#[from] TransactionValidationError),
+
+    /// A remote signing service returned a response that could not be used
+    #[error("Invalid response: {0}")]
+    InvalidResponse(#[from] ResponseError),
+
+    /// Request rejected by a signing policy
+    #[error("Policy violation: {0}")]
+    PolicyViolation(String),
+
     /// Generic error
     #[error("{0}")]
     Other(String),
 }
 
+/// Reasons a transaction is rejected before signing because it could never land
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TransactionValidationError {
+    /// The recent blockhash (or nonce) is all zeroes
+    #[error("recent blockhash is zero")]
+    ZeroBlockhash,
+
+    /// The signed transaction would not fit in a single packet
+    #[error("transaction is {size} bytes, exceeding the {max}-byte packet limit")]
+    TooLarge { size: usize, max: usize },
+
+    /// The same account key appears more than once
+    #[error("duplicate account key {0}")]
+    DuplicateAccountKey(String),
+
+    /// The signer's key is in the transaction but not as a required signer
+    #[error("signer {0} appears in the transaction as a non-signer")]
+    SignerNotRequired(String),
+
+    /// The signer's key is not in the transaction at all
+    #[error("signer {0} is not in the transaction")]
+    SignerNotFound(String),
+}
+
+/// Reasons a remote signing service's response is rejected
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ResponseError {
+    /// The body is not JSON of the expected shape
+    #[error("malformed {0}")]
+    Malformed(&'static str),
+
+    /// A field needed to complete the request is absent
+    #[error("missing {0}")]
+    MissingField(&'static str),
+
+    /// A field is not valid in its declared encoding
+    #[error("{field} is not valid {encoding}")]
+    InvalidEncoding {
+        field: &'static str,
+        encoding: &'static str,
+    },
+
+    /// A decoded field has the wrong length
+    #[error("{field} is {actual} bytes, expected {expected}")]
+    InvalidLength {
+        field: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+
+    /// The wallet belongs to another chain
+    #[error("wallet chain type is {0}, expected trezoa")]
+    UnexpectedChainType(String),
+}
+
 impl From<std::io::Error> for SignerError {
     fn from(err: std::io::Error) -> Self {
         SignerError::IoError(err.to_string())
@@ -63,7 +130,8 @@ impl From<serde_json::Error> for SignerError {
     feature = "vault",
     feature = "privy",
     feature = "turnkey",
-    feature = "fireblocks"
+    feature = "fireblocks",
+    feature = "rpc"
 ))]
 impl From<reqwest::Error> for SignerError {
     fn from(err: reqwest::Error) -> Self {
@@ -92,6 +160,13 @@ impl fmt::Debug for SignerError {
             SignerError::ConfigError(_) => write!(f, "SignerError::ConfigError([REDACTED])"),
             SignerError::NotAvailable(_) => write!(f, "SignerError::NotAvailable([REDACTED])"),
             SignerError::IoError(_) => write!(f, "SignerError::IoError([REDACTED])"),
+            SignerError::InvalidTransaction(e) => {
+                write!(f, "SignerError::InvalidTransaction({e:?})")
+            }
+            SignerError::InvalidResponse(e) => write!(f, "SignerError::InvalidResponse({e:?})"),
+            SignerError::PolicyViolation(_) => {
+                write!(f, "SignerError::PolicyViolation([REDACTED])")
+            }
             SignerError::Other(_) => write!(f, "SignerError::Other([REDACTED])"),
         }
     }
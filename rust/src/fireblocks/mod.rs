@@ -3,11 +3,13 @@
 mod jwt;
 mod types;
 
-use crate::sdk_adapter::{Pubkey, Signature, Transaction};
+use crate::sdk_adapter::{Pubkey, Signature, Transaction, VersionedTransaction};
 pub use crate::traits::SignedTransaction;
-use crate::{error::SignerError, traits::TrezoaSigner, transaction_util::TransactionUtil};
+use crate::{
+    error::SignerError, nonce_util::NonceUtil, traits::TrezoaSigner,
+    transaction_util::TransactionUtil,
+};
 use base64::{engine::general_purpose::STANDARD, Engine};
-use std::str::FromStr;
 use types::{
     CreateTransactionRequest, CreateTransactionResponse, ExtraParameters,
     ProgramCallExtraParameters, RawExtraParameters, RawMessage, RawMessageData,
@@ -26,7 +28,9 @@ pub struct FireblocksSigner {
     client: reqwest::Client,
     poll_interval_ms: u64,
     max_poll_attempts: u32,
+    nonce_max_poll_attempts: Option<u32>,
     use_program_call: bool,
+    validate_transactions: bool,
 }
 
 impl std::fmt::Debug for FireblocksSigner {
@@ -77,10 +81,27 @@ impl FireblocksSigner {
             client: reqwest::Client::new(),
             poll_interval_ms: config.poll_interval_ms.unwrap_or(1000),
             max_poll_attempts: config.max_poll_attempts.unwrap_or(300),
+            nonce_max_poll_attempts: None,
             use_program_call: config.use_program_call.unwrap_or(false),
+            validate_transactions: true,
         }
     }
 
+    /// Poll attempts for durable nonce transactions (default: `max_poll_attempts`)
+    ///
+    /// Nonce transactions don't expire while waiting in the Fireblocks approval queue, so
+    /// they can be given a longer budget than transactions tied to a recent blockhash.
+    pub fn with_nonce_max_poll_attempts(mut self, attempts: u32) -> Self {
+        self.nonce_max_poll_attempts = Some(attempts);
+        self
+    }
+
+    /// Enable or disable [`TransactionUtil::validate_for_signing`] (enabled by default)
+    pub fn with_transaction_validation(mut self, enabled: bool) -> Self {
+        self.validate_transactions = enabled;
+        self
+    }
+
     /// Initialize the signer by fetching the public key from Fireblocks
     pub async fn init(&mut self) -> Result<(), SignerError> {
         let pubkey = self.fetch_public_key().await?;
@@ -125,28 +146,15 @@ impl FireblocksSigner {
 
         let response_text = response.text().await?;
 
-        let addresses_response: VaultAddressesResponse = serde_json::from_str(&response_text)
-            .map_err(|_e| {
-                #[cfg(feature = "unsafe-debug")]
-                log::error!("Failed to parse Fireblocks response: {_e}");
-
-                #[cfg(not(feature = "unsafe-debug"))]
-                log::error!("Failed to parse Fireblocks response");
-
-                SignerError::SerializationError("Failed to parse Fireblocks response".to_string())
-            })?;
-
-        let address = addresses_response.addresses.first().ok_or_else(|| {
-            SignerError::InvalidPublicKey("Invalid public key from Fireblocks".to_string())
-        })?;
-
-        Pubkey::from_str(&address.address).map_err(|_| {
-            SignerError::InvalidPublicKey("Invalid public key from Fireblocks".to_string())
-        })
+        Ok(VaultAddressesResponse::parse(&response_text)?.pubkey()?)
     }
 
     /// Sign raw bytes using RAW operation
-    async fn sign_raw_bytes(&self, message: &[u8]) -> Result<Signature, SignerError> {
+    async fn sign_raw_bytes(
+        &self,
+        message: &[u8],
+        max_poll_attempts: u32,
+    ) -> Result<Signature, SignerError> {
         let hex_message = hex::encode(message);
 
         let request = CreateTransactionRequest {
@@ -165,13 +173,15 @@ impl FireblocksSigner {
             })),
         };
 
-        self.request_and_poll_signature(request).await
+        self.request_and_poll_signature(request, max_poll_attempts)
+            .await
     }
 
-    /// Sign a transaction using PROGRAM_CALL operation
+    /// Sign a legacy or versioned transaction using PROGRAM_CALL operation
     async fn sign_with_program_call(
         &self,
-        transaction: &Transaction,
+        transaction: &(impl serde::Serialize + Sync),
+        max_poll_attempts: u32,
     ) -> Result<Signature, SignerError> {
         let serialized = bincode::serialize(transaction).map_err(|e| {
             SignerError::SerializationError(format!("Failed to serialize transaction: {e}"))
@@ -190,18 +200,22 @@ impl FireblocksSigner {
             })),
         };
 
-        self.request_and_poll_signature(request).await
+        self.request_and_poll_signature(request, max_poll_attempts)
+            .await
     }
 
     /// Request a signature from Fireblocks and poll until complete
     async fn request_and_poll_signature(
         &self,
         request: CreateTransactionRequest,
+        max_poll_attempts: u32,
     ) -> Result<Signature, SignerError> {
         let create_response = self.create_transaction(request).await?;
-        let tx_response = self.poll_for_signature(&create_response.id).await?;
+        let tx_response = self
+            .poll_for_signature(&create_response.id, max_poll_attempts)
+            .await?;
 
-        self.extract_signature(&tx_response)
+        Ok(tx_response.signature()?)
     }
 
     /// Create a transaction (signing request) in Fireblocks
@@ -244,17 +258,16 @@ impl FireblocksSigner {
 
         let response_text = response.text().await?;
 
-        serde_json::from_str(&response_text).map_err(|_e| {
-            #[cfg(feature = "unsafe-debug")]
-            log::error!("Failed to parse create_transaction response: {_e}, body: {response_text}");
-
-            SignerError::SerializationError("Failed to parse response".to_string())
-        })
+        Ok(CreateTransactionResponse::parse(&response_text)?)
     }
 
     /// Poll for transaction completion
-    async fn poll_for_signature(&self, tx_id: &str) -> Result<TransactionResponse, SignerError> {
-        for _attempt in 0..self.max_poll_attempts {
+    async fn poll_for_signature(
+        &self,
+        tx_id: &str,
+        max_poll_attempts: u32,
+    ) -> Result<TransactionResponse, SignerError> {
+        for _attempt in 0..max_poll_attempts {
             let response = self.get_transaction(tx_id).await?;
 
             match response.status.as_str() {
@@ -277,7 +290,7 @@ impl FireblocksSigner {
 
         Err(SignerError::RemoteApiError(format!(
             "Transaction polling timeout after {} attempts - signing request may still complete",
-            self.max_poll_attempts
+            max_poll_attempts
         )))
     }
 
@@ -317,78 +330,38 @@ impl FireblocksSigner {
 
         let response_text = response.text().await?;
 
-        serde_json::from_str(&response_text).map_err(|e| {
-            #[cfg(feature = "unsafe-debug")]
-            log::error!(
-                "Failed to parse get_transaction response: {}, body: {}",
-                e,
-                response_text
-            );
-            SignerError::SerializationError(format!("Failed to parse response: {e}"))
-        })
-    }
-
-    /// Extract signature from transaction response
-    /// - RAW operations: signature in signed_messages[0].signature.full_sig (hex encoded)
-    /// - PROGRAM_CALL: signature in tx_hash (base58 encoded, already broadcast)
-    fn extract_signature(&self, response: &TransactionResponse) -> Result<Signature, SignerError> {
-        // Try signed_messages first (RAW operations)
-        if let Some(signed_message) = response.signed_messages.first() {
-            let sig_hex = &signed_message.signature.full_sig;
-            let sig_bytes = hex::decode(sig_hex).map_err(|_e| {
-                #[cfg(feature = "unsafe-debug")]
-                log::error!("Failed to decode hex signature: {_e}");
-
-                #[cfg(not(feature = "unsafe-debug"))]
-                log::error!("Failed to decode hex signature");
-
-                SignerError::SerializationError("Failed to decode hex signature".to_string())
-            })?;
-
-            let sig_array: [u8; 64] = sig_bytes.try_into().map_err(|_| {
-                SignerError::SigningFailed(
-                    "Invalid signature length (expected 64 bytes)".to_string(),
-                )
-            })?;
-
-            return Ok(Signature::from(sig_array));
-        }
-
-        // Try tx_hash (PROGRAM_CALL - base58 encoded signature, already broadcast)
-        if let Some(tx_hash) = &response.tx_hash {
-            let sig_bytes = bs58::decode(tx_hash).into_vec().map_err(|_e| {
-                #[cfg(feature = "unsafe-debug")]
-                log::error!("Failed to decode base58 tx_hash: {_e}");
-
-                #[cfg(not(feature = "unsafe-debug"))]
-                log::error!("Failed to decode base58 tx_hash");
-
-                SignerError::SerializationError("Failed to decode base58 tx_hash".to_string())
-            })?;
-
-            let sig_array: [u8; 64] = sig_bytes.try_into().map_err(|_| {
-                SignerError::SigningFailed("Invalid tx_hash length (expected 64 bytes)".to_string())
-            })?;
+        Ok(TransactionResponse::parse(&response_text)?)
+    }
 
-            return Ok(Signature::from(sig_array));
+    /// Poll budget for a transaction, see
+    /// [`with_nonce_max_poll_attempts`](Self::with_nonce_max_poll_attempts)
+    fn poll_attempts(&self, is_nonce_transaction: bool) -> u32 {
+        match self.nonce_max_poll_attempts {
+            Some(attempts) if is_nonce_transaction => attempts,
+            _ => self.max_poll_attempts,
         }
-
-        Err(SignerError::SigningFailed(
-            "No signature found in response (no signed_messages or tx_hash)".to_string(),
-        ))
     }
 
     async fn sign_and_serialize(
         &self,
         transaction: &mut Transaction,
     ) -> Result<SignedTransaction, SignerError> {
+        if self.validate_transactions {
+            TransactionUtil::validate_for_signing(transaction, &self.public_key)?;
+        }
+
+        let max_poll_attempts = self.poll_attempts(NonceUtil::is_nonce_transaction(transaction));
+
         let signature = if self.use_program_call {
             // PROGRAM_CALL: signs and auto-broadcasts to Trezoa
-            self.sign_with_program_call(transaction).await?
+            TransactionUtil::ensure_no_placeholder_signatures(transaction)?;
+            self.sign_with_program_call(transaction, max_poll_attempts)
+                .await?
         } else {
             // RAW (default): sign the message bytes, caller broadcasts
             let message_bytes = transaction.message_data();
-            self.sign_raw_bytes(&message_bytes).await?
+            self.sign_raw_bytes(&message_bytes, max_poll_attempts)
+                .await?
         };
 
         TransactionUtil::add_signature_to_transaction(transaction, &self.public_key, signature)?;
@@ -399,6 +372,38 @@ impl FireblocksSigner {
         ))
     }
 
+    async fn sign_versioned_and_serialize(
+        &self,
+        transaction: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        if self.validate_transactions {
+            TransactionUtil::validate_versioned_for_signing(transaction, &self.public_key)?;
+        }
+
+        let max_poll_attempts =
+            self.poll_attempts(NonceUtil::is_versioned_nonce_transaction(transaction));
+
+        let signature = if self.use_program_call {
+            TransactionUtil::ensure_no_versioned_placeholder_signatures(transaction)?;
+            self.sign_with_program_call(transaction, max_poll_attempts)
+                .await?
+        } else {
+            self.sign_raw_bytes(&transaction.message.serialize(), max_poll_attempts)
+                .await?
+        };
+
+        TransactionUtil::add_signature_to_versioned_transaction(
+            transaction,
+            &self.public_key,
+            signature,
+        )?;
+
+        Ok((
+            TransactionUtil::serialize_versioned_transaction(transaction)?,
+            signature,
+        ))
+    }
+
     /// Check if Fireblocks API is available
     async fn check_availability(&self) -> bool {
         let uri = format!("/v1/vault/accounts/{}", self.vault_account_id);
@@ -437,7 +442,12 @@ impl TrezoaSigner for FireblocksSigner {
     }
 
     async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
-        self.sign_raw_bytes(message).await
+        TransactionUtil::ensure_not_transaction_message(message, &self.pubkey())?;
+        self.sign_raw_bytes(message, self.max_poll_attempts).await
+    }
+
+    async fn sign_message_unchecked(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        self.sign_raw_bytes(message, self.max_poll_attempts).await
     }
 
     async fn sign_partial_transaction(
@@ -447,6 +457,13 @@ impl TrezoaSigner for FireblocksSigner {
         self.sign_and_serialize(tx).await
     }
 
+    async fn sign_versioned_transaction(
+        &self,
+        tx: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        self.sign_versioned_and_serialize(tx).await
+    }
+
     async fn is_available(&self) -> bool {
         self.check_availability().await
     }
@@ -455,6 +472,7 @@ impl TrezoaSigner for FireblocksSigner {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::str::FromStr;
     use wiremock::{
         matchers::{header, method, path, path_regex},
         Mock, MockServer, ResponseTemplate,
@@ -503,7 +521,9 @@ p6B5CCtpBPgD01Vm+bT/JQ==
             client: reqwest::Client::new(),
             poll_interval_ms: 10,
             max_poll_attempts: 3,
+            nonce_max_poll_attempts: None,
             use_program_call: false, // Use RAW (default) for message signing tests
+            validate_transactions: false,
         }
     }
 
@@ -518,7 +538,9 @@ p6B5CCtpBPgD01Vm+bT/JQ==
             client: reqwest::Client::new(),
             poll_interval_ms: 10,
             max_poll_attempts: 3,
+            nonce_max_poll_attempts: None,
             use_program_call: false, // Use RAW (default) for message signing tests
+            validate_transactions: false,
         }
     }
 
@@ -533,7 +555,9 @@ p6B5CCtpBPgD01Vm+bT/JQ==
             client: reqwest::Client::new(),
             poll_interval_ms: 10,
             max_poll_attempts: 3,
+            nonce_max_poll_attempts: None,
             use_program_call: true, // Use PROGRAM_CALL for transaction tests
+            validate_transactions: false,
         }
     }
 
@@ -761,6 +785,116 @@ p6B5CCtpBPgD01Vm+bT/JQ==
         assert_eq!(signature.as_ref(), &sig_bytes);
     }
 
+    #[tokio::test]
+    async fn test_program_call_refuses_placeholder_signatures() {
+        use crate::sdk_adapter::{Hash, Message};
+        use crate::test_util::create_transfer_instruction;
+
+        let mock_server = MockServer::start().await;
+        let signer = create_test_signer_program_call(&mock_server.uri());
+
+        // PROGRAM_CALL broadcasts right away, so nothing may reach Fireblocks
+        Mock::given(method("POST"))
+            .and(path("/v1/transactions"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        // Co-signed by a watch-only signer
+        let co_signer = Pubkey::new_unique();
+        let mut message = Message::new(
+            &[create_transfer_instruction(
+                &co_signer,
+                &Pubkey::new_unique(),
+                1_000,
+            )],
+            Some(&signer.pubkey()),
+        );
+        message.recent_blockhash = Hash::new_from_array([1u8; 32]);
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction.signatures[1] = TransactionUtil::placeholder_signature(&co_signer);
+        let mut versioned = VersionedTransaction::from(transaction.clone());
+
+        assert!(matches!(
+            signer.sign_transaction(&mut transaction).await,
+            Err(SignerError::SigningFailed(_))
+        ));
+        assert!(matches!(
+            signer.sign_versioned_transaction(&mut versioned).await,
+            Err(SignerError::SigningFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_nonce_transaction_uses_nonce_poll_budget() {
+        use crate::nonce_util::NonceUtil;
+        use crate::sdk_adapter::Hash;
+        use crate::test_util::create_transfer_instruction;
+
+        let mock_server = MockServer::start().await;
+        let mut signer = create_test_signer(&mock_server.uri()).with_nonce_max_poll_attempts(3);
+        signer.max_poll_attempts = 1;
+
+        let sig_bytes = [0x42u8; 64];
+        let sig_hex = hex::encode(sig_bytes);
+
+        Mock::given(method("POST"))
+            .and(path("/v1/transactions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "tx-789",
+                "status": "SUBMITTED"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        // Still waiting for approval on the first two polls
+        Mock::given(method("GET"))
+            .and(path("/v1/transactions/tx-789"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "tx-789",
+                "status": "PENDING_AUTHORIZATION"
+            })))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/transactions/tx-789"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "tx-789",
+                "status": "COMPLETED",
+                "signedMessages": [{
+                    "signature": {
+                        "fullSig": sig_hex
+                    }
+                }]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let payer = signer.pubkey();
+        let mut transaction = NonceUtil::build_nonce_transaction(
+            &[create_transfer_instruction(
+                &payer,
+                &Pubkey::new_unique(),
+                1_000,
+            )],
+            &payer,
+            &Pubkey::new_unique(),
+            &payer,
+            Hash::new_from_array([1u8; 32]),
+        );
+
+        let result = signer.sign_transaction(&mut transaction).await;
+        assert!(result.is_ok());
+        let (_, signature) = result.unwrap();
+        assert_eq!(signature.as_ref(), &sig_bytes);
+    }
+
     #[test]
     fn test_use_program_call_config() {
         // Test with use_program_call = true (PROGRAM_CALL mode)
@@ -1,5 +1,8 @@
 //! Fireblocks API types
 
+use crate::error::ResponseError;
+use crate::response;
+use crate::sdk_adapter::{Pubkey, Signature};
 use serde::{Deserialize, Serialize};
 
 /// Request to create a signing transaction in Fireblocks
@@ -109,3 +112,114 @@ pub struct VaultAddressesResponse {
 pub struct VaultAddress {
     pub address: String,
 }
+
+impl CreateTransactionResponse {
+    pub fn parse(body: &str) -> Result<Self, ResponseError> {
+        response::parse_json(body, "Fireblocks create transaction response")
+    }
+}
+
+impl TransactionResponse {
+    pub fn parse(body: &str) -> Result<Self, ResponseError> {
+        response::parse_json(body, "Fireblocks transaction response")
+    }
+
+    /// Signature of a completed transaction
+    ///
+    /// - RAW operations: signature in signed_messages[0].signature.full_sig (hex encoded)
+    /// - PROGRAM_CALL: signature in tx_hash (base58 encoded, already broadcast)
+    pub fn signature(&self) -> Result<Signature, ResponseError> {
+        if let Some(signed_message) = self.signed_messages.first() {
+            let bytes = response::decode_hex(&signed_message.signature.full_sig, "fullSig")?;
+            return response::signature(bytes, "fullSig");
+        }
+
+        if let Some(tx_hash) = &self.tx_hash {
+            let bytes = response::decode_base58(tx_hash, "txHash")?;
+            return response::signature(bytes, "txHash");
+        }
+
+        Err(ResponseError::MissingField("signedMessages or txHash"))
+    }
+}
+
+impl VaultAddressesResponse {
+    pub fn parse(body: &str) -> Result<Self, ResponseError> {
+        response::parse_json(body, "Fireblocks vault addresses response")
+    }
+
+    /// Public key of the vault account's first address
+    pub fn pubkey(&self) -> Result<Pubkey, ResponseError> {
+        let address = self
+            .addresses
+            .first()
+            .ok_or(ResponseError::MissingField("address"))?;
+        let bytes = response::decode_base58(&address.address, "address")?;
+        response::pubkey(bytes, "address")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{arb_encoded_string, arb_json};
+    use proptest::prelude::*;
+    use serde_json::json;
+
+    const FIELDS: &[&str] = &[
+        "id",
+        "status",
+        "subStatus",
+        "signedMessages",
+        "signature",
+        "fullSig",
+        "txHash",
+        "addresses",
+        "address",
+    ];
+
+    fn parse_all(body: &str) {
+        let _ = CreateTransactionResponse::parse(body);
+        if let Ok(response) = TransactionResponse::parse(body) {
+            let _ = response.signature();
+        }
+        if let Ok(response) = VaultAddressesResponse::parse(body) {
+            let _ = response.pubkey();
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_parsers_never_panic(value in arb_json(FIELDS), raw in ".*") {
+            parse_all(&value.to_string());
+            parse_all(&raw);
+        }
+
+        #[test]
+        fn test_field_decoding_never_panics(value in arb_encoded_string()) {
+            parse_all(&json!({
+                "id": "tx", "status": "COMPLETED",
+                "signedMessages": [{ "signature": { "fullSig": value } }]
+            }).to_string());
+            parse_all(&json!({ "id": "tx", "status": "COMPLETED", "txHash": value }).to_string());
+            parse_all(&json!({ "addresses": [{ "address": value }] }).to_string());
+        }
+    }
+
+    #[test]
+    fn test_missing_signature() {
+        let response =
+            TransactionResponse::parse(&json!({ "id": "tx", "status": "COMPLETED" }).to_string())
+                .unwrap();
+        assert!(matches!(
+            response.signature(),
+            Err(ResponseError::MissingField(_))
+        ));
+
+        let response = VaultAddressesResponse::parse(r#"{"addresses":[]}"#).unwrap();
+        assert_eq!(
+            response.pubkey(),
+            Err(ResponseError::MissingField("address"))
+        );
+    }
+}
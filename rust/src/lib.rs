@@ -12,19 +12,39 @@
 //! - `turnkey`: Turnkey API integration
 //! - `aws_kms`: AWS KMS integration with EdDSA (Ed25519) signing
 //! - `fireblocks`: Fireblocks API integration
+//! - `watch_only`: Public-key-only signer producing placeholder signatures (dry runs)
 //! - `all`: Enable all signer backends
 //!
+//! ## Utilities
+//! - `rpc`: Send and confirm signed transactions over Trezoa JSON-RPC
+//! - `offline`: Export, sign and re-import transactions for air-gapped signing
+//! - `approval`: Hold signing requests until an operator approves them
+//!
 //! ## SDK Version Selection
 //! - `sdk-v2` (default): Use Trezoa SDK v2.3.x
 //! - `sdk-v3`: Use Trezoa SDK v3.x
 //!
 //! **Note**: Only one SDK version can be enabled at a time.
 
+pub mod cache;
+pub mod compute_budget_util;
+pub mod ed25519_util;
 pub mod error;
+pub mod nonce_util;
+pub mod offchain_message;
+pub mod policy;
+#[cfg(any(
+    feature = "vault",
+    feature = "privy",
+    feature = "turnkey",
+    feature = "fireblocks"
+))]
+pub(crate) mod response;
 mod sdk_adapter;
+pub(crate) mod store_util;
 #[cfg(test)]
 pub mod test_util;
-#[cfg(feature = "integration-tests")]
+#[cfg(any(test, feature = "integration-tests"))]
 pub mod tests;
 pub mod traits;
 pub mod transaction_util;
@@ -47,9 +67,21 @@ pub mod aws_kms;
 #[cfg(feature = "fireblocks")]
 pub mod fireblocks;
 
+#[cfg(feature = "watch_only")]
+pub mod watch_only;
+
+#[cfg(feature = "rpc")]
+pub mod rpc;
+
+#[cfg(feature = "offline")]
+pub mod offline;
+
+#[cfg(feature = "approval")]
+pub mod approval;
+
 // Re-export core types
-pub use error::SignerError;
-pub use traits::TrezoaSigner;
+pub use error::{SignerError, TransactionValidationError};
+pub use traits::{SigningRequest, SigningResponse, TrezoaSigner};
 
 // Re-export signer types
 #[cfg(feature = "memory")]
@@ -59,10 +91,10 @@ pub use memory::MemorySigner;
 pub use vault::VaultSigner;
 
 #[cfg(feature = "privy")]
-pub use privy::PrivySigner;
+pub use privy::{PrivySigner, PrivySigningMode};
 
 #[cfg(feature = "turnkey")]
-pub use turnkey::TurnkeySigner;
+pub use turnkey::{TurnkeyKey, TurnkeySigner};
 
 #[cfg(feature = "aws_kms")]
 pub use aws_kms::KmsSigner;
@@ -70,6 +102,9 @@ pub use aws_kms::KmsSigner;
 #[cfg(feature = "fireblocks")]
 pub use fireblocks::{FireblocksSigner, FireblocksSignerConfig};
 
+#[cfg(feature = "watch_only")]
+pub use watch_only::WatchOnlySigner;
+
 use crate::traits::SignedTransaction;
 
 // Ensure at least one signer backend is enabled
@@ -79,10 +114,11 @@ use crate::traits::SignedTransaction;
     feature = "privy",
     feature = "turnkey",
     feature = "aws_kms",
-    feature = "fireblocks"
+    feature = "fireblocks",
+    feature = "watch_only"
 )))]
 compile_error!(
-    "At least one signer backend feature must be enabled: memory, vault, privy, turnkey, aws_kms, or fireblocks"
+    "At least one signer backend feature must be enabled: memory, vault, privy, turnkey, aws_kms, fireblocks, or watch_only"
 );
 
 /// Unified signer enum supporting multiple backends
@@ -104,6 +140,9 @@ pub enum Signer {
 
     #[cfg(feature = "fireblocks")]
     Fireblocks(FireblocksSigner),
+
+    #[cfg(feature = "watch_only")]
+    WatchOnly(WatchOnlySigner),
 }
 
 impl Signer {
@@ -131,6 +170,23 @@ impl Signer {
         )?))
     }
 
+    /// Create a Vault signer for one context of a derived transit key
+    ///
+    /// The public key is read from Vault; `config.pubkey`, if set, must match it.
+    #[cfg(feature = "vault")]
+    pub async fn from_vault_derived(
+        config: vault::VaultSignerConfig,
+        context: &[u8],
+    ) -> Result<Self, SignerError> {
+        Ok(Self::Vault(
+            VaultSigner::connect(vault::VaultSignerConfig {
+                context: Some(context.to_vec()),
+                ..config
+            })
+            .await?,
+        ))
+    }
+
     /// Create a Privy signer (requires initialization)
     #[cfg(feature = "privy")]
     pub async fn from_privy(
@@ -149,18 +205,39 @@ impl Signer {
         api_public_key: String,
         api_private_key: String,
         organization_id: String,
-        private_key_id: String,
+        sign_with: String,
         public_key: String,
     ) -> Result<Self, SignerError> {
         Ok(Self::Turnkey(TurnkeySigner::new(
             api_public_key,
             api_private_key,
             organization_id,
-            private_key_id,
+            sign_with,
             public_key,
         )?))
     }
 
+    /// Create a Turnkey signer, resolving the public key from Turnkey
+    #[cfg(feature = "turnkey")]
+    pub async fn from_turnkey_key(
+        api_public_key: String,
+        api_private_key: String,
+        organization_id: String,
+        key: TurnkeyKey,
+        public_key: Option<String>,
+    ) -> Result<Self, SignerError> {
+        Ok(Self::Turnkey(
+            TurnkeySigner::connect(
+                api_public_key,
+                api_private_key,
+                organization_id,
+                key,
+                public_key,
+            )
+            .await?,
+        ))
+    }
+
     /// Create an AWS KMS signer (requires initialization)
     #[cfg(feature = "aws_kms")]
     pub async fn from_kms(
@@ -178,6 +255,27 @@ impl Signer {
         signer.init().await?;
         Ok(Self::Fireblocks(signer))
     }
+
+    /// Create a watch-only signer from a public key string (dry-run mode)
+    #[cfg(feature = "watch_only")]
+    pub fn from_watch_only(pubkey: &str) -> Result<Self, SignerError> {
+        Ok(Self::WatchOnly(WatchOnlySigner::from_pubkey_string(
+            pubkey,
+        )?))
+    }
+
+    /// Whether this signer only produces placeholder signatures
+    ///
+    /// Watch-only signers report themselves available, so check this before
+    /// broadcasting anything they signed.
+    #[allow(unreachable_patterns)]
+    pub fn is_watch_only(&self) -> bool {
+        match self {
+            #[cfg(feature = "watch_only")]
+            Signer::WatchOnly(_) => true,
+            _ => false,
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -201,6 +299,9 @@ impl TrezoaSigner for Signer {
 
             #[cfg(feature = "fireblocks")]
             Signer::Fireblocks(s) => s.pubkey(),
+
+            #[cfg(feature = "watch_only")]
+            Signer::WatchOnly(s) => s.pubkey(),
         }
     }
 
@@ -226,6 +327,9 @@ impl TrezoaSigner for Signer {
 
             #[cfg(feature = "fireblocks")]
             Signer::Fireblocks(s) => s.sign_transaction(tx).await,
+
+            #[cfg(feature = "watch_only")]
+            Signer::WatchOnly(s) => s.sign_transaction(tx).await,
         }
     }
 
@@ -248,6 +352,37 @@ impl TrezoaSigner for Signer {
 
             #[cfg(feature = "fireblocks")]
             Signer::Fireblocks(s) => s.sign_message(message).await,
+
+            #[cfg(feature = "watch_only")]
+            Signer::WatchOnly(s) => s.sign_message(message).await,
+        }
+    }
+
+    async fn sign_message_unchecked(
+        &self,
+        message: &[u8],
+    ) -> Result<sdk_adapter::Signature, SignerError> {
+        match self {
+            #[cfg(feature = "memory")]
+            Signer::Memory(s) => s.sign_message_unchecked(message).await,
+
+            #[cfg(feature = "vault")]
+            Signer::Vault(s) => s.sign_message_unchecked(message).await,
+
+            #[cfg(feature = "privy")]
+            Signer::Privy(s) => s.sign_message_unchecked(message).await,
+
+            #[cfg(feature = "turnkey")]
+            Signer::Turnkey(s) => s.sign_message_unchecked(message).await,
+
+            #[cfg(feature = "aws_kms")]
+            Signer::Kms(s) => s.sign_message_unchecked(message).await,
+
+            #[cfg(feature = "fireblocks")]
+            Signer::Fireblocks(s) => s.sign_message_unchecked(message).await,
+
+            #[cfg(feature = "watch_only")]
+            Signer::WatchOnly(s) => s.sign_message_unchecked(message).await,
         }
     }
 
@@ -273,6 +408,37 @@ impl TrezoaSigner for Signer {
 
             #[cfg(feature = "fireblocks")]
             Signer::Fireblocks(s) => s.sign_partial_transaction(tx).await,
+
+            #[cfg(feature = "watch_only")]
+            Signer::WatchOnly(s) => s.sign_partial_transaction(tx).await,
+        }
+    }
+
+    async fn sign_versioned_transaction(
+        &self,
+        tx: &mut sdk_adapter::VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        match self {
+            #[cfg(feature = "memory")]
+            Signer::Memory(s) => s.sign_versioned_transaction(tx).await,
+
+            #[cfg(feature = "vault")]
+            Signer::Vault(s) => s.sign_versioned_transaction(tx).await,
+
+            #[cfg(feature = "privy")]
+            Signer::Privy(s) => s.sign_versioned_transaction(tx).await,
+
+            #[cfg(feature = "turnkey")]
+            Signer::Turnkey(s) => s.sign_versioned_transaction(tx).await,
+
+            #[cfg(feature = "aws_kms")]
+            Signer::Kms(s) => s.sign_versioned_transaction(tx).await,
+
+            #[cfg(feature = "fireblocks")]
+            Signer::Fireblocks(s) => s.sign_versioned_transaction(tx).await,
+
+            #[cfg(feature = "watch_only")]
+            Signer::WatchOnly(s) => s.sign_versioned_transaction(tx).await,
         }
     }
 
@@ -295,6 +461,34 @@ impl TrezoaSigner for Signer {
 
             #[cfg(feature = "fireblocks")]
             Signer::Fireblocks(s) => s.is_available().await,
+
+            #[cfg(feature = "watch_only")]
+            Signer::WatchOnly(s) => s.is_available().await,
+        }
+    }
+
+    async fn sign(&self, request: SigningRequest) -> Result<SigningResponse, SignerError> {
+        match self {
+            #[cfg(feature = "memory")]
+            Signer::Memory(s) => s.sign(request).await,
+
+            #[cfg(feature = "vault")]
+            Signer::Vault(s) => s.sign(request).await,
+
+            #[cfg(feature = "privy")]
+            Signer::Privy(s) => s.sign(request).await,
+
+            #[cfg(feature = "turnkey")]
+            Signer::Turnkey(s) => s.sign(request).await,
+
+            #[cfg(feature = "aws_kms")]
+            Signer::Kms(s) => s.sign(request).await,
+
+            #[cfg(feature = "fireblocks")]
+            Signer::Fireblocks(s) => s.sign(request).await,
+
+            #[cfg(feature = "watch_only")]
+            Signer::WatchOnly(s) => s.sign(request).await,
         }
     }
 }
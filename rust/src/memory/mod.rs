@@ -1,6 +1,6 @@
 //! Memory-based local keypair signer
 
-mod keypair_util;
+pub(crate) mod keypair_util;
 
 use crate::{
     error::SignerError,
@@ -11,12 +11,14 @@ use crate::{
 
 use crate::sdk_adapter::{
     keypair_pubkey, keypair_sign_message, Keypair, Pubkey, Signature, Transaction,
+    VersionedTransaction,
 };
 use keypair_util::KeypairUtil;
 
 /// A Trezoa-based signer that uses an in-memory keypair
 pub struct MemorySigner {
     keypair: Keypair,
+    validate_transactions: bool,
 }
 
 impl std::fmt::Debug for MemorySigner {
@@ -30,7 +32,10 @@ impl std::fmt::Debug for MemorySigner {
 impl MemorySigner {
     /// Creates a new signer from a Trezoa keypair
     pub fn new(keypair: Keypair) -> Self {
-        Self { keypair }
+        Self {
+            keypair,
+            validate_transactions: true,
+        }
     }
 
     /// Creates a new signer from a private key byte array
@@ -38,7 +43,7 @@ impl MemorySigner {
         let keypair = keypair_from_bytes(private_key).map_err(|e| {
             SignerError::InvalidPrivateKey(format!("Invalid private key bytes: {e}"))
         })?;
-        Ok(Self { keypair })
+        Ok(Self::new(keypair))
     }
 
     /// Creates a new signer from a private key string that can be in multiple formats:
@@ -50,9 +55,55 @@ impl MemorySigner {
         Ok(Self::new(keypair))
     }
 
+    /// Enable or disable [`TransactionUtil::validate_for_signing`] (enabled by default)
+    pub fn with_transaction_validation(mut self, enabled: bool) -> Self {
+        self.validate_transactions = enabled;
+        self
+    }
+
     async fn sign_bytes(&self, serialized: &[u8]) -> Result<Signature, SignerError> {
         Ok(keypair_sign_message(&self.keypair, serialized))
     }
+
+    async fn sign_and_serialize(
+        &self,
+        transaction: &mut Transaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        if self.validate_transactions {
+            TransactionUtil::validate_for_signing(transaction, &self.pubkey())?;
+        }
+
+        let signature = self.sign_bytes(&transaction.message_data()).await?;
+
+        TransactionUtil::add_signature_to_transaction(transaction, &self.pubkey(), signature)?;
+
+        Ok((
+            TransactionUtil::serialize_transaction(transaction)?,
+            signature,
+        ))
+    }
+
+    async fn sign_versioned_and_serialize(
+        &self,
+        transaction: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        if self.validate_transactions {
+            TransactionUtil::validate_versioned_for_signing(transaction, &self.pubkey())?;
+        }
+
+        let signature = self.sign_bytes(&transaction.message.serialize()).await?;
+
+        TransactionUtil::add_signature_to_versioned_transaction(
+            transaction,
+            &self.pubkey(),
+            signature,
+        )?;
+
+        Ok((
+            TransactionUtil::serialize_versioned_transaction(transaction)?,
+            signature,
+        ))
+    }
 }
 
 #[async_trait::async_trait]
@@ -65,14 +116,15 @@ impl TrezoaSigner for MemorySigner {
         &self,
         tx: &mut Transaction,
     ) -> Result<SignedTransaction, SignerError> {
-        let signature = self.sign_bytes(&tx.message_data()).await?;
-
-        TransactionUtil::add_signature_to_transaction(tx, &self.pubkey(), signature)?;
-
-        Ok((TransactionUtil::serialize_transaction(tx)?, signature))
+        self.sign_and_serialize(tx).await
     }
 
     async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        TransactionUtil::ensure_not_transaction_message(message, &self.pubkey())?;
+        self.sign_bytes(message).await
+    }
+
+    async fn sign_message_unchecked(&self, message: &[u8]) -> Result<Signature, SignerError> {
         self.sign_bytes(message).await
     }
 
@@ -80,11 +132,14 @@ impl TrezoaSigner for MemorySigner {
         &self,
         tx: &mut Transaction,
     ) -> Result<SignedTransaction, SignerError> {
-        let signature = self.sign_bytes(&tx.message_data()).await?;
-
-        TransactionUtil::add_signature_to_transaction(tx, &self.pubkey(), signature)?;
+        self.sign_and_serialize(tx).await
+    }
 
-        Ok((TransactionUtil::serialize_transaction(tx)?, signature))
+    async fn sign_versioned_transaction(
+        &self,
+        tx: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        self.sign_versioned_and_serialize(tx).await
     }
 
     async fn is_available(&self) -> bool {
@@ -132,6 +187,26 @@ mod tests {
         assert_eq!(sig.as_ref().len(), 64);
     }
 
+    #[tokio::test]
+    async fn test_sign_message_rejects_transaction_message() {
+        let signer = create_test_signer();
+        let message = create_test_transaction(&signer.pubkey()).message_data();
+
+        let result = signer.sign_message(&message).await;
+        assert!(matches!(result, Err(SignerError::SigningFailed(_))));
+
+        let signature = signer.sign_message_unchecked(&message).await.unwrap();
+        assert!(signature.verify(signer.pubkey().as_ref(), &message));
+    }
+
+    #[tokio::test]
+    async fn test_sign_message_allows_other_signers_transaction() {
+        let signer = create_test_signer();
+        let tx = create_test_transaction(&Pubkey::new_unique());
+
+        assert!(signer.sign_message(&tx.message_data()).await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_is_available() {
         let signer = create_test_signer();
@@ -181,4 +256,30 @@ mod tests {
         assert_eq!(tx.signatures.len(), 1);
         assert_eq!(tx.signatures[0], signature);
     }
+
+    #[tokio::test]
+    async fn test_sign_transaction_validates_before_signing() {
+        let signer = create_test_signer();
+        let mut tx = create_test_transaction(&signer.pubkey());
+        tx.message.recent_blockhash = crate::sdk_adapter::Hash::default();
+
+        let result = signer.sign_transaction(&mut tx).await;
+        assert!(matches!(
+            result,
+            Err(SignerError::InvalidTransaction(
+                crate::error::TransactionValidationError::ZeroBlockhash
+            ))
+        ));
+
+        let mut other = create_test_transaction(&crate::sdk_adapter::Pubkey::new_unique());
+        assert!(matches!(
+            signer.sign_partial_transaction(&mut other).await,
+            Err(SignerError::InvalidTransaction(
+                crate::error::TransactionValidationError::SignerNotFound(_)
+            ))
+        ));
+
+        let signer = signer.with_transaction_validation(false);
+        assert!(signer.sign_transaction(&mut tx).await.is_ok());
+    }
 }
@@ -0,0 +1,373 @@
+//! Durable nonce helpers
+//!
+//! A durable nonce transaction starts with an `AdvanceNonceAccount` instruction
+//! and uses the stored nonce value as its `recent_blockhash`, so it stays valid
+//! until the nonce is advanced instead of expiring with the blockhash. This makes
+//! it suitable for offline or approval-gated signing flows.
+//!
+//! Signers don't depend on blockhash freshness, so they sign nonce transactions like
+//! any other. Where the crate does assume freshness, it checks
+//! [`NonceUtil::is_nonce_transaction`]: the Fireblocks signer polls nonce transactions
+//! for longer, and `TransactionSender::send_and_confirm` rejects them rather than
+//! replacing the nonce with a fresh blockhash.
+
+use crate::error::SignerError;
+use crate::sdk_adapter::{
+    AccountMeta, CompiledInstruction, Hash, Instruction, Message, Pubkey, Transaction,
+    VersionedTransaction,
+};
+
+/// System program ID
+pub const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::from_str_const("11111111111111111111111111111111");
+
+/// RecentBlockhashes sysvar ID (required by the nonce instructions)
+pub const RECENT_BLOCKHASHES_SYSVAR_ID: Pubkey =
+    Pubkey::from_str_const("SysvarRecentB1ockHashes11111111111111111111");
+
+/// Rent sysvar ID
+pub const RENT_SYSVAR_ID: Pubkey =
+    Pubkey::from_str_const("SysvarRent111111111111111111111111111111111");
+
+/// Size in bytes of a nonce account
+pub const NONCE_ACCOUNT_LENGTH: u64 = 80;
+
+// System instruction discriminants (bincode-encoded u32)
+const CREATE_ACCOUNT_IX: u32 = 0;
+const ADVANCE_NONCE_ACCOUNT_IX: u32 = 4;
+const INITIALIZE_NONCE_ACCOUNT_IX: u32 = 6;
+const AUTHORIZE_NONCE_ACCOUNT_IX: u32 = 7;
+
+// Nonce account layout: version (u32) | state (u32) | authority | durable nonce | fee calculator
+const NONCE_STATE_INITIALIZED: u32 = 1;
+const NONCE_AUTHORITY_OFFSET: usize = 8;
+const NONCE_BLOCKHASH_OFFSET: usize = 40;
+const NONCE_FEE_OFFSET: usize = 72;
+
+/// Decoded contents of an initialized nonce account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonceAccountState {
+    /// Authority allowed to advance or re-authorize the nonce
+    pub authority: Pubkey,
+    /// Current durable nonce, used as the transaction's `recent_blockhash`
+    pub blockhash: Hash,
+    /// Lamports per signature recorded when the nonce was last advanced
+    pub lamports_per_signature: u64,
+}
+
+pub struct NonceUtil;
+
+impl NonceUtil {
+    /// Build an `AdvanceNonceAccount` instruction
+    pub fn advance_nonce_instruction(nonce_account: &Pubkey, authority: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: SYSTEM_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(*nonce_account, false),
+                AccountMeta::new_readonly(RECENT_BLOCKHASHES_SYSVAR_ID, false),
+                AccountMeta::new_readonly(*authority, true),
+            ],
+            data: ADVANCE_NONCE_ACCOUNT_IX.to_le_bytes().to_vec(),
+        }
+    }
+
+    /// Build the `CreateAccount` + `InitializeNonceAccount` instructions for a new nonce account
+    ///
+    /// Both `payer` and `nonce_account` must sign the resulting transaction.
+    pub fn create_nonce_account_instructions(
+        payer: &Pubkey,
+        nonce_account: &Pubkey,
+        authority: &Pubkey,
+        lamports: u64,
+    ) -> Vec<Instruction> {
+        let mut create_data = CREATE_ACCOUNT_IX.to_le_bytes().to_vec();
+        create_data.extend_from_slice(&lamports.to_le_bytes());
+        create_data.extend_from_slice(&NONCE_ACCOUNT_LENGTH.to_le_bytes());
+        create_data.extend_from_slice(SYSTEM_PROGRAM_ID.as_ref());
+
+        let mut initialize_data = INITIALIZE_NONCE_ACCOUNT_IX.to_le_bytes().to_vec();
+        initialize_data.extend_from_slice(authority.as_ref());
+
+        vec![
+            Instruction {
+                program_id: SYSTEM_PROGRAM_ID,
+                accounts: vec![
+                    AccountMeta::new(*payer, true),
+                    AccountMeta::new(*nonce_account, true),
+                ],
+                data: create_data,
+            },
+            Instruction {
+                program_id: SYSTEM_PROGRAM_ID,
+                accounts: vec![
+                    AccountMeta::new(*nonce_account, false),
+                    AccountMeta::new_readonly(RECENT_BLOCKHASHES_SYSVAR_ID, false),
+                    AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+                ],
+                data: initialize_data,
+            },
+        ]
+    }
+
+    /// Build an `AuthorizeNonceAccount` instruction that hands the nonce to `new_authority`
+    pub fn authorize_nonce_account_instruction(
+        nonce_account: &Pubkey,
+        authority: &Pubkey,
+        new_authority: &Pubkey,
+    ) -> Instruction {
+        let mut data = AUTHORIZE_NONCE_ACCOUNT_IX.to_le_bytes().to_vec();
+        data.extend_from_slice(new_authority.as_ref());
+
+        Instruction {
+            program_id: SYSTEM_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(*nonce_account, false),
+                AccountMeta::new_readonly(*authority, true),
+            ],
+            data,
+        }
+    }
+
+    /// Build an unsigned transaction that creates and initializes a nonce account
+    pub fn create_nonce_account_transaction(
+        payer: &Pubkey,
+        nonce_account: &Pubkey,
+        authority: &Pubkey,
+        lamports: u64,
+        recent_blockhash: Hash,
+    ) -> Transaction {
+        let instructions =
+            Self::create_nonce_account_instructions(payer, nonce_account, authority, lamports);
+        let mut message = Message::new(&instructions, Some(payer));
+        message.recent_blockhash = recent_blockhash;
+        Transaction::new_unsigned(message)
+    }
+
+    /// Build an unsigned transaction that changes the authority of a nonce account
+    pub fn authorize_nonce_account_transaction(
+        payer: &Pubkey,
+        nonce_account: &Pubkey,
+        authority: &Pubkey,
+        new_authority: &Pubkey,
+        recent_blockhash: Hash,
+    ) -> Transaction {
+        let instruction =
+            Self::authorize_nonce_account_instruction(nonce_account, authority, new_authority);
+        let mut message = Message::new(&[instruction], Some(payer));
+        message.recent_blockhash = recent_blockhash;
+        Transaction::new_unsigned(message)
+    }
+
+    /// Build an unsigned durable nonce transaction
+    ///
+    /// Prepends `AdvanceNonceAccount` to `instructions` and sets `recent_blockhash`
+    /// to `nonce`, the value currently stored in the nonce account.
+    pub fn build_nonce_transaction(
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        nonce_account: &Pubkey,
+        nonce_authority: &Pubkey,
+        nonce: Hash,
+    ) -> Transaction {
+        let mut all_instructions = Vec::with_capacity(instructions.len() + 1);
+        all_instructions.push(Self::advance_nonce_instruction(
+            nonce_account,
+            nonce_authority,
+        ));
+        all_instructions.extend_from_slice(instructions);
+
+        let mut message = Message::new(&all_instructions, Some(payer));
+        message.recent_blockhash = nonce;
+        Transaction::new_unsigned(message)
+    }
+
+    /// Decode the data of an initialized nonce account
+    pub fn parse_nonce_account(data: &[u8]) -> Result<NonceAccountState, SignerError> {
+        if data.len() < NONCE_ACCOUNT_LENGTH as usize {
+            return Err(SignerError::SerializationError(format!(
+                "Invalid nonce account length: expected {} bytes, got {}",
+                NONCE_ACCOUNT_LENGTH,
+                data.len()
+            )));
+        }
+
+        let state = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+        if state != NONCE_STATE_INITIALIZED {
+            return Err(SignerError::SerializationError(
+                "Nonce account is not initialized".to_string(),
+            ));
+        }
+
+        let authority = Pubkey::try_from(&data[NONCE_AUTHORITY_OFFSET..NONCE_BLOCKHASH_OFFSET])
+            .map_err(|_| SignerError::SerializationError("Invalid nonce authority".to_string()))?;
+
+        let mut blockhash = [0u8; 32];
+        blockhash.copy_from_slice(&data[NONCE_BLOCKHASH_OFFSET..NONCE_FEE_OFFSET]);
+
+        let mut fee = [0u8; 8];
+        fee.copy_from_slice(&data[NONCE_FEE_OFFSET..NONCE_FEE_OFFSET + 8]);
+
+        Ok(NonceAccountState {
+            authority,
+            blockhash: Hash::new_from_array(blockhash),
+            lamports_per_signature: u64::from_le_bytes(fee),
+        })
+    }
+
+    /// Get the nonce account of a durable nonce transaction
+    ///
+    /// Returns `None` if the first instruction is not `AdvanceNonceAccount`,
+    /// meaning the transaction relies on a regular recent blockhash.
+    pub fn get_nonce_account(transaction: &Transaction) -> Option<Pubkey> {
+        let message = &transaction.message;
+        let instruction =
+            Self::advance_nonce_account_ix(&message.account_keys, &message.instructions)?;
+
+        let nonce_index = *instruction.accounts.first()?;
+        message.account_keys.get(nonce_index as usize).copied()
+    }
+
+    /// Check whether a transaction uses a durable nonce instead of a recent blockhash
+    pub fn is_nonce_transaction(transaction: &Transaction) -> bool {
+        Self::get_nonce_account(transaction).is_some()
+    }
+
+    /// [`is_nonce_transaction`](Self::is_nonce_transaction) for a versioned transaction
+    pub fn is_versioned_nonce_transaction(transaction: &VersionedTransaction) -> bool {
+        let message = &transaction.message;
+        Self::advance_nonce_account_ix(message.static_account_keys(), message.instructions())
+            .is_some()
+    }
+
+    /// The first instruction, if it is `AdvanceNonceAccount`
+    ///
+    /// Program ids are always static account keys, so this works for v0 messages too.
+    fn advance_nonce_account_ix<'a>(
+        account_keys: &[Pubkey],
+        instructions: &'a [CompiledInstruction],
+    ) -> Option<&'a CompiledInstruction> {
+        let instruction = instructions.first()?;
+        let program_id = account_keys.get(instruction.program_id_index as usize)?;
+
+        (*program_id == SYSTEM_PROGRAM_ID
+            && instruction.data.get(..4) == Some(&ADVANCE_NONCE_ACCOUNT_IX.to_le_bytes()[..]))
+        .then_some(instruction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdk_adapter::{keypair_pubkey, Keypair};
+    use crate::test_util::{create_test_transaction, create_transfer_instruction};
+
+    fn build_nonce_account_data(authority: &Pubkey, blockhash: &Hash, fee: u64) -> Vec<u8> {
+        let mut data = Vec::with_capacity(NONCE_ACCOUNT_LENGTH as usize);
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&NONCE_STATE_INITIALIZED.to_le_bytes());
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(blockhash.as_ref());
+        data.extend_from_slice(&fee.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_advance_nonce_instruction() {
+        let nonce_account = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+
+        let ix = NonceUtil::advance_nonce_instruction(&nonce_account, &authority);
+
+        assert_eq!(ix.program_id, SYSTEM_PROGRAM_ID);
+        assert_eq!(ix.data, vec![4, 0, 0, 0]);
+        assert_eq!(ix.accounts.len(), 3);
+        assert!(ix.accounts[0].is_writable);
+        assert_eq!(ix.accounts[1].pubkey, RECENT_BLOCKHASHES_SYSVAR_ID);
+        assert!(ix.accounts[2].is_signer);
+    }
+
+    #[test]
+    fn test_create_nonce_account_instructions() {
+        let payer = Pubkey::new_unique();
+        let nonce_account = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+
+        let ixs = NonceUtil::create_nonce_account_instructions(
+            &payer,
+            &nonce_account,
+            &authority,
+            1_500_000,
+        );
+
+        assert_eq!(ixs.len(), 2);
+        assert_eq!(ixs[0].data.len(), 52);
+        assert_eq!(&ixs[0].data[4..12], &1_500_000u64.to_le_bytes());
+        assert_eq!(&ixs[0].data[12..20], &NONCE_ACCOUNT_LENGTH.to_le_bytes());
+        assert_eq!(&ixs[1].data[..4], &[6, 0, 0, 0]);
+        assert_eq!(&ixs[1].data[4..], authority.as_ref());
+    }
+
+    #[test]
+    fn test_build_nonce_transaction() {
+        let payer = Pubkey::new_unique();
+        let nonce_account = Pubkey::new_unique();
+        let nonce = Hash::new_from_array([7u8; 32]);
+        let transfer_ix = create_transfer_instruction(&payer, &Pubkey::new_unique(), 1_000);
+
+        let tx = NonceUtil::build_nonce_transaction(
+            &[transfer_ix],
+            &payer,
+            &nonce_account,
+            &payer,
+            nonce,
+        );
+
+        assert_eq!(tx.message.recent_blockhash, nonce);
+        assert_eq!(tx.message.instructions.len(), 2);
+        assert_eq!(tx.message.account_keys[0], payer);
+        assert!(NonceUtil::is_nonce_transaction(&tx));
+        assert_eq!(NonceUtil::get_nonce_account(&tx), Some(nonce_account));
+        assert!(NonceUtil::is_versioned_nonce_transaction(
+            &VersionedTransaction::from(tx)
+        ));
+    }
+
+    #[test]
+    fn test_regular_transaction_is_not_nonce() {
+        let keypair = Keypair::new();
+        let tx = create_test_transaction(&keypair_pubkey(&keypair));
+
+        assert!(!NonceUtil::is_nonce_transaction(&tx));
+        assert_eq!(NonceUtil::get_nonce_account(&tx), None);
+        assert!(!NonceUtil::is_versioned_nonce_transaction(
+            &VersionedTransaction::from(tx)
+        ));
+    }
+
+    #[test]
+    fn test_parse_nonce_account() {
+        let authority = Pubkey::new_unique();
+        let blockhash = Hash::new_from_array([9u8; 32]);
+        let data = build_nonce_account_data(&authority, &blockhash, 5000);
+
+        let state = NonceUtil::parse_nonce_account(&data).unwrap();
+
+        assert_eq!(state.authority, authority);
+        assert_eq!(state.blockhash, blockhash);
+        assert_eq!(state.lamports_per_signature, 5000);
+    }
+
+    #[test]
+    fn test_parse_nonce_account_uninitialized() {
+        let mut data = build_nonce_account_data(&Pubkey::new_unique(), &Hash::default(), 0);
+        data[4..8].copy_from_slice(&0u32.to_le_bytes());
+
+        let result = NonceUtil::parse_nonce_account(&data);
+        assert!(matches!(result, Err(SignerError::SerializationError(_))));
+    }
+
+    #[test]
+    fn test_parse_nonce_account_too_short() {
+        let result = NonceUtil::parse_nonce_account(&[0u8; 40]);
+        assert!(matches!(result, Err(SignerError::SerializationError(_))));
+    }
+}
@@ -0,0 +1,128 @@
+//! Off-chain message encoding
+//!
+//! Off-chain messages are prefixed with a signing domain that no transaction message
+//! can start with, so a signature over one can never be replayed as a transaction
+//! signature.
+
+use crate::error::SignerError;
+
+/// Signing domain prepended to every off-chain message
+pub const OFFCHAIN_SIGNING_DOMAIN: &[u8; 16] = b"\xfftrezoa offchain";
+
+/// Maximum message length that hardware wallets can display
+pub const MAX_LEDGER_MESSAGE_LENGTH: usize = 1212;
+
+/// Maximum message length of a version 0 off-chain message
+pub const MAX_MESSAGE_LENGTH: usize = 65515;
+
+/// Message format, picked from the message content
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OffchainMessageFormat {
+    /// Printable ASCII, fits on a hardware wallet screen
+    RestrictedAscii = 0,
+    /// UTF-8, fits on a hardware wallet screen
+    LimitedUtf8 = 1,
+    /// UTF-8 up to the maximum message length
+    ExtendedUtf8 = 2,
+}
+
+/// A version 0 off-chain message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OffchainMessage {
+    format: OffchainMessageFormat,
+    message: Vec<u8>,
+}
+
+impl OffchainMessage {
+    /// Create an off-chain message, picking the most restrictive format that fits
+    pub fn new(message: &[u8]) -> Result<Self, SignerError> {
+        if message.is_empty() {
+            return Err(SignerError::SerializationError(
+                "Off-chain message cannot be empty".to_string(),
+            ));
+        }
+
+        let format = if message.len() <= MAX_LEDGER_MESSAGE_LENGTH
+            && message.iter().all(|b| (0x20..=0x7e).contains(b))
+        {
+            OffchainMessageFormat::RestrictedAscii
+        } else if std::str::from_utf8(message).is_err() {
+            return Err(SignerError::SerializationError(
+                "Off-chain message must be valid UTF-8".to_string(),
+            ));
+        } else if message.len() <= MAX_LEDGER_MESSAGE_LENGTH {
+            OffchainMessageFormat::LimitedUtf8
+        } else if message.len() <= MAX_MESSAGE_LENGTH {
+            OffchainMessageFormat::ExtendedUtf8
+        } else {
+            return Err(SignerError::SerializationError(format!(
+                "Off-chain message exceeds {MAX_MESSAGE_LENGTH} bytes"
+            )));
+        };
+
+        Ok(Self {
+            format,
+            message: message.to_vec(),
+        })
+    }
+
+    pub fn format(&self) -> OffchainMessageFormat {
+        self.format
+    }
+
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// Serialize to the bytes that get signed
+    ///
+    /// Layout: signing domain, version (0), format, little-endian u16 length, message.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(OFFCHAIN_SIGNING_DOMAIN.len() + 4 + self.message.len());
+        data.extend_from_slice(OFFCHAIN_SIGNING_DOMAIN);
+        data.push(0);
+        data.push(self.format as u8);
+        data.extend_from_slice(&(self.message.len() as u16).to_le_bytes());
+        data.extend_from_slice(&self.message);
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_selection() {
+        assert_eq!(
+            OffchainMessage::new(b"Hello Trezoa!").unwrap().format(),
+            OffchainMessageFormat::RestrictedAscii
+        );
+        assert_eq!(
+            OffchainMessage::new("Grüße".as_bytes()).unwrap().format(),
+            OffchainMessageFormat::LimitedUtf8
+        );
+        assert_eq!(
+            OffchainMessage::new(&[b'a'; MAX_LEDGER_MESSAGE_LENGTH + 1])
+                .unwrap()
+                .format(),
+            OffchainMessageFormat::ExtendedUtf8
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_messages() {
+        assert!(OffchainMessage::new(b"").is_err());
+        assert!(OffchainMessage::new(&[0xff, 0xfe]).is_err());
+        assert!(OffchainMessage::new(&[b'a'; MAX_MESSAGE_LENGTH + 1]).is_err());
+    }
+
+    #[test]
+    fn test_serialize() {
+        let data = OffchainMessage::new(b"hi").unwrap().serialize();
+
+        assert_eq!(&data[..16], OFFCHAIN_SIGNING_DOMAIN);
+        assert_eq!(&data[16..], &[0, 0, 2, 0, b'h', b'i']);
+    }
+}
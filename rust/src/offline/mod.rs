@@ -0,0 +1,536 @@
+//! Air-gapped signing workflow
+//!
+//! The online machine exports an unsigned transaction as an [`OfflineSigningRequest`].
+//! The offline machine reviews the summary, signs with a local key and returns an
+//! [`OfflineSigningResponse`]. Back online, every returned signature is verified
+//! against the original message before it is merged into the transaction.
+
+use crate::error::SignerError;
+use crate::nonce_util::NonceUtil;
+use crate::sdk_adapter::{
+    CompiledInstruction, Hash, Message, Pubkey, Signature, Transaction, VersionedTransaction,
+};
+use crate::traits::TrezoaSigner;
+use crate::transaction_util::TransactionUtil;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+
+/// Version of the request/response format
+pub const OFFLINE_FORMAT_VERSION: u8 = 1;
+
+/// Human-readable summary of the transaction, shown to the offline operator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSummary {
+    pub fee_payer: String,
+    pub recent_blockhash: String,
+    /// Set when the transaction uses a durable nonce instead of a recent blockhash
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub durable_nonce_account: Option<String>,
+    pub instructions: Vec<InstructionSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstructionSummary {
+    pub program_id: String,
+    pub accounts: Vec<String>,
+    /// Hex-encoded instruction data
+    pub data: String,
+}
+
+/// Unsigned transaction exported for signing on an offline machine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfflineSigningRequest {
+    pub version: u8,
+    /// Base64-encoded serialized transaction message
+    pub message: String,
+    /// Base58-encoded public keys that must sign, in signature order
+    pub required_signers: Vec<String>,
+    pub summary: TransactionSummary,
+    /// Hex-encoded SHA-256 of the message bytes
+    pub checksum: String,
+}
+
+/// Signatures produced on the offline machine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfflineSigningResponse {
+    pub version: u8,
+    /// Checksum of the message that was signed
+    pub checksum: String,
+    pub signatures: Vec<OfflineSignature>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfflineSignature {
+    /// Base58-encoded public key
+    pub pubkey: String,
+    /// Base58-encoded signature
+    pub signature: String,
+}
+
+fn message_checksum(message: &[u8]) -> String {
+    hex::encode(Sha256::digest(message))
+}
+
+fn parse_pubkey(pubkey: &str) -> Result<Pubkey, SignerError> {
+    Pubkey::from_str(pubkey)
+        .map_err(|e| SignerError::InvalidPublicKey(format!("Invalid public key {pubkey}: {e}")))
+}
+
+impl TransactionSummary {
+    /// Decode the fee payer, blockhash and instructions of a transaction
+    pub fn from_transaction(transaction: &Transaction) -> Self {
+        let message = &transaction.message;
+        let account_keys: Vec<String> = message
+            .account_keys
+            .iter()
+            .map(|key| key.to_string())
+            .collect();
+
+        Self::from_parts(
+            &account_keys,
+            &message.recent_blockhash,
+            &message.instructions,
+            NonceUtil::is_nonce_transaction(transaction),
+        )
+    }
+
+    /// Decode the fee payer, blockhash and instructions of a versioned transaction
+    ///
+    /// Address lookup tables are not resolved: an account loaded from one is shown as
+    /// `<table>[<index>]`.
+    pub fn from_versioned_transaction(transaction: &VersionedTransaction) -> Self {
+        let message = &transaction.message;
+        let lookups = message.address_table_lookups().unwrap_or_default();
+
+        // Loaded addresses follow the static keys: all writable entries, then all readonly ones
+        let writable = lookups.iter().flat_map(|lookup| {
+            lookup
+                .writable_indexes
+                .iter()
+                .map(move |index| format!("{}[{index}]", lookup.account_key))
+        });
+        let readonly = lookups.iter().flat_map(|lookup| {
+            lookup
+                .readonly_indexes
+                .iter()
+                .map(move |index| format!("{}[{index}]", lookup.account_key))
+        });
+        let account_keys: Vec<String> = message
+            .static_account_keys()
+            .iter()
+            .map(|key| key.to_string())
+            .chain(writable)
+            .chain(readonly)
+            .collect();
+
+        Self::from_parts(
+            &account_keys,
+            message.recent_blockhash(),
+            message.instructions(),
+            NonceUtil::is_versioned_nonce_transaction(transaction),
+        )
+    }
+
+    fn from_parts(
+        account_keys: &[String],
+        recent_blockhash: &Hash,
+        instructions: &[CompiledInstruction],
+        is_nonce_transaction: bool,
+    ) -> Self {
+        let account = |index: u8| account_keys.get(index as usize).cloned();
+
+        let durable_nonce_account = instructions
+            .first()
+            .filter(|_| is_nonce_transaction)
+            .and_then(|ix| account(*ix.accounts.first()?));
+
+        let instructions = instructions
+            .iter()
+            .map(|ix| InstructionSummary {
+                program_id: account(ix.program_id_index).unwrap_or_default(),
+                accounts: ix
+                    .accounts
+                    .iter()
+                    .filter_map(|index| account(*index))
+                    .collect(),
+                data: hex::encode(&ix.data),
+            })
+            .collect();
+
+        Self {
+            fee_payer: account(0).unwrap_or_default(),
+            recent_blockhash: recent_blockhash.to_string(),
+            durable_nonce_account,
+            instructions,
+        }
+    }
+}
+
+impl OfflineSigningRequest {
+    /// Export an unsigned transaction for offline signing
+    pub fn from_transaction(transaction: &Transaction) -> Result<Self, SignerError> {
+        let message = &transaction.message;
+        let message_bytes = transaction.message_data();
+        let num_required_signatures = message.header.num_required_signatures as usize;
+
+        if message.account_keys.len() < num_required_signatures {
+            return Err(SignerError::SerializationError(
+                "Invalid message: not enough account keys".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            version: OFFLINE_FORMAT_VERSION,
+            message: STANDARD.encode(&message_bytes),
+            required_signers: message.account_keys[..num_required_signatures]
+                .iter()
+                .map(|key| key.to_string())
+                .collect(),
+            summary: TransactionSummary::from_transaction(transaction),
+            checksum: message_checksum(&message_bytes),
+        })
+    }
+
+    /// Serialize the request to JSON
+    pub fn to_json(&self) -> Result<String, SignerError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parse a request from JSON
+    pub fn from_json(json: &str) -> Result<Self, SignerError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Decode the message and check it against the checksum, required signers and summary
+    ///
+    /// The summary must match one recomputed from the message, so what the operator
+    /// reviews is what gets signed.
+    pub fn decode_transaction(&self) -> Result<Transaction, SignerError> {
+        if self.version != OFFLINE_FORMAT_VERSION {
+            return Err(SignerError::SerializationError(format!(
+                "Unsupported offline request version {}",
+                self.version
+            )));
+        }
+
+        let message_bytes = STANDARD.decode(&self.message).map_err(|e| {
+            SignerError::SerializationError(format!("Failed to decode message: {e}"))
+        })?;
+
+        if message_checksum(&message_bytes) != self.checksum {
+            return Err(SignerError::SerializationError(
+                "Message checksum mismatch".to_string(),
+            ));
+        }
+
+        let message: Message = bincode::deserialize(&message_bytes).map_err(|e| {
+            SignerError::SerializationError(format!("Failed to deserialize message: {e}"))
+        })?;
+        let transaction = Transaction::new_unsigned(message);
+
+        let num_required_signatures = transaction.message.header.num_required_signatures as usize;
+        let signers: Vec<String> = transaction
+            .message
+            .account_keys
+            .iter()
+            .take(num_required_signatures)
+            .map(|key| key.to_string())
+            .collect();
+        if signers != self.required_signers {
+            return Err(SignerError::SerializationError(
+                "Required signers do not match the message".to_string(),
+            ));
+        }
+
+        if TransactionSummary::from_transaction(&transaction) != self.summary {
+            return Err(SignerError::SerializationError(
+                "Summary does not match the message".to_string(),
+            ));
+        }
+
+        Ok(transaction)
+    }
+
+    /// Sign the request on the offline machine
+    ///
+    /// Each signer must be one of the required signers. Signing goes through
+    /// `sign_partial_transaction`, so any transaction-level checks still apply.
+    pub async fn sign(
+        &self,
+        signers: &[&dyn TrezoaSigner],
+    ) -> Result<OfflineSigningResponse, SignerError> {
+        let mut transaction = self.decode_transaction()?;
+
+        let mut signatures = Vec::with_capacity(signers.len());
+        for signer in signers {
+            TransactionUtil::get_signing_keypair_position(&transaction, &signer.pubkey())?;
+            let (_, signature) = signer.sign_partial_transaction(&mut transaction).await?;
+            signatures.push(OfflineSignature {
+                pubkey: signer.pubkey().to_string(),
+                signature: signature.to_string(),
+            });
+        }
+
+        Ok(OfflineSigningResponse {
+            version: OFFLINE_FORMAT_VERSION,
+            checksum: self.checksum.clone(),
+            signatures,
+        })
+    }
+}
+
+impl OfflineSigningResponse {
+    /// Serialize the response to JSON
+    pub fn to_json(&self) -> Result<String, SignerError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parse a response from JSON
+    pub fn from_json(json: &str) -> Result<Self, SignerError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Verify the returned signatures and merge them into the original transaction
+    ///
+    /// Nothing is merged unless every signature verifies against the transaction's
+    /// message and belongs to one of its required signers.
+    ///
+    /// # Returns
+    ///
+    /// The public keys whose signatures were merged
+    pub fn merge_into(&self, transaction: &mut Transaction) -> Result<Vec<Pubkey>, SignerError> {
+        if self.version != OFFLINE_FORMAT_VERSION {
+            return Err(SignerError::SerializationError(format!(
+                "Unsupported offline response version {}",
+                self.version
+            )));
+        }
+
+        let message_bytes = transaction.message_data();
+        if message_checksum(&message_bytes) != self.checksum {
+            return Err(SignerError::SigningFailed(
+                "Offline response was produced for a different message".to_string(),
+            ));
+        }
+
+        let mut verified = Vec::with_capacity(self.signatures.len());
+        for entry in &self.signatures {
+            let pubkey = parse_pubkey(&entry.pubkey)?;
+            let signature = Signature::from_str(&entry.signature)
+                .map_err(|e| SignerError::SerializationError(format!("Invalid signature: {e}")))?;
+
+            TransactionUtil::get_signing_keypair_position(transaction, &pubkey)?;
+
+            if !signature.verify(pubkey.as_ref(), &message_bytes) {
+                return Err(SignerError::SigningFailed(format!(
+                    "Invalid offline signature for {pubkey}"
+                )));
+            }
+
+            verified.push((pubkey, signature));
+        }
+
+        for (pubkey, signature) in &verified {
+            TransactionUtil::add_signature_to_transaction(transaction, pubkey, *signature)?;
+        }
+
+        Ok(verified.into_iter().map(|(pubkey, _)| pubkey).collect())
+    }
+}
+
+#[cfg(all(test, feature = "memory"))]
+mod tests {
+    use super::*;
+    use crate::memory::MemorySigner;
+    use crate::sdk_adapter::{v0, Instruction, Keypair, VersionedMessage};
+    use crate::test_util::{create_test_transaction, create_transfer_instruction};
+
+    fn create_two_signer_transaction(payer: &Pubkey, other: &Pubkey) -> Transaction {
+        let instructions: Vec<Instruction> = vec![
+            create_transfer_instruction(payer, &Pubkey::new_unique(), 1_000),
+            create_transfer_instruction(other, &Pubkey::new_unique(), 2_000),
+        ];
+        let mut message = Message::new(&instructions, Some(payer));
+        message.recent_blockhash = Hash::new_from_array([3u8; 32]);
+        Transaction::new_unsigned(message)
+    }
+
+    #[test]
+    fn test_request_from_transaction() {
+        let payer = Pubkey::new_unique();
+        let tx = create_test_transaction(&payer);
+
+        let request = OfflineSigningRequest::from_transaction(&tx).unwrap();
+
+        assert_eq!(request.version, OFFLINE_FORMAT_VERSION);
+        assert_eq!(request.required_signers, vec![payer.to_string()]);
+        assert_eq!(request.summary.fee_payer, payer.to_string());
+        assert_eq!(request.summary.instructions.len(), 1);
+        assert_eq!(request.summary.durable_nonce_account, None);
+        assert_eq!(request.checksum, message_checksum(&tx.message_data()));
+    }
+
+    #[test]
+    fn test_summary_of_versioned_transaction() {
+        let payer = Pubkey::new_unique();
+        let legacy = create_test_transaction(&payer).message;
+        let table = Pubkey::new_unique();
+        let mut instructions = legacy.instructions.clone();
+        // Send to the first writable address of the lookup table instead
+        instructions[0].accounts[1] = legacy.account_keys.len() as u8;
+        let tx = VersionedTransaction {
+            signatures: vec![Default::default()],
+            message: VersionedMessage::V0(v0::Message {
+                header: legacy.header,
+                account_keys: legacy.account_keys.clone(),
+                recent_blockhash: legacy.recent_blockhash,
+                instructions,
+                address_table_lookups: vec![v0::MessageAddressTableLookup {
+                    account_key: table,
+                    writable_indexes: vec![4],
+                    readonly_indexes: vec![],
+                }],
+            }),
+        };
+
+        let summary = TransactionSummary::from_versioned_transaction(&tx);
+
+        assert_eq!(summary.fee_payer, payer.to_string());
+        assert_eq!(
+            summary.recent_blockhash,
+            legacy.recent_blockhash.to_string()
+        );
+        assert_eq!(
+            summary.instructions[0].accounts,
+            vec![payer.to_string(), format!("{table}[4]")]
+        );
+    }
+
+    #[test]
+    fn test_request_json_round_trip() {
+        let tx = create_test_transaction(&Pubkey::new_unique());
+        let request = OfflineSigningRequest::from_transaction(&tx).unwrap();
+
+        let parsed = OfflineSigningRequest::from_json(&request.to_json().unwrap()).unwrap();
+
+        assert_eq!(parsed, request);
+        assert_eq!(
+            parsed.decode_transaction().unwrap().message_data(),
+            tx.message_data()
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_checksum_mismatch() {
+        let tx = create_test_transaction(&Pubkey::new_unique());
+        let mut request = OfflineSigningRequest::from_transaction(&tx).unwrap();
+        request.checksum = message_checksum(b"something else");
+
+        assert!(matches!(
+            request.decode_transaction(),
+            Err(SignerError::SerializationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_rejects_signer_mismatch() {
+        let tx = create_test_transaction(&Pubkey::new_unique());
+        let mut request = OfflineSigningRequest::from_transaction(&tx).unwrap();
+        request.required_signers = vec![Pubkey::new_unique().to_string()];
+
+        assert!(request.decode_transaction().is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_summary_mismatch() {
+        let tx = create_test_transaction(&Pubkey::new_unique());
+        let mut request = OfflineSigningRequest::from_transaction(&tx).unwrap();
+        request.summary.instructions[0].data = hex::encode(b"harmless");
+
+        assert!(matches!(
+            request.decode_transaction(),
+            Err(SignerError::SerializationError(_))
+        ));
+
+        let mut request = OfflineSigningRequest::from_transaction(&tx).unwrap();
+        request.summary.durable_nonce_account = Some(Pubkey::new_unique().to_string());
+
+        assert!(request.decode_transaction().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_offline_round_trip() {
+        let payer = MemorySigner::new(Keypair::new());
+        let other = MemorySigner::new(Keypair::new());
+        let mut tx = create_two_signer_transaction(&payer.pubkey(), &other.pubkey());
+
+        let request = OfflineSigningRequest::from_transaction(&tx).unwrap();
+        assert_eq!(request.required_signers.len(), 2);
+
+        // Offline machine
+        let request = OfflineSigningRequest::from_json(&request.to_json().unwrap()).unwrap();
+        let response = request.sign(&[&payer, &other]).await.unwrap();
+        let response_json = response.to_json().unwrap();
+
+        // Back online
+        let response = OfflineSigningResponse::from_json(&response_json).unwrap();
+        let merged = response.merge_into(&mut tx).unwrap();
+
+        assert_eq!(merged, vec![payer.pubkey(), other.pubkey()]);
+        let message = tx.message_data();
+        assert!(tx.signatures[0].verify(payer.pubkey().as_ref(), &message));
+        assert!(tx.signatures[1].verify(other.pubkey().as_ref(), &message));
+    }
+
+    #[tokio::test]
+    async fn test_sign_rejects_unknown_signer() {
+        let payer = MemorySigner::new(Keypair::new());
+        let stranger = MemorySigner::new(Keypair::new());
+        let tx = create_test_transaction(&payer.pubkey());
+
+        let request = OfflineSigningRequest::from_transaction(&tx).unwrap();
+        let result = request.sign(&[&stranger]).await;
+
+        assert!(matches!(result, Err(SignerError::SigningFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_merge_rejects_invalid_signature() {
+        let payer = MemorySigner::new(Keypair::new());
+        let mut tx = create_test_transaction(&payer.pubkey());
+
+        let request = OfflineSigningRequest::from_transaction(&tx).unwrap();
+        let mut response = request.sign(&[&payer]).await.unwrap();
+        response.signatures[0].signature = payer
+            .sign_message(b"not the message")
+            .await
+            .unwrap()
+            .to_string();
+
+        let result = response.merge_into(&mut tx);
+
+        assert!(matches!(result, Err(SignerError::SigningFailed(_))));
+        assert_eq!(tx.signatures[0], Signature::default());
+    }
+
+    #[tokio::test]
+    async fn test_merge_rejects_different_message() {
+        let payer = MemorySigner::new(Keypair::new());
+        let tx = create_test_transaction(&payer.pubkey());
+
+        let request = OfflineSigningRequest::from_transaction(&tx).unwrap();
+        let response = request.sign(&[&payer]).await.unwrap();
+
+        let mut other_tx = create_test_transaction(&payer.pubkey());
+        let result = response.merge_into(&mut other_tx);
+
+        assert!(matches!(result, Err(SignerError::SigningFailed(_))));
+    }
+}
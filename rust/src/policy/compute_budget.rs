@@ -0,0 +1,300 @@
+//! Compute budget and priority-fee caps
+
+use crate::compute_budget_util::{ComputeBudgetLimits, ComputeBudgetUtil};
+use crate::error::SignerError;
+use crate::sdk_adapter::{Transaction, VersionedTransaction};
+use crate::traits::{SigningRequest, SigningResponse, TrezoaSigner};
+
+/// Configuration for [`ComputeBudgetPolicy`]
+///
+/// Every cap is optional; `None` leaves that value unchecked.
+#[derive(Debug, Clone, Default)]
+pub struct ComputeBudgetPolicyConfig {
+    /// Maximum compute unit price in micro-lamports
+    pub max_unit_price: Option<u64>,
+    /// Maximum compute unit limit, explicit or defaulted by the runtime
+    pub max_unit_limit: Option<u32>,
+    /// Maximum total priority fee in lamports
+    pub max_priority_fee_lamports: Option<u64>,
+    /// Maximum requested heap frame in bytes
+    pub max_heap_frame_bytes: Option<u32>,
+    /// Reject transactions without any Compute Budget instruction (default: false)
+    pub require_compute_budget: bool,
+}
+
+/// Enforces compute budget caps on every transaction before it is signed
+#[derive(Debug, Clone)]
+pub struct ComputeBudgetPolicy<S> {
+    inner: S,
+    config: ComputeBudgetPolicyConfig,
+}
+
+impl<S: TrezoaSigner> ComputeBudgetPolicy<S> {
+    pub fn new(inner: S, config: ComputeBudgetPolicyConfig) -> Self {
+        Self { inner, config }
+    }
+
+    /// The wrapped signer
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Check a transaction against the caps without signing it
+    ///
+    /// # Returns
+    ///
+    /// The decoded compute budget of the transaction
+    pub fn check_transaction(&self, tx: &Transaction) -> Result<ComputeBudgetLimits, SignerError> {
+        self.check_limits(ComputeBudgetUtil::parse(&tx.message)?)
+    }
+
+    /// [`check_transaction`](Self::check_transaction) for a versioned transaction
+    pub fn check_versioned_transaction(
+        &self,
+        tx: &VersionedTransaction,
+    ) -> Result<ComputeBudgetLimits, SignerError> {
+        self.check_limits(ComputeBudgetUtil::parse_versioned(&tx.message)?)
+    }
+
+    fn check_limits(
+        &self,
+        limits: ComputeBudgetLimits,
+    ) -> Result<ComputeBudgetLimits, SignerError> {
+        if self.config.require_compute_budget && !limits.has_compute_budget_instructions() {
+            return Err(SignerError::PolicyViolation(
+                "Transaction has no Compute Budget instructions".to_string(),
+            ));
+        }
+
+        if let (Some(max), Some(price)) = (self.config.max_unit_price, limits.unit_price) {
+            if price > max {
+                return Err(SignerError::PolicyViolation(format!(
+                    "Compute unit price of {price} micro-lamports exceeds the cap of {max}"
+                )));
+            }
+        }
+
+        if let Some(max) = self.config.max_unit_limit {
+            if limits.effective_unit_limit > max {
+                return Err(SignerError::PolicyViolation(format!(
+                    "Compute unit limit of {} exceeds the cap of {max}",
+                    limits.effective_unit_limit
+                )));
+            }
+        }
+
+        if let Some(max) = self.config.max_priority_fee_lamports {
+            let priority_fee = limits.priority_fee_lamports();
+            if priority_fee > max {
+                return Err(SignerError::PolicyViolation(format!(
+                    "Priority fee of {priority_fee} lamports exceeds the cap of {max}"
+                )));
+            }
+        }
+
+        if let (Some(max), Some(bytes)) =
+            (self.config.max_heap_frame_bytes, limits.heap_frame_bytes)
+        {
+            if bytes > max {
+                return Err(SignerError::PolicyViolation(format!(
+                    "Heap frame of {bytes} bytes exceeds the cap of {max}"
+                )));
+            }
+        }
+
+        Ok(limits)
+    }
+
+    async fn sign_request(&self, request: SigningRequest) -> Result<SigningResponse, SignerError> {
+        request.ensure_inspectable("Compute budget policy")?;
+        match &request {
+            SigningRequest::Transaction(tx) | SigningRequest::PartialTransaction(tx) => {
+                self.check_transaction(tx)?;
+            }
+            SigningRequest::VersionedTransaction(tx) => {
+                self.check_versioned_transaction(tx)?;
+            }
+            _ => {}
+        }
+
+        self.inner.sign(request).await
+    }
+}
+
+crate::traits::forward_to_sign!(ComputeBudgetPolicy);
+
+#[cfg(all(test, feature = "memory"))]
+mod tests {
+    use super::*;
+    use crate::memory::MemorySigner;
+    use crate::sdk_adapter::{Hash, Instruction, Keypair, Message, Pubkey, Signature};
+    use crate::test_util::create_transfer_instruction;
+
+    fn policy(config: ComputeBudgetPolicyConfig) -> ComputeBudgetPolicy<MemorySigner> {
+        ComputeBudgetPolicy::new(MemorySigner::new(Keypair::new()), config)
+    }
+
+    fn transaction(payer: &Pubkey, compute_budget: &[Instruction]) -> Transaction {
+        let mut instructions = compute_budget.to_vec();
+        instructions.push(create_transfer_instruction(
+            payer,
+            &Pubkey::new_unique(),
+            1_000,
+        ));
+        let mut message = Message::new(&instructions, Some(payer));
+        message.recent_blockhash = Hash::new_from_array([1u8; 32]);
+        Transaction::new_unsigned(message)
+    }
+
+    fn is_violation<T>(result: Result<T, SignerError>) -> bool {
+        matches!(result, Err(SignerError::PolicyViolation(_)))
+    }
+
+    #[tokio::test]
+    async fn test_signs_within_caps() {
+        let policy = policy(ComputeBudgetPolicyConfig {
+            max_unit_price: Some(10_000),
+            max_unit_limit: Some(200_000),
+            max_priority_fee_lamports: Some(2_000),
+            max_heap_frame_bytes: Some(64 * 1024),
+            require_compute_budget: true,
+        });
+        let mut tx = transaction(
+            &policy.pubkey(),
+            &[
+                ComputeBudgetUtil::set_compute_unit_limit_instruction(200_000),
+                ComputeBudgetUtil::set_compute_unit_price_instruction(10_000),
+            ],
+        );
+
+        policy.sign_transaction(&mut tx).await.unwrap();
+
+        assert!(tx.verify().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unit_price_over_cap() {
+        let policy = policy(ComputeBudgetPolicyConfig {
+            max_unit_price: Some(10_000),
+            ..Default::default()
+        });
+        let mut tx = transaction(
+            &policy.pubkey(),
+            &[ComputeBudgetUtil::set_compute_unit_price_instruction(
+                10_001,
+            )],
+        );
+
+        assert!(is_violation(policy.sign_transaction(&mut tx).await));
+        assert_eq!(tx.signatures[0], Signature::default());
+    }
+
+    #[tokio::test]
+    async fn test_checks_versioned_transactions() {
+        let policy = policy(ComputeBudgetPolicyConfig {
+            max_unit_price: Some(10_000),
+            ..Default::default()
+        });
+        let mut tx = VersionedTransaction::from(transaction(
+            &policy.pubkey(),
+            &[ComputeBudgetUtil::set_compute_unit_price_instruction(
+                10_001,
+            )],
+        ));
+
+        assert!(is_violation(
+            policy.sign_versioned_transaction(&mut tx).await
+        ));
+        assert!(is_violation(
+            policy
+                .sign(SigningRequest::VersionedTransaction(tx.clone()))
+                .await
+        ));
+        assert_eq!(tx.signatures[0], Signature::default());
+    }
+
+    #[test]
+    fn test_rejects_unit_limit_over_cap() {
+        let policy = policy(ComputeBudgetPolicyConfig {
+            max_unit_limit: Some(100_000),
+            ..Default::default()
+        });
+
+        // Without an explicit limit the runtime default applies
+        assert!(is_violation(
+            policy.check_transaction(&transaction(&policy.pubkey(), &[]))
+        ));
+        assert!(policy
+            .check_transaction(&transaction(
+                &policy.pubkey(),
+                &[ComputeBudgetUtil::set_compute_unit_limit_instruction(
+                    100_000
+                )],
+            ))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_rejects_priority_fee_over_cap() {
+        let policy = policy(ComputeBudgetPolicyConfig {
+            max_priority_fee_lamports: Some(1_000),
+            ..Default::default()
+        });
+        // Default limit of 200_000 CU at 10_000 micro-lamports = 2_000 lamports
+        let tx = transaction(
+            &policy.pubkey(),
+            &[ComputeBudgetUtil::set_compute_unit_price_instruction(
+                10_000,
+            )],
+        );
+
+        assert!(is_violation(policy.check_transaction(&tx)));
+    }
+
+    #[test]
+    fn test_rejects_heap_frame_over_cap() {
+        let policy = policy(ComputeBudgetPolicyConfig {
+            max_heap_frame_bytes: Some(32 * 1024),
+            ..Default::default()
+        });
+        let tx = transaction(
+            &policy.pubkey(),
+            &[ComputeBudgetUtil::request_heap_frame_instruction(64 * 1024)],
+        );
+
+        assert!(is_violation(policy.check_transaction(&tx)));
+    }
+
+    #[test]
+    fn test_require_compute_budget() {
+        let optional = policy(ComputeBudgetPolicyConfig::default());
+        let required = policy(ComputeBudgetPolicyConfig {
+            require_compute_budget: true,
+            ..Default::default()
+        });
+
+        assert!(optional
+            .check_transaction(&transaction(&optional.pubkey(), &[]))
+            .is_ok());
+        assert!(is_violation(
+            required.check_transaction(&transaction(&required.pubkey(), &[]))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_malformed_compute_budget() {
+        let policy = policy(ComputeBudgetPolicyConfig::default());
+        let tx = transaction(
+            &policy.pubkey(),
+            &[
+                ComputeBudgetUtil::set_compute_unit_price_instruction(1),
+                ComputeBudgetUtil::set_compute_unit_price_instruction(2),
+            ],
+        );
+
+        assert!(matches!(
+            policy.check_transaction(&tx),
+            Err(SignerError::SerializationError(_))
+        ));
+    }
+}
@@ -0,0 +1,298 @@
+//! Fee-payer drain protection for relayer keys
+
+use crate::compute_budget_util::{ComputeBudgetLimits, ComputeBudgetUtil};
+use crate::error::SignerError;
+use crate::sdk_adapter::{CompiledInstruction, Pubkey, Transaction, VersionedTransaction};
+use crate::traits::{SigningRequest, SigningResponse, TrezoaSigner};
+
+/// An instruction role the fee payer is allowed to take besides paying fees
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllowedRole {
+    /// Program whose instructions may reference the fee payer
+    pub program_id: Pubkey,
+    /// Position in the instruction's accounts, or any position if `None`
+    pub account_index: Option<u8>,
+}
+
+impl AllowedRole {
+    pub fn new(program_id: Pubkey, account_index: Option<u8>) -> Self {
+        Self {
+            program_id,
+            account_index,
+        }
+    }
+}
+
+/// Configuration for [`FeePayerGuard`]
+#[derive(Debug, Clone, Default)]
+pub struct FeePayerGuardConfig {
+    /// Roles the fee payer may take in instructions (default: none)
+    pub allowed_roles: Vec<AllowedRole>,
+    /// Maximum priority fee in lamports (default: no cap)
+    pub max_priority_fee_lamports: Option<u64>,
+}
+
+/// Signs only transactions where the wrapped key pays fees and nothing else
+///
+/// A relayer that pays fees for user-submitted transactions must not let the
+/// client make its key the source of a transfer, a token authority or any other
+/// role. The guard requires the key to be the fee payer (index 0) and to appear in
+/// no instruction except in an allowlisted role, and caps the priority fee.
+#[derive(Debug, Clone)]
+pub struct FeePayerGuard<S> {
+    inner: S,
+    config: FeePayerGuardConfig,
+}
+
+impl<S: TrezoaSigner> FeePayerGuard<S> {
+    pub fn new(inner: S, config: FeePayerGuardConfig) -> Self {
+        Self { inner, config }
+    }
+
+    /// The wrapped signer
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Check a transaction against the guard without signing it
+    pub fn check_transaction(&self, tx: &Transaction) -> Result<(), SignerError> {
+        let message = &tx.message;
+        self.check_accounts(&message.account_keys, &message.instructions, || {
+            ComputeBudgetUtil::parse(message)
+        })
+    }
+
+    /// [`check_transaction`](Self::check_transaction) for a versioned transaction
+    ///
+    /// Only static account keys are checked: the runtime rejects a transaction that
+    /// loads one of them again from an address lookup table.
+    pub fn check_versioned_transaction(
+        &self,
+        tx: &VersionedTransaction,
+    ) -> Result<(), SignerError> {
+        let message = &tx.message;
+        self.check_accounts(
+            message.static_account_keys(),
+            message.instructions(),
+            || ComputeBudgetUtil::parse_versioned(message),
+        )
+    }
+
+    fn check_accounts(
+        &self,
+        account_keys: &[Pubkey],
+        instructions: &[CompiledInstruction],
+        compute_budget: impl FnOnce() -> Result<ComputeBudgetLimits, SignerError>,
+    ) -> Result<(), SignerError> {
+        let fee_payer = self.inner.pubkey();
+
+        if account_keys.first() != Some(&fee_payer) {
+            return Err(SignerError::PolicyViolation(format!(
+                "{fee_payer} is not the fee payer"
+            )));
+        }
+
+        for (ix_index, ix) in instructions.iter().enumerate() {
+            let program_id = account_keys
+                .get(ix.program_id_index as usize)
+                .ok_or_else(|| {
+                    SignerError::SerializationError(
+                        "Invalid program id index in instruction".to_string(),
+                    )
+                })?;
+
+            for (position, account_index) in ix.accounts.iter().enumerate() {
+                if account_keys.get(*account_index as usize) != Some(&fee_payer) {
+                    continue;
+                }
+
+                let allowed = self.config.allowed_roles.iter().any(|role| {
+                    role.program_id == *program_id
+                        && match role.account_index {
+                            Some(index) => index as usize == position,
+                            None => true,
+                        }
+                });
+                if !allowed {
+                    return Err(SignerError::PolicyViolation(format!(
+                        "Fee payer is used by instruction {ix_index} ({program_id}) at account {position}"
+                    )));
+                }
+            }
+        }
+
+        if let Some(max) = self.config.max_priority_fee_lamports {
+            let priority_fee = compute_budget()?.priority_fee_lamports();
+            if priority_fee > max {
+                return Err(SignerError::PolicyViolation(format!(
+                    "Priority fee of {priority_fee} lamports exceeds the cap of {max}"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn sign_request(&self, request: SigningRequest) -> Result<SigningResponse, SignerError> {
+        request.ensure_inspectable("Fee payer guard")?;
+        match &request {
+            SigningRequest::Transaction(tx) | SigningRequest::PartialTransaction(tx) => {
+                self.check_transaction(tx)?;
+            }
+            SigningRequest::VersionedTransaction(tx) => {
+                self.check_versioned_transaction(tx)?;
+            }
+            _ => {}
+        }
+
+        self.inner.sign(request).await
+    }
+}
+
+crate::traits::forward_to_sign!(FeePayerGuard);
+
+#[cfg(all(test, feature = "memory"))]
+mod tests {
+    use super::*;
+    use crate::compute_budget_util::ComputeBudgetUtil;
+    use crate::memory::MemorySigner;
+    use crate::sdk_adapter::{AccountMeta, Hash, Instruction, Keypair, Message, Signature};
+    use crate::test_util::create_transfer_instruction;
+
+    fn guard(config: FeePayerGuardConfig) -> FeePayerGuard<MemorySigner> {
+        FeePayerGuard::new(MemorySigner::new(Keypair::new()), config)
+    }
+
+    fn transaction(instructions: &[Instruction], payer: &Pubkey) -> Transaction {
+        let mut message = Message::new(instructions, Some(payer));
+        message.recent_blockhash = Hash::new_from_array([1u8; 32]);
+        Transaction::new_unsigned(message)
+    }
+
+    fn user_transfer(user: &Pubkey) -> Instruction {
+        create_transfer_instruction(user, &Pubkey::new_unique(), 1_000)
+    }
+
+    #[tokio::test]
+    async fn test_signs_when_only_fee_payer() {
+        let guard = guard(FeePayerGuardConfig::default());
+        let user = MemorySigner::new(Keypair::new());
+        let mut tx = transaction(&[user_transfer(&user.pubkey())], &guard.pubkey());
+
+        user.sign_partial_transaction(&mut tx).await.unwrap();
+        guard.sign_transaction(&mut tx).await.unwrap();
+
+        assert!(tx.verify().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_when_not_fee_payer() {
+        let guard = guard(FeePayerGuardConfig::default());
+        let user = Pubkey::new_unique();
+        let mut message = Message::new(&[user_transfer(&user)], Some(&user));
+        message.account_keys.push(guard.pubkey());
+        let mut tx = Transaction::new_unsigned(message);
+
+        let result = guard.sign_partial_transaction(&mut tx).await;
+
+        assert!(matches!(result, Err(SignerError::PolicyViolation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_transfer_from_fee_payer() {
+        let guard = guard(FeePayerGuardConfig::default());
+        let mut tx = transaction(&[user_transfer(&guard.pubkey())], &guard.pubkey());
+
+        let result = guard.sign_transaction(&mut tx).await;
+
+        assert!(matches!(result, Err(SignerError::PolicyViolation(_))));
+        assert_eq!(tx.signatures[0], Signature::default());
+    }
+
+    #[tokio::test]
+    async fn test_checks_versioned_transactions() {
+        let guard = guard(FeePayerGuardConfig::default());
+        let user = Pubkey::new_unique();
+        let tx = transaction(&[user_transfer(&user)], &guard.pubkey());
+        let mut drain = VersionedTransaction::from(transaction(
+            &[user_transfer(&guard.pubkey())],
+            &guard.pubkey(),
+        ));
+
+        let result = guard
+            .sign(SigningRequest::VersionedTransaction(tx.into()))
+            .await;
+
+        assert!(matches!(
+            result,
+            Ok(SigningResponse::VersionedTransaction { .. })
+        ));
+        assert!(matches!(
+            guard.sign_versioned_transaction(&mut drain).await,
+            Err(SignerError::PolicyViolation(_))
+        ));
+    }
+
+    #[test]
+    fn test_allowlisted_role() {
+        let funding_program = Pubkey::new_unique();
+        let guard = guard(FeePayerGuardConfig {
+            allowed_roles: vec![AllowedRole::new(funding_program, Some(0))],
+            ..Default::default()
+        });
+        let fund = |position: usize| {
+            let mut accounts = vec![AccountMeta::new(Pubkey::new_unique(), false)];
+            accounts.insert(position, AccountMeta::new(guard.pubkey(), true));
+            Instruction {
+                program_id: funding_program,
+                accounts,
+                data: vec![],
+            }
+        };
+
+        assert!(guard
+            .check_transaction(&transaction(&[fund(0)], &guard.pubkey()))
+            .is_ok());
+        assert!(guard
+            .check_transaction(&transaction(&[fund(1)], &guard.pubkey()))
+            .is_err());
+
+        // Allowlisting one program does not cover the System program
+        let tx = transaction(&[fund(0), user_transfer(&guard.pubkey())], &guard.pubkey());
+        assert!(guard.check_transaction(&tx).is_err());
+    }
+
+    #[test]
+    fn test_caps_priority_fee() {
+        let guard = guard(FeePayerGuardConfig {
+            max_priority_fee_lamports: Some(5_000),
+            ..Default::default()
+        });
+        let user = Pubkey::new_unique();
+        let with_price = |micro_lamports: u64| {
+            transaction(
+                &[
+                    ComputeBudgetUtil::set_compute_unit_limit_instruction(100_000),
+                    ComputeBudgetUtil::set_compute_unit_price_instruction(micro_lamports),
+                    user_transfer(&user),
+                ],
+                &guard.pubkey(),
+            )
+        };
+
+        // 100_000 CU at 50_000 micro-lamports = 5_000 lamports
+        assert!(guard.check_transaction(&with_price(50_000)).is_ok());
+        assert!(matches!(
+            guard.check_transaction(&with_price(50_001)),
+            Err(SignerError::PolicyViolation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unchecked_messages() {
+        let guard = guard(FeePayerGuardConfig::default());
+
+        assert!(guard.sign_message(b"hello").await.is_ok());
+        assert!(guard.sign_message_unchecked(b"hello").await.is_err());
+    }
+}
@@ -0,0 +1,14 @@
+//! Signing policies
+//!
+//! Policies wrap any [`TrezoaSigner`](crate::traits::TrezoaSigner) and inspect each
+//! request before forwarding it to the wrapped signer.
+
+mod compute_budget;
+mod fee_payer;
+mod replay;
+mod replay_store;
+
+pub use compute_budget::{ComputeBudgetPolicy, ComputeBudgetPolicyConfig};
+pub use fee_payer::{AllowedRole, FeePayerGuard, FeePayerGuardConfig};
+pub use replay::{ReplayAction, ReplayProtection, ReplayProtectionConfig};
+pub use replay_store::{FileReplayStore, MemoryReplayStore, ReplayKind, ReplayRecord, ReplayStore};
@@ -0,0 +1,463 @@
+//! Replay protection: refuse to sign the same payload twice within a window
+
+use super::replay_store::{ReplayKind, ReplayRecord, ReplayStore};
+use crate::error::SignerError;
+use crate::sdk_adapter::{hashv, Hash, Signature, Transaction, VersionedTransaction};
+use crate::traits::{SigningRequest, SigningResponse, TrezoaSigner};
+use crate::transaction_util::TransactionUtil;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// What to do when a payload was already signed within the window
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReplayAction {
+    /// Fail with a policy violation
+    #[default]
+    Reject,
+    /// Return the signature (and signed transaction) produced the first time
+    ///
+    /// A cached transaction is only returned for the exact same message. With
+    /// `ignore_blockhash`, a retry under a new blockhash is rejected instead, since the
+    /// cached copy carries the old blockhash and would not land.
+    ReturnCached,
+}
+
+/// Configuration for [`ReplayProtection`]
+#[derive(Debug, Clone)]
+pub struct ReplayProtectionConfig {
+    /// How long a signed payload is remembered (default: 120 seconds)
+    pub window: Duration,
+    /// Treat transactions that differ only in `recent_blockhash` as identical (default: false)
+    pub ignore_blockhash: bool,
+    /// Behaviour on a repeated payload (default: reject)
+    pub action: ReplayAction,
+}
+
+impl Default for ReplayProtectionConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(120),
+            ignore_blockhash: false,
+            action: ReplayAction::Reject,
+        }
+    }
+}
+
+/// De-duplicates signing requests by a canonical hash of the payload
+///
+/// Concurrent identical requests are rejected while the first one is in flight,
+/// whatever the configured action.
+pub struct ReplayProtection<S> {
+    inner: S,
+    store: Arc<dyn ReplayStore>,
+    config: ReplayProtectionConfig,
+}
+
+impl<S: TrezoaSigner> std::fmt::Debug for ReplayProtection<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReplayProtection")
+            .field("pubkey", &self.inner.pubkey())
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: TrezoaSigner> ReplayProtection<S> {
+    pub fn new(inner: S, store: Arc<dyn ReplayStore>, config: ReplayProtectionConfig) -> Self {
+        Self {
+            inner,
+            store,
+            config,
+        }
+    }
+
+    /// The wrapped signer
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Canonical hash of the bytes a signature covers, for replay detection
+    ///
+    /// A transaction message hashes the same whichever entry point signs it. With
+    /// `ignore_blockhash`, the recent blockhash of a transaction message is zeroed
+    /// before hashing.
+    pub fn canonical_message_hash(message: &[u8], ignore_blockhash: bool) -> Hash {
+        if ignore_blockhash {
+            if let Some(mut parsed) = TransactionUtil::parse_transaction_message(message) {
+                parsed.set_recent_blockhash(Hash::default());
+                return hashv(&[&parsed.serialize()]);
+            }
+        }
+        hashv(&[message])
+    }
+
+    /// How a decoded request is signed
+    fn kind(request: &SigningRequest) -> ReplayKind {
+        match request {
+            SigningRequest::Transaction(_) | SigningRequest::SerializedTransaction(_) => {
+                ReplayKind::Transaction
+            }
+            SigningRequest::PartialTransaction(_) => ReplayKind::PartialTransaction,
+            SigningRequest::VersionedTransaction(_) => ReplayKind::VersionedTransaction,
+            SigningRequest::Message(_) | SigningRequest::OffchainMessage(_) => ReplayKind::Message,
+            SigningRequest::UncheckedMessage(_) => ReplayKind::UncheckedMessage,
+        }
+    }
+
+    /// Replay key of a decoded request
+    ///
+    /// Only the signer and the signed bytes count, so the same message can't be signed
+    /// again through another entry point.
+    fn key(&self, request: &SigningRequest) -> Result<String, SignerError> {
+        let payload_hash =
+            Self::canonical_message_hash(&request.message_bytes()?, self.config.ignore_blockhash);
+
+        let pubkey = self.inner.pubkey();
+        Ok(hashv(&[pubkey.as_ref(), payload_hash.as_ref()]).to_string())
+    }
+
+    fn replayed(&self, record: &ReplayRecord) -> Result<Signature, SignerError> {
+        match (&self.config.action, &record.signature) {
+            (ReplayAction::ReturnCached, Some(signature)) => Signature::from_str(signature)
+                .map_err(|e| SignerError::SerializationError(format!("Invalid signature: {e}"))),
+            (ReplayAction::ReturnCached, None) => Err(SignerError::PolicyViolation(
+                "Identical payload is already being signed".to_string(),
+            )),
+            (ReplayAction::Reject, _) => Err(SignerError::PolicyViolation(
+                "Identical payload was already signed within the replay window".to_string(),
+            )),
+        }
+    }
+
+    /// The response recorded for a replayed request
+    fn replayed_response(
+        &self,
+        record: &ReplayRecord,
+        request: &SigningRequest,
+    ) -> Result<SigningResponse, SignerError> {
+        let signature = self.replayed(record)?;
+        let serialized = || {
+            record.serialized_transaction.clone().ok_or_else(|| {
+                SignerError::PolicyViolation("No cached transaction to return".to_string())
+            })
+        };
+        let different_blockhash = || {
+            SignerError::PolicyViolation(
+                "Identical payload was already signed with a different blockhash".to_string(),
+            )
+        };
+
+        // Hand back the transaction exactly as it was signed the first time. Legacy and
+        // versioned transactions share a wire format, so either form can answer the
+        // other, but a partial signature must never answer a full signing request.
+        match (record.kind, request) {
+            (
+                ReplayKind::Transaction | ReplayKind::VersionedTransaction,
+                SigningRequest::Transaction(tx),
+            )
+            | (ReplayKind::PartialTransaction, SigningRequest::PartialTransaction(tx)) => {
+                let serialized = serialized()?;
+                let transaction = TransactionUtil::deserialize_transaction(&serialized)?;
+                if transaction.message != tx.message {
+                    return Err(different_blockhash());
+                }
+                Ok(SigningResponse::Transaction {
+                    transaction,
+                    serialized,
+                    signature,
+                })
+            }
+            (
+                ReplayKind::Transaction | ReplayKind::VersionedTransaction,
+                SigningRequest::VersionedTransaction(tx),
+            ) => {
+                let serialized = serialized()?;
+                let transaction = TransactionUtil::deserialize_versioned_transaction(&serialized)?;
+                if transaction.message != tx.message {
+                    return Err(different_blockhash());
+                }
+                Ok(SigningResponse::VersionedTransaction {
+                    transaction,
+                    serialized,
+                    signature,
+                })
+            }
+            (kind, request) if kind == Self::kind(request) => {
+                Ok(SigningResponse::Signature(signature))
+            }
+            _ => Err(SignerError::PolicyViolation(
+                "Identical payload was already signed through a different signing method"
+                    .to_string(),
+            )),
+        }
+    }
+
+    async fn sign_request(&self, request: SigningRequest) -> Result<SigningResponse, SignerError> {
+        let key = self.key(&request)?;
+
+        if let Some(record) = self
+            .store
+            .reserve(&key, Self::kind(&request), self.config.window)
+            .await?
+        {
+            return self.replayed_response(&record, &request);
+        }
+
+        // A store failure must not hide the signing outcome: losing the signature would
+        // leave the key reserved and refuse every retry within the window
+        match self.inner.sign(request).await {
+            Ok(response) => {
+                if let Err(e) = self
+                    .store
+                    .complete(
+                        &key,
+                        response.signature().to_string(),
+                        response.serialized().map(str::to_string),
+                    )
+                    .await
+                {
+                    log::error!(
+                        "Failed to record signature {} for replay protection: {e}",
+                        response.signature()
+                    );
+                }
+                Ok(response)
+            }
+            Err(e) => {
+                if let Err(release_error) = self.store.release(&key).await {
+                    log::error!("Failed to release replay reservation: {release_error}");
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+crate::traits::forward_to_sign!(ReplayProtection);
+
+#[cfg(all(test, feature = "memory"))]
+mod tests {
+    use super::*;
+    use crate::memory::MemorySigner;
+    use crate::policy::MemoryReplayStore;
+    use crate::sdk_adapter::{Keypair, Message, Pubkey};
+    use crate::test_util::create_transfer_instruction;
+
+    fn protected(config: ReplayProtectionConfig) -> ReplayProtection<MemorySigner> {
+        ReplayProtection::new(
+            MemorySigner::new(Keypair::new()),
+            Arc::new(MemoryReplayStore::new()),
+            config,
+        )
+    }
+
+    fn transfer(payer: &Pubkey, recipient: &Pubkey, blockhash: u8) -> Transaction {
+        let mut message = Message::new(
+            &[create_transfer_instruction(payer, recipient, 1_000)],
+            Some(payer),
+        );
+        message.recent_blockhash = Hash::new_from_array([blockhash; 32]);
+        Transaction::new_unsigned(message)
+    }
+
+    #[tokio::test]
+    async fn test_rejects_identical_transaction() {
+        let signer = protected(ReplayProtectionConfig::default());
+        let recipient = Pubkey::new_unique();
+
+        let mut first = transfer(&signer.pubkey(), &recipient, 1);
+        let mut second = first.clone();
+        signer.sign_transaction(&mut first).await.unwrap();
+
+        let result = signer.sign_transaction(&mut second).await;
+        assert!(matches!(result, Err(SignerError::PolicyViolation(_))));
+
+        // A new blockhash is a different payload unless the blockhash is ignored
+        let mut refreshed = transfer(&signer.pubkey(), &recipient, 2);
+        assert!(signer.sign_transaction(&mut refreshed).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_ignore_blockhash() {
+        let signer = protected(ReplayProtectionConfig {
+            ignore_blockhash: true,
+            ..Default::default()
+        });
+        let recipient = Pubkey::new_unique();
+
+        let mut first = transfer(&signer.pubkey(), &recipient, 1);
+        let mut retried = transfer(&signer.pubkey(), &recipient, 2);
+        let mut other = transfer(&signer.pubkey(), &Pubkey::new_unique(), 2);
+
+        signer.sign_transaction(&mut first).await.unwrap();
+        assert!(signer.sign_transaction(&mut retried).await.is_err());
+        assert!(signer.sign_transaction(&mut other).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_return_cached() {
+        let signer = protected(ReplayProtectionConfig {
+            ignore_blockhash: true,
+            action: ReplayAction::ReturnCached,
+            ..Default::default()
+        });
+        let recipient = Pubkey::new_unique();
+
+        let mut first = transfer(&signer.pubkey(), &recipient, 1);
+        let (serialized, signature) = signer.sign_transaction(&mut first).await.unwrap();
+
+        let mut retried = transfer(&signer.pubkey(), &recipient, 1);
+        let (cached_serialized, cached_signature) =
+            signer.sign_transaction(&mut retried).await.unwrap();
+
+        assert_eq!(cached_serialized, serialized);
+        assert_eq!(cached_signature, signature);
+        assert_eq!(retried, first);
+
+        // The cached copy has the old blockhash, so a refreshed retry is not served from it
+        let mut refreshed = transfer(&signer.pubkey(), &recipient, 2);
+        let original = refreshed.clone();
+        assert!(matches!(
+            signer.sign_transaction(&mut refreshed).await,
+            Err(SignerError::PolicyViolation(_))
+        ));
+        assert_eq!(refreshed, original);
+
+        let message_signature = signer.sign_message(b"hello").await.unwrap();
+        assert_eq!(
+            signer.sign_message(b"hello").await.unwrap(),
+            message_signature
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_same_message_through_other_entry_points() {
+        let signer = protected(ReplayProtectionConfig::default());
+        let recipient = Pubkey::new_unique();
+
+        let mut tx = transfer(&signer.pubkey(), &recipient, 1);
+        signer.sign_transaction(&mut tx).await.unwrap();
+
+        let mut partial = transfer(&signer.pubkey(), &recipient, 1);
+        let mut versioned = VersionedTransaction::from(transfer(&signer.pubkey(), &recipient, 1));
+        assert!(matches!(
+            signer.sign_partial_transaction(&mut partial).await,
+            Err(SignerError::PolicyViolation(_))
+        ));
+        assert!(matches!(
+            signer.sign_versioned_transaction(&mut versioned).await,
+            Err(SignerError::PolicyViolation(_))
+        ));
+        assert!(matches!(
+            signer.sign_message_unchecked(&tx.message_data()).await,
+            Err(SignerError::PolicyViolation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_return_cached_across_entry_points() {
+        let signer = protected(ReplayProtectionConfig {
+            action: ReplayAction::ReturnCached,
+            ..Default::default()
+        });
+        let recipient = Pubkey::new_unique();
+
+        let mut tx = transfer(&signer.pubkey(), &recipient, 1);
+        let (serialized, signature) = signer.sign_transaction(&mut tx).await.unwrap();
+
+        // A legacy transaction re-signed in versioned form gets the cached copy
+        let mut versioned = VersionedTransaction::from(transfer(&signer.pubkey(), &recipient, 1));
+        let (cached_serialized, cached_signature) = signer
+            .sign_versioned_transaction(&mut versioned)
+            .await
+            .unwrap();
+        assert_eq!(cached_serialized, serialized);
+        assert_eq!(cached_signature, signature);
+        assert_eq!(versioned, VersionedTransaction::from(tx.clone()));
+
+        // Other signing methods can't be answered with the cached transaction
+        let mut partial = transfer(&signer.pubkey(), &recipient, 1);
+        assert!(matches!(
+            signer.sign_partial_transaction(&mut partial).await,
+            Err(SignerError::PolicyViolation(_))
+        ));
+        assert!(matches!(
+            signer.sign_message_unchecked(&tx.message_data()).await,
+            Err(SignerError::PolicyViolation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_failed_signing_releases_reservation() {
+        let signer = protected(ReplayProtectionConfig::default());
+        let mut tx = transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1);
+
+        // Not a signer of this transaction, so the inner signer fails
+        assert!(matches!(
+            signer.sign_transaction(&mut tx).await,
+            Err(SignerError::InvalidTransaction(_))
+        ));
+        assert!(matches!(
+            signer.sign_transaction(&mut tx).await,
+            Err(SignerError::InvalidTransaction(_))
+        ));
+    }
+
+    /// Reserves in memory but fails to record outcomes
+    struct FailingStore(MemoryReplayStore);
+
+    #[async_trait::async_trait]
+    impl ReplayStore for FailingStore {
+        async fn reserve(
+            &self,
+            key: &str,
+            kind: ReplayKind,
+            ttl: Duration,
+        ) -> Result<Option<ReplayRecord>, SignerError> {
+            self.0.reserve(key, kind, ttl).await
+        }
+
+        async fn complete(
+            &self,
+            _key: &str,
+            _signature: String,
+            _serialized_transaction: Option<String>,
+        ) -> Result<(), SignerError> {
+            Err(SignerError::IoError("disk full".to_string()))
+        }
+
+        async fn release(&self, _key: &str) -> Result<(), SignerError> {
+            Err(SignerError::IoError("disk full".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_store_failures_keep_the_signing_outcome() {
+        let signer = ReplayProtection::new(
+            MemorySigner::new(Keypair::new()),
+            Arc::new(FailingStore(MemoryReplayStore::new())),
+            ReplayProtectionConfig::default(),
+        );
+
+        let mut tx = transfer(&signer.pubkey(), &Pubkey::new_unique(), 1);
+        let (_, signature) = signer.sign_transaction(&mut tx).await.unwrap();
+        assert_eq!(tx.signatures[0], signature);
+
+        let mut foreign = transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1);
+        assert!(matches!(
+            signer.sign_transaction(&mut foreign).await,
+            Err(SignerError::InvalidTransaction(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_identical_message() {
+        let signer = protected(ReplayProtectionConfig::default());
+
+        signer.sign_message(b"hello").await.unwrap();
+
+        assert!(signer.sign_message(b"hello").await.is_err());
+        assert!(signer.sign_message(b"world").await.is_ok());
+    }
+}
@@ -0,0 +1,281 @@
+//! TTL stores for replay protection
+
+use crate::error::SignerError;
+use crate::store_util::{self, now_secs};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// How a replayed payload was signed, which decides the shape of a cached response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReplayKind {
+    Transaction,
+    PartialTransaction,
+    VersionedTransaction,
+    Message,
+    UncheckedMessage,
+}
+
+/// A payload that was signed, or is being signed, within the replay window
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayRecord {
+    /// How the payload was first signed
+    pub kind: ReplayKind,
+    /// Base58-encoded signature, `None` while signing is in flight
+    pub signature: Option<String>,
+    /// Base64-encoded signed transaction, for transaction requests
+    pub serialized_transaction: Option<String>,
+    /// Unix timestamp (seconds) after which the record is ignored
+    pub expires_at: u64,
+}
+
+impl ReplayRecord {
+    fn is_expired(&self, now: u64) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// Storage for replay records
+///
+/// Implementations must make `reserve` atomic, so that two identical requests
+/// arriving together cannot both be signed.
+#[async_trait]
+pub trait ReplayStore: Send + Sync {
+    /// Reserve `key` for a `kind` request for `ttl` unless a live record exists
+    ///
+    /// # Returns
+    ///
+    /// The existing record if `key` is already taken, `None` if it was reserved
+    async fn reserve(
+        &self,
+        key: &str,
+        kind: ReplayKind,
+        ttl: Duration,
+    ) -> Result<Option<ReplayRecord>, SignerError>;
+
+    /// Store the result of signing a reserved key
+    async fn complete(
+        &self,
+        key: &str,
+        signature: String,
+        serialized_transaction: Option<String>,
+    ) -> Result<(), SignerError>;
+
+    /// Drop a reservation after signing failed
+    async fn release(&self, key: &str) -> Result<(), SignerError>;
+}
+
+fn reserve_in(
+    records: &mut HashMap<String, ReplayRecord>,
+    key: &str,
+    kind: ReplayKind,
+    ttl: Duration,
+) -> Option<ReplayRecord> {
+    let now = now_secs();
+    records.retain(|_, record| !record.is_expired(now));
+
+    if let Some(existing) = records.get(key) {
+        return Some(existing.clone());
+    }
+
+    records.insert(
+        key.to_string(),
+        ReplayRecord {
+            kind,
+            signature: None,
+            serialized_transaction: None,
+            expires_at: now.saturating_add(ttl.as_secs().max(1)),
+        },
+    );
+    None
+}
+
+fn complete_in(
+    records: &mut HashMap<String, ReplayRecord>,
+    key: &str,
+    signature: String,
+    serialized_transaction: Option<String>,
+) {
+    if let Some(record) = records.get_mut(key) {
+        record.signature = Some(signature);
+        record.serialized_transaction = serialized_transaction;
+    }
+}
+
+/// In-memory replay store, lost on restart
+#[derive(Debug, Default)]
+pub struct MemoryReplayStore {
+    records: Mutex<HashMap<String, ReplayRecord>>,
+}
+
+impl MemoryReplayStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn records(&self) -> std::sync::MutexGuard<'_, HashMap<String, ReplayRecord>> {
+        store_util::lock(&self.records)
+    }
+}
+
+#[async_trait]
+impl ReplayStore for MemoryReplayStore {
+    async fn reserve(
+        &self,
+        key: &str,
+        kind: ReplayKind,
+        ttl: Duration,
+    ) -> Result<Option<ReplayRecord>, SignerError> {
+        Ok(reserve_in(&mut self.records(), key, kind, ttl))
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        signature: String,
+        serialized_transaction: Option<String>,
+    ) -> Result<(), SignerError> {
+        complete_in(&mut self.records(), key, signature, serialized_transaction);
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> Result<(), SignerError> {
+        self.records().remove(key);
+        Ok(())
+    }
+}
+
+/// Replay store persisted to a JSON file, so the window survives restarts
+#[derive(Debug)]
+pub struct FileReplayStore {
+    path: PathBuf,
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl FileReplayStore {
+    /// Create a store backed by `path`; the file is created on first write
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    async fn load(&self) -> Result<HashMap<String, ReplayRecord>, SignerError> {
+        store_util::read_json(&self.path).await
+    }
+
+    async fn save(&self, records: &HashMap<String, ReplayRecord>) -> Result<(), SignerError> {
+        store_util::write_json(&self.path, records).await
+    }
+}
+
+#[async_trait]
+impl ReplayStore for FileReplayStore {
+    async fn reserve(
+        &self,
+        key: &str,
+        kind: ReplayKind,
+        ttl: Duration,
+    ) -> Result<Option<ReplayRecord>, SignerError> {
+        let _guard = self.lock.lock().await;
+        let mut records = self.load().await?;
+        let existing = reserve_in(&mut records, key, kind, ttl);
+        if existing.is_none() {
+            self.save(&records).await?;
+        }
+        Ok(existing)
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        signature: String,
+        serialized_transaction: Option<String>,
+    ) -> Result<(), SignerError> {
+        let _guard = self.lock.lock().await;
+        let mut records = self.load().await?;
+        complete_in(&mut records, key, signature, serialized_transaction);
+        self.save(&records).await
+    }
+
+    async fn release(&self, key: &str) -> Result<(), SignerError> {
+        let _guard = self.lock.lock().await;
+        let mut records = self.load().await?;
+        if records.remove(key).is_some() {
+            self.save(&records).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+    const KIND: ReplayKind = ReplayKind::Transaction;
+
+    async fn exercise_store(store: &dyn ReplayStore) {
+        assert_eq!(store.reserve("a", KIND, TTL).await.unwrap(), None);
+
+        let pending = store.reserve("a", KIND, TTL).await.unwrap().unwrap();
+        assert_eq!(pending.kind, KIND);
+        assert_eq!(pending.signature, None);
+
+        store
+            .complete("a", "sig".to_string(), Some("tx".to_string()))
+            .await
+            .unwrap();
+        let completed = store.reserve("a", KIND, TTL).await.unwrap().unwrap();
+        assert_eq!(completed.signature.as_deref(), Some("sig"));
+        assert_eq!(completed.serialized_transaction.as_deref(), Some("tx"));
+
+        assert_eq!(store.reserve("b", KIND, TTL).await.unwrap(), None);
+        store.release("b").await.unwrap();
+        assert_eq!(store.reserve("b", KIND, TTL).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_memory_store() {
+        exercise_store(&MemoryReplayStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_file_store_persists() {
+        let path = std::env::temp_dir().join(format!(
+            "trezoa-keychain-replay-{}.json",
+            rand::random::<u64>()
+        ));
+
+        exercise_store(&FileReplayStore::new(&path)).await;
+
+        // A new store on the same file sees the existing records
+        let reopened = FileReplayStore::new(&path);
+        assert!(reopened.reserve("a", KIND, TTL).await.unwrap().is_some());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_expired_records_are_purged() {
+        let mut records = HashMap::new();
+        records.insert(
+            "old".to_string(),
+            ReplayRecord {
+                kind: KIND,
+                signature: Some("sig".to_string()),
+                serialized_transaction: None,
+                expires_at: now_secs() - 1,
+            },
+        );
+
+        assert_eq!(reserve_in(&mut records, "old", KIND, TTL), None);
+        assert_eq!(records["old"].signature, None);
+    }
+}
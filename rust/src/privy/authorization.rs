@@ -0,0 +1,194 @@
+//! Privy authorization signatures for wallets with owners or key quorums
+//!
+//! Each request to such a wallet carries a `privy-authorization-signature` header: P-256
+//! ECDSA signatures over the canonical JSON (RFC 8785) of the request, comma-separated when
+//! several keys sign.
+
+use crate::error::SignerError;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use p256::ecdsa::{signature::Signer as _, Signature, SigningKey};
+use p256::pkcs8::DecodePrivateKey;
+use serde_json::{json, Value};
+
+/// Header carrying the authorization signatures
+pub const AUTHORIZATION_SIGNATURE_HEADER: &str = "privy-authorization-signature";
+
+/// Prefix of authorization private keys exported from the Privy dashboard
+const KEY_PREFIX: &str = "wallet-auth:";
+
+/// P-256 authorization private key
+#[derive(Clone)]
+pub struct PrivyAuthorizationKey {
+    signing_key: SigningKey,
+}
+
+impl std::fmt::Debug for PrivyAuthorizationKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrivyAuthorizationKey")
+            .finish_non_exhaustive()
+    }
+}
+
+impl PrivyAuthorizationKey {
+    /// Parse a base64 PKCS#8 private key, with or without the `wallet-auth:` prefix
+    pub fn from_private_key(private_key: &str) -> Result<Self, SignerError> {
+        let private_key = private_key.trim();
+        let private_key = private_key.strip_prefix(KEY_PREFIX).unwrap_or(private_key);
+
+        let der = STANDARD.decode(private_key).map_err(|e| {
+            SignerError::InvalidPrivateKey(format!("Failed to decode authorization key: {e}"))
+        })?;
+        let signing_key = SigningKey::from_pkcs8_der(&der).map_err(|e| {
+            SignerError::InvalidPrivateKey(format!("Invalid authorization key: {e}"))
+        })?;
+
+        Ok(Self { signing_key })
+    }
+
+    /// Base64 DER signature over `payload`
+    fn sign(&self, payload: &[u8]) -> String {
+        let signature: Signature = self.signing_key.sign(payload);
+        STANDARD.encode(signature.to_der().as_bytes())
+    }
+}
+
+/// The request fields covered by authorization signatures
+pub fn authorization_payload(method: &str, url: &str, body: &Value, app_id: &str) -> Value {
+    json!({
+        "version": 1,
+        "method": method,
+        "url": url,
+        "body": body,
+        "headers": { "privy-app-id": app_id }
+    })
+}
+
+/// `privy-authorization-signature` header value for a request signed by `keys`
+pub fn authorization_signature(keys: &[PrivyAuthorizationKey], payload: &Value) -> String {
+    let canonical = canonical_json(payload);
+    keys.iter()
+        .map(|key| key.sign(canonical.as_bytes()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Serialize `value` as RFC 8785 canonical JSON
+///
+/// Object members are sorted by their UTF-16 code units and no whitespace is emitted.
+/// Privy request bodies only hold strings, integers and booleans, whose serde_json
+/// formatting already matches the RFC.
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(members) => {
+            let mut members: Vec<_> = members.iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            out.push('{');
+            for (i, (key, member)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(member, out);
+            }
+            out.push('}');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::{signature::Verifier, VerifyingKey};
+    use p256::pkcs8::EncodePrivateKey;
+
+    fn test_key() -> (String, VerifyingKey) {
+        let signing_key = SigningKey::random(&mut rand::thread_rng());
+        let der = signing_key.to_pkcs8_der().unwrap();
+        (
+            format!("{KEY_PREFIX}{}", STANDARD.encode(der.as_bytes())),
+            *signing_key.verifying_key(),
+        )
+    }
+
+    #[test]
+    fn test_canonical_json() {
+        let value = json!({
+            "b": [1, "two", { "z": true, "a": null }],
+            "a": "é\n",
+            "c": {}
+        });
+
+        assert_eq!(
+            canonical_json(&value),
+            r#"{"a":"é\n","b":[1,"two",{"a":null,"z":true}],"c":{}}"#
+        );
+    }
+
+    #[test]
+    fn test_authorization_signature() {
+        let (first, first_verifier) = test_key();
+        let (second, second_verifier) = test_key();
+        let keys = [
+            PrivyAuthorizationKey::from_private_key(&first).unwrap(),
+            // The prefix is optional
+            PrivyAuthorizationKey::from_private_key(first.strip_prefix(KEY_PREFIX).unwrap())
+                .unwrap(),
+            PrivyAuthorizationKey::from_private_key(&second).unwrap(),
+        ];
+        let payload = authorization_payload(
+            "POST",
+            "https://api.privy.io/v1/wallets/w/rpc",
+            &json!({ "method": "signMessage" }),
+            "app",
+        );
+
+        let header = authorization_signature(&keys, &payload);
+        let signatures: Vec<Signature> = header
+            .split(',')
+            .map(|s| Signature::from_der(&STANDARD.decode(s).unwrap()).unwrap())
+            .collect();
+
+        let canonical = canonical_json(&payload);
+        assert_eq!(signatures.len(), 3);
+        assert!(first_verifier
+            .verify(canonical.as_bytes(), &signatures[0])
+            .is_ok());
+        assert!(first_verifier
+            .verify(canonical.as_bytes(), &signatures[1])
+            .is_ok());
+        assert!(second_verifier
+            .verify(canonical.as_bytes(), &signatures[2])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        assert!(matches!(
+            PrivyAuthorizationKey::from_private_key("wallet-auth:not-base64!"),
+            Err(SignerError::InvalidPrivateKey(_))
+        ));
+        assert!(matches!(
+            PrivyAuthorizationKey::from_private_key(&STANDARD.encode([0u8; 16])),
+            Err(SignerError::InvalidPrivateKey(_))
+        ));
+    }
+}
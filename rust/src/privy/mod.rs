@@ -1,14 +1,33 @@
 //! Privy API signer integration
 
+mod authorization;
 mod types;
 
-use crate::sdk_adapter::{Pubkey, Signature, Transaction};
+pub use authorization::PrivyAuthorizationKey;
+
+use crate::sdk_adapter::{Pubkey, Signature, Transaction, VersionedTransaction};
 use crate::traits::SignedTransaction;
 use crate::transaction_util::TransactionUtil;
 use crate::{error::SignerError, traits::TrezoaSigner};
+use authorization::{
+    authorization_payload, authorization_signature, AUTHORIZATION_SIGNATURE_HEADER,
+};
 use base64::{engine::general_purpose::STANDARD, Engine};
-use std::str::FromStr;
-use types::{SignMessageParams, SignMessageRequest, SignMessageResponse, WalletResponse};
+use serde::Serialize;
+use types::{
+    SignAndSendTransactionResponse, SignMessageParams, SignMessageRequest, SignMessageResponse,
+    SignTransactionResponse, TransactionParams, TransactionRequest, WalletResponse,
+};
+
+/// How transactions are sent to Privy for signing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PrivySigningMode {
+    /// `signMessage` over the transaction's message bytes
+    #[default]
+    Message,
+    /// `signTransaction` with the serialized transaction, so Privy policies can inspect it
+    Transaction,
+}
 
 /// Privy-based signer using Privy's wallet API
 #[derive(Clone)]
@@ -19,6 +38,9 @@ pub struct PrivySigner {
     api_base_url: String,
     client: reqwest::Client,
     public_key: Pubkey,
+    authorization_keys: Vec<PrivyAuthorizationKey>,
+    signing_mode: PrivySigningMode,
+    validate_transactions: bool,
 }
 
 impl std::fmt::Debug for PrivySigner {
@@ -46,9 +68,37 @@ impl PrivySigner {
             client: reqwest::Client::new(),
             // Set the public key to default to indicate that it's not initialized
             public_key: Pubkey::default(),
+            authorization_keys: Vec::new(),
+            signing_mode: PrivySigningMode::default(),
+            validate_transactions: true,
         }
     }
 
+    /// Sign RPC requests with an authorization key, for wallets with an owner or key quorum
+    ///
+    /// Call once per key when a quorum needs several signatures.
+    ///
+    /// # Arguments
+    ///
+    /// * `private_key` - Base64 PKCS#8 P-256 private key, optionally prefixed with `wallet-auth:`
+    pub fn with_authorization_key(mut self, private_key: &str) -> Result<Self, SignerError> {
+        self.authorization_keys
+            .push(PrivyAuthorizationKey::from_private_key(private_key)?);
+        Ok(self)
+    }
+
+    /// Choose how transactions are signed (default: [`PrivySigningMode::Message`])
+    pub fn with_signing_mode(mut self, mode: PrivySigningMode) -> Self {
+        self.signing_mode = mode;
+        self
+    }
+
+    /// Enable or disable [`TransactionUtil::validate_for_signing`] (enabled by default)
+    pub fn with_transaction_validation(mut self, enabled: bool) -> Self {
+        self.validate_transactions = enabled;
+        self
+    }
+
     /// Initialize the signer by fetching the public key
     pub async fn init(&mut self) -> Result<(), SignerError> {
         let pubkey = self.fetch_public_key().await?;
@@ -92,12 +142,34 @@ impl PrivySigner {
             return Err(SignerError::RemoteApiError(format!("API error {status}")));
         }
 
-        let wallet_info: WalletResponse = response.json().await?;
+        let response_text = response.text().await?;
+        Ok(WalletResponse::parse(&response_text)?.pubkey()?)
+    }
 
-        // For Trezoa wallets, the address is the public key
-        Pubkey::from_str(&wallet_info.address).map_err(|_| {
-            SignerError::InvalidPublicKey("Invalid public key from Privy API".to_string())
-        })
+    /// POST an RPC request, adding authorization signatures when keys are configured
+    async fn post_rpc(
+        &self,
+        url: &str,
+        request: &impl Serialize,
+    ) -> Result<reqwest::Response, SignerError> {
+        let body = serde_json::to_value(request)?;
+
+        let mut builder = self
+            .client
+            .post(url)
+            .header("Authorization", self.get_privy_auth_header())
+            .header("privy-app-id", &self.app_id)
+            .header("Content-Type", "application/json");
+
+        if !self.authorization_keys.is_empty() {
+            let payload = authorization_payload("POST", url, &body, &self.app_id);
+            builder = builder.header(
+                AUTHORIZATION_SIGNATURE_HEADER,
+                authorization_signature(&self.authorization_keys, &payload),
+            );
+        }
+
+        Ok(builder.json(&body).send().await?)
     }
 
     /// Sign message bytes using Privy API
@@ -112,15 +184,7 @@ impl PrivySigner {
             },
         };
 
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", self.get_privy_auth_header())
-            .header("privy-app-id", &self.app_id)
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await?;
+        let response = self.post_rpc(&url, &request).await?;
 
         if !response.status().is_success() {
             let status = response.status().as_u16();
@@ -139,23 +203,188 @@ impl PrivySigner {
         }
 
         let response_text = response.text().await?;
-        let sign_response: SignMessageResponse = serde_json::from_str(&response_text)?;
+        let signature = SignMessageResponse::parse(&response_text)?.signature()?;
+
+        Ok(signature)
+    }
 
-        let decoded_response = STANDARD
-            .decode(&sign_response.data.signature)
-            .expect("Failed to decode response");
+    /// Sign with the `signTransaction` RPC and extract this wallet's signature
+    async fn sign_transaction_rpc(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<Signature, SignerError> {
+        let response_text = self
+            .transaction_rpc(
+                "signTransaction",
+                None,
+                TransactionUtil::serialize_transaction(transaction)?,
+            )
+            .await?;
+        let signed = SignTransactionResponse::parse(&response_text)?.signed_transaction()?;
+        if signed.message_data() != transaction.message_data() {
+            return Err(SignerError::SigningFailed(
+                "Privy returned a different transaction".to_string(),
+            ));
+        }
 
-        let signature = Signature::try_from(decoded_response.as_slice())
-            .map_err(|_| SignerError::SigningFailed("Failed to parse signature".to_string()))?;
+        let position =
+            TransactionUtil::get_signing_keypair_position(transaction, &self.public_key)?;
+        let signature = signed.signatures.get(position).copied().ok_or_else(|| {
+            SignerError::SigningFailed("Privy returned no signature for this wallet".to_string())
+        })?;
+        self.verify(&signature, &transaction.message_data())?;
 
         Ok(signature)
     }
 
+    /// [`sign_transaction_rpc`](Self::sign_transaction_rpc) for a versioned transaction
+    async fn sign_versioned_transaction_rpc(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<Signature, SignerError> {
+        let response_text = self
+            .transaction_rpc(
+                "signTransaction",
+                None,
+                TransactionUtil::serialize_versioned_transaction(transaction)?,
+            )
+            .await?;
+        let signed =
+            SignTransactionResponse::parse(&response_text)?.signed_versioned_transaction()?;
+        let message = transaction.message.serialize();
+        if signed.message.serialize() != message {
+            return Err(SignerError::SigningFailed(
+                "Privy returned a different transaction".to_string(),
+            ));
+        }
+
+        let position =
+            TransactionUtil::get_versioned_signing_keypair_position(transaction, &self.public_key)?;
+        let signature = signed.signatures.get(position).copied().ok_or_else(|| {
+            SignerError::SigningFailed("Privy returned no signature for this wallet".to_string())
+        })?;
+        self.verify(&signature, &message)?;
+
+        Ok(signature)
+    }
+
+    /// Sign with Privy and broadcast through Privy's RPC provider
+    ///
+    /// The transaction must already carry every other required signature. If this wallet
+    /// is the fee payer, its signature is also added to `transaction`. Once Privy has
+    /// broadcast the transaction its hash is returned, even if it does not verify.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - Transaction to sign and send
+    /// * `caip2` - CAIP-2 id of the target chain
+    ///
+    /// # Returns
+    ///
+    /// The signature of the broadcast transaction
+    pub async fn sign_and_send_transaction(
+        &self,
+        transaction: &mut Transaction,
+        caip2: &str,
+    ) -> Result<Signature, SignerError> {
+        if self.validate_transactions {
+            TransactionUtil::validate_for_signing(transaction, &self.public_key)?;
+        }
+        // Privy broadcasts right away, so every check has to happen first
+        TransactionUtil::ensure_no_placeholder_signatures(transaction)?;
+        let position =
+            TransactionUtil::get_signing_keypair_position(transaction, &self.public_key)?;
+
+        let response_text = self
+            .transaction_rpc(
+                "signAndSendTransaction",
+                Some(caip2),
+                TransactionUtil::serialize_transaction(transaction)?,
+            )
+            .await?;
+        let signature = SignAndSendTransactionResponse::parse(&response_text)?.hash()?;
+
+        // The transaction is on chain by now, so a bad hash is logged rather than returned
+        if position == 0 {
+            let added = self
+                .verify(&signature, &transaction.message_data())
+                .and_then(|()| {
+                    TransactionUtil::add_signature_to_transaction(
+                        transaction,
+                        &self.public_key,
+                        signature,
+                    )
+                });
+            if let Err(e) = added {
+                log::warn!("Not adding Privy signature {signature} to the transaction: {e}");
+            }
+        }
+
+        Ok(signature)
+    }
+
+    /// Send a transaction RPC and return its response body
+    async fn transaction_rpc(
+        &self,
+        method: &'static str,
+        caip2: Option<&str>,
+        serialized_transaction: String,
+    ) -> Result<String, SignerError> {
+        let url = format!("{}/wallets/{}/rpc", self.api_base_url, self.wallet_id);
+
+        let request = TransactionRequest {
+            method,
+            caip2: caip2.map(str::to_string),
+            params: TransactionParams {
+                transaction: serialized_transaction,
+                encoding: "base64",
+            },
+        };
+
+        let response = self.post_rpc(&url, &request).await?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let _error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Failed to read error response".to_string());
+
+            #[cfg(feature = "unsafe-debug")]
+            log::error!("Privy API {method} error - status: {status}, response: {_error_text}");
+
+            #[cfg(not(feature = "unsafe-debug"))]
+            log::error!("Privy API {method} error - status: {status}");
+
+            return Err(SignerError::RemoteApiError(format!("API error {status}")));
+        }
+
+        Ok(response.text().await?)
+    }
+
+    /// Check that `signature` is this wallet's signature over `message`
+    fn verify(&self, signature: &Signature, message: &[u8]) -> Result<(), SignerError> {
+        if signature.verify(self.public_key.as_ref(), message) {
+            Ok(())
+        } else {
+            Err(SignerError::SigningFailed(
+                "Privy signature does not verify against the wallet public key".to_string(),
+            ))
+        }
+    }
+
     async fn sign_and_serialize(
         &self,
         transaction: &mut Transaction,
     ) -> Result<SignedTransaction, SignerError> {
-        let signature = self.sign_bytes(&transaction.message_data()).await?;
+        if self.validate_transactions {
+            TransactionUtil::validate_for_signing(transaction, &self.public_key)?;
+        }
+
+        let signature = match self.signing_mode {
+            PrivySigningMode::Message => self.sign_bytes(&transaction.message_data()).await?,
+            PrivySigningMode::Transaction => self.sign_transaction_rpc(transaction).await?,
+        };
 
         TransactionUtil::add_signature_to_transaction(transaction, &self.pubkey(), signature)?;
 
@@ -164,6 +393,33 @@ impl PrivySigner {
             signature,
         ))
     }
+
+    async fn sign_versioned_and_serialize(
+        &self,
+        transaction: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        if self.validate_transactions {
+            TransactionUtil::validate_versioned_for_signing(transaction, &self.public_key)?;
+        }
+
+        let signature = match self.signing_mode {
+            PrivySigningMode::Message => self.sign_bytes(&transaction.message.serialize()).await?,
+            PrivySigningMode::Transaction => {
+                self.sign_versioned_transaction_rpc(transaction).await?
+            }
+        };
+
+        TransactionUtil::add_signature_to_versioned_transaction(
+            transaction,
+            &self.public_key,
+            signature,
+        )?;
+
+        Ok((
+            TransactionUtil::serialize_versioned_transaction(transaction)?,
+            signature,
+        ))
+    }
 }
 
 #[async_trait::async_trait]
@@ -180,6 +436,11 @@ impl TrezoaSigner for PrivySigner {
     }
 
     async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        TransactionUtil::ensure_not_transaction_message(message, &self.pubkey())?;
+        self.sign_bytes(message).await
+    }
+
+    async fn sign_message_unchecked(&self, message: &[u8]) -> Result<Signature, SignerError> {
         self.sign_bytes(message).await
     }
 
@@ -190,6 +451,13 @@ impl TrezoaSigner for PrivySigner {
         self.sign_and_serialize(tx).await
     }
 
+    async fn sign_versioned_transaction(
+        &self,
+        tx: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        self.sign_versioned_and_serialize(tx).await
+    }
+
     async fn is_available(&self) -> bool {
         // Check if public key is initialized
         self.public_key != Pubkey::default()
@@ -202,7 +470,7 @@ mod tests {
     use crate::sdk_adapter::{keypair_pubkey, Keypair, Signer};
     use crate::test_util::create_test_transaction;
     use wiremock::{
-        matchers::{header, method, path},
+        matchers::{body_partial_json, header, method, path},
         Mock, MockServer, ResponseTemplate,
     };
 
@@ -292,7 +560,8 @@ mod tests {
         signer.api_base_url = mock_server.uri();
         signer.public_key = keypair.pubkey();
 
-        let result = signer.sign_message(&tx.message_data()).await;
+        // Raw transaction message bytes are blocked by sign_message
+        let result = signer.sign_message_unchecked(&tx.message_data()).await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), signature);
     }
@@ -344,6 +613,280 @@ mod tests {
         assert!(!serialized_tx.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_privy_authorization_signatures() {
+        use p256::ecdsa::{signature::Verifier, SigningKey, VerifyingKey};
+        use p256::pkcs8::EncodePrivateKey;
+
+        let mock_server = MockServer::start().await;
+        let keypair = create_test_keypair();
+        let signature = keypair.sign_message(b"test");
+
+        let signing_keys: Vec<SigningKey> = (0..2)
+            .map(|_| SigningKey::random(&mut rand::thread_rng()))
+            .collect();
+        let verifying_keys: Vec<VerifyingKey> = signing_keys
+            .iter()
+            .map(|key| *key.verifying_key())
+            .collect();
+        let url = format!("{}/wallets/test-wallet-id/rpc", mock_server.uri());
+
+        // Both quorum members must have signed the request Privy receives
+        Mock::given(method("POST"))
+            .and(path("/wallets/test-wallet-id/rpc"))
+            .and(move |request: &wiremock::Request| {
+                let Some(header) = request.headers.get("privy-authorization-signature") else {
+                    return false;
+                };
+                let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+                let payload = authorization::canonical_json(&authorization_payload(
+                    "POST",
+                    &url,
+                    &body,
+                    "test-app-id",
+                ));
+                let signatures: Vec<_> = header.to_str().unwrap().split(',').collect();
+
+                signatures.len() == verifying_keys.len()
+                    && signatures.iter().zip(&verifying_keys).all(|(sig, key)| {
+                        let sig = STANDARD.decode(sig).unwrap();
+                        p256::ecdsa::Signature::from_der(&sig)
+                            .is_ok_and(|sig| key.verify(payload.as_bytes(), &sig).is_ok())
+                    })
+            })
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "method": "signMessage",
+                "data": {
+                    "signature": STANDARD.encode(signature),
+                    "encoding": "base64"
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut signer = PrivySigner::new(
+            "test-app-id".to_string(),
+            "test-app-secret".to_string(),
+            "test-wallet-id".to_string(),
+        );
+        for key in &signing_keys {
+            let der = key.to_pkcs8_der().unwrap();
+            signer = signer
+                .with_authorization_key(&format!("wallet-auth:{}", STANDARD.encode(der.as_bytes())))
+                .unwrap();
+        }
+        signer.api_base_url = mock_server.uri();
+        signer.public_key = keypair.pubkey();
+
+        assert_eq!(signer.sign_message(b"test").await.unwrap(), signature);
+    }
+
+    fn transaction_mode_signer(mock_server: &MockServer, keypair: &Keypair) -> PrivySigner {
+        let mut signer = PrivySigner::new(
+            "test-app-id".to_string(),
+            "test-app-secret".to_string(),
+            "test-wallet-id".to_string(),
+        )
+        .with_signing_mode(PrivySigningMode::Transaction)
+        .with_transaction_validation(false);
+        signer.api_base_url = mock_server.uri();
+        signer.public_key = keypair.pubkey();
+        signer
+    }
+
+    #[tokio::test]
+    async fn test_privy_sign_transaction_mode() {
+        let mock_server = MockServer::start().await;
+        let keypair = create_test_keypair();
+
+        let mut tx = create_test_transaction(&keypair_pubkey(&keypair));
+        let signature = keypair.sign_message(&tx.message_data());
+        let mut signed_tx = tx.clone();
+        signed_tx.signatures = vec![signature];
+
+        // Privy receives the whole transaction, not just its message bytes
+        Mock::given(method("POST"))
+            .and(path("/wallets/test-wallet-id/rpc"))
+            .and(body_partial_json(serde_json::json!({
+                "method": "signTransaction",
+                "params": {
+                    "transaction": TransactionUtil::serialize_transaction(&tx).unwrap(),
+                    "encoding": "base64"
+                }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "method": "signTransaction",
+                "data": {
+                    "signed_transaction": TransactionUtil::serialize_transaction(&signed_tx).unwrap(),
+                    "encoding": "base64"
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let signer = transaction_mode_signer(&mock_server, &keypair);
+
+        let (serialized_tx, returned_sig) = signer.sign_transaction(&mut tx).await.unwrap();
+        assert_eq!(returned_sig, signature);
+        assert_eq!(tx.signatures, vec![signature]);
+        assert_eq!(
+            serialized_tx,
+            TransactionUtil::serialize_transaction(&signed_tx).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_privy_sign_transaction_mode_rejects_bad_response() {
+        let keypair = create_test_keypair();
+        let tx = create_test_transaction(&keypair_pubkey(&keypair));
+
+        // A different message, and the right message with a signature by another key
+        let mut altered = create_test_transaction(&keypair_pubkey(&keypair));
+        altered.message.recent_blockhash = crate::sdk_adapter::Hash::new_unique();
+        altered.signatures = vec![keypair.sign_message(&altered.message_data())];
+        let mut forged = tx.clone();
+        forged.signatures = vec![Keypair::new().sign_message(&tx.message_data())];
+
+        for response in [altered, forged] {
+            let mock_server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/wallets/test-wallet-id/rpc"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "method": "signTransaction",
+                    "data": {
+                        "signed_transaction": TransactionUtil::serialize_transaction(&response).unwrap(),
+                        "encoding": "base64"
+                    }
+                })))
+                .mount(&mock_server)
+                .await;
+
+            let signer = transaction_mode_signer(&mock_server, &keypair);
+            let mut tx = tx.clone();
+
+            let result = signer.sign_transaction(&mut tx).await;
+            assert!(matches!(result, Err(SignerError::SigningFailed(_))));
+            assert_eq!(tx.signatures, vec![Signature::default()]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_privy_sign_and_send_transaction() {
+        let mock_server = MockServer::start().await;
+        let keypair = create_test_keypair();
+
+        let mut tx = create_test_transaction(&keypair_pubkey(&keypair));
+        let signature = keypair.sign_message(&tx.message_data());
+        let caip2 = "trezoa:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp";
+
+        Mock::given(method("POST"))
+            .and(path("/wallets/test-wallet-id/rpc"))
+            .and(body_partial_json(serde_json::json!({
+                "method": "signAndSendTransaction",
+                "caip2": caip2
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "method": "signAndSendTransaction",
+                "data": {
+                    "hash": signature.to_string(),
+                    "caip2": caip2
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let signer = transaction_mode_signer(&mock_server, &keypair);
+
+        let sent = signer
+            .sign_and_send_transaction(&mut tx, caip2)
+            .await
+            .unwrap();
+        assert_eq!(sent, signature);
+        // The wallet is the fee payer, so the broadcast signature is its own
+        assert_eq!(tx.signatures, vec![signature]);
+    }
+
+    #[tokio::test]
+    async fn test_privy_sign_and_send_requires_wallet_signer() {
+        let mock_server = MockServer::start().await;
+        let keypair = create_test_keypair();
+
+        Mock::given(method("POST"))
+            .and(path("/wallets/test-wallet-id/rpc"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        // Validation is off, so only the signer lookup stands between this and a broadcast
+        let signer = transaction_mode_signer(&mock_server, &keypair);
+        let mut tx = create_test_transaction(&Pubkey::new_unique());
+
+        let result = signer
+            .sign_and_send_transaction(&mut tx, "trezoa:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp")
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_privy_sign_and_send_returns_hash_that_does_not_verify() {
+        let mock_server = MockServer::start().await;
+        let keypair = create_test_keypair();
+        let caip2 = "trezoa:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp";
+        let hash = Signature::from([7u8; 64]);
+
+        Mock::given(method("POST"))
+            .and(path("/wallets/test-wallet-id/rpc"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "method": "signAndSendTransaction",
+                "data": {
+                    "hash": hash.to_string(),
+                    "caip2": caip2
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let signer = transaction_mode_signer(&mock_server, &keypair);
+        let mut tx = create_test_transaction(&keypair_pubkey(&keypair));
+
+        // Already broadcast, so the hash is returned but not added as the wallet's signature
+        let sent = signer
+            .sign_and_send_transaction(&mut tx, caip2)
+            .await
+            .unwrap();
+        assert_eq!(sent, hash);
+        assert_eq!(tx.signatures, vec![Signature::default()]);
+    }
+
+    #[tokio::test]
+    async fn test_privy_sign_and_send_refuses_placeholder_signatures() {
+        let mock_server = MockServer::start().await;
+        let keypair = create_test_keypair();
+
+        Mock::given(method("POST"))
+            .and(path("/wallets/test-wallet-id/rpc"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let signer = transaction_mode_signer(&mock_server, &keypair);
+        let mut tx = create_test_transaction(&keypair_pubkey(&keypair));
+        tx.signatures[0] = TransactionUtil::placeholder_signature(&keypair_pubkey(&keypair));
+
+        let result = signer
+            .sign_and_send_transaction(&mut tx, "trezoa:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp")
+            .await;
+
+        assert!(matches!(result, Err(SignerError::SigningFailed(_))));
+    }
+
     #[tokio::test]
     async fn test_privy_pubkey() {
         let keypair = create_test_keypair();
@@ -410,11 +953,45 @@ mod tests {
         signer.api_base_url = mock_server.uri();
 
         let result = signer.init().await;
-        assert!(result.is_err());
         assert!(matches!(
-            result.unwrap_err(),
-            SignerError::InvalidPublicKey(_)
+            result,
+            Err(SignerError::InvalidResponse(
+                crate::error::ResponseError::InvalidEncoding { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_privy_fetch_public_key_wrong_chain() {
+        let mock_server = MockServer::start().await;
+        let keypair = create_test_keypair();
+
+        Mock::given(method("GET"))
+            .and(path("/wallets/test-wallet-id"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "test-wallet-id",
+                "address": keypair.pubkey().to_string(),
+                "chain_type": "ethereum"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut signer = PrivySigner::new(
+            "test-app-id".to_string(),
+            "test-app-secret".to_string(),
+            "test-wallet-id".to_string(),
+        );
+        signer.api_base_url = mock_server.uri();
+
+        let result = signer.init().await;
+        assert!(matches!(
+            result,
+            Err(SignerError::InvalidResponse(
+                crate::error::ResponseError::UnexpectedChainType(_)
+            ))
         ));
+        assert!(!signer.is_available().await);
     }
 
     #[tokio::test]
@@ -1,7 +1,13 @@
 //! Privy API types and error handling
 
+use crate::error::ResponseError;
+use crate::response;
+use crate::sdk_adapter::{Pubkey, Signature, Transaction, VersionedTransaction};
 use serde::{Deserialize, Serialize};
 
+/// `chain_type` of Trezoa wallets
+pub const TREZOA_CHAIN_TYPE: &str = "trezoa";
+
 // API request/response types for Privy
 #[derive(Serialize)]
 pub struct SignMessageRequest {
@@ -29,6 +35,17 @@ pub struct SignMessageData {
     pub encoding: String,
 }
 
+impl SignMessageResponse {
+    pub fn parse(body: &str) -> Result<Self, ResponseError> {
+        response::parse_json(body, "Privy signMessage response")
+    }
+
+    pub fn signature(&self) -> Result<Signature, ResponseError> {
+        let bytes = response::decode_base64(&self.data.signature, "signature")?;
+        response::signature(bytes, "signature")
+    }
+}
+
 // Wallet info response
 #[derive(Deserialize)]
 #[allow(dead_code)]
@@ -59,3 +76,167 @@ pub struct WalletResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_at: Option<i64>,
 }
+
+// signTransaction / signAndSendTransaction RPC
+#[derive(Serialize)]
+pub struct TransactionRequest {
+    pub method: &'static str,
+    /// CAIP-2 chain id, for signAndSendTransaction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caip2: Option<String>,
+    pub params: TransactionParams,
+}
+
+#[derive(Serialize)]
+pub struct TransactionParams {
+    pub transaction: String,
+    pub encoding: &'static str,
+}
+
+#[derive(Deserialize)]
+pub struct SignTransactionResponse {
+    pub data: SignTransactionData,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+pub struct SignTransactionData {
+    pub signed_transaction: String,
+    pub encoding: String,
+}
+
+#[derive(Deserialize)]
+pub struct SignAndSendTransactionResponse {
+    pub data: SignAndSendTransactionData,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+pub struct SignAndSendTransactionData {
+    /// Base58 signature of the broadcast transaction
+    pub hash: String,
+    pub caip2: Option<String>,
+}
+
+impl WalletResponse {
+    pub fn parse(body: &str) -> Result<Self, ResponseError> {
+        response::parse_json(body, "Privy wallet response")
+    }
+
+    /// The wallet address, which for Trezoa wallets is the public key
+    pub fn pubkey(&self) -> Result<Pubkey, ResponseError> {
+        if self.chain_type != TREZOA_CHAIN_TYPE {
+            return Err(ResponseError::UnexpectedChainType(self.chain_type.clone()));
+        }
+        let bytes = response::decode_base58(&self.address, "wallet address")?;
+        response::pubkey(bytes, "wallet address")
+    }
+}
+
+impl SignTransactionResponse {
+    pub fn parse(body: &str) -> Result<Self, ResponseError> {
+        response::parse_json(body, "Privy signTransaction response")
+    }
+
+    pub fn signed_transaction(&self) -> Result<Transaction, ResponseError> {
+        let bytes = response::decode_base64(&self.data.signed_transaction, "signed transaction")?;
+        bincode::deserialize(&bytes).map_err(|_| ResponseError::InvalidEncoding {
+            field: "signed transaction",
+            encoding: "transaction",
+        })
+    }
+
+    pub fn signed_versioned_transaction(&self) -> Result<VersionedTransaction, ResponseError> {
+        let bytes = response::decode_base64(&self.data.signed_transaction, "signed transaction")?;
+        bincode::deserialize(&bytes).map_err(|_| ResponseError::InvalidEncoding {
+            field: "signed transaction",
+            encoding: "transaction",
+        })
+    }
+}
+
+impl SignAndSendTransactionResponse {
+    pub fn parse(body: &str) -> Result<Self, ResponseError> {
+        response::parse_json(body, "Privy signAndSendTransaction response")
+    }
+
+    pub fn hash(&self) -> Result<Signature, ResponseError> {
+        let bytes = response::decode_base58(&self.data.hash, "transaction hash")?;
+        response::signature(bytes, "transaction hash")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{arb_encoded_string, arb_json};
+    use proptest::prelude::*;
+    use serde_json::json;
+
+    const FIELDS: &[&str] = &[
+        "method",
+        "data",
+        "signature",
+        "encoding",
+        "signed_transaction",
+        "hash",
+        "caip2",
+        "id",
+        "address",
+        "chain_type",
+    ];
+
+    fn parse_all(body: &str) {
+        if let Ok(response) = SignMessageResponse::parse(body) {
+            let _ = response.signature();
+        }
+        if let Ok(response) = WalletResponse::parse(body) {
+            let _ = response.pubkey();
+        }
+        if let Ok(response) = SignTransactionResponse::parse(body) {
+            let _ = response.signed_transaction();
+        }
+        if let Ok(response) = SignAndSendTransactionResponse::parse(body) {
+            let _ = response.hash();
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_parsers_never_panic(value in arb_json(FIELDS), raw in ".*") {
+            parse_all(&value.to_string());
+            parse_all(&raw);
+        }
+
+        #[test]
+        fn test_field_decoding_never_panics(value in arb_encoded_string(), chain in ".*") {
+            parse_all(&json!({ "method": "signMessage", "data": { "signature": value, "encoding": "base64" } }).to_string());
+            parse_all(&json!({ "id": "w", "address": value, "chain_type": chain }).to_string());
+            parse_all(&json!({ "id": "w", "address": value, "chain_type": TREZOA_CHAIN_TYPE }).to_string());
+            parse_all(&json!({ "data": { "signed_transaction": value, "encoding": "base64" } }).to_string());
+            parse_all(&json!({ "data": { "hash": value } }).to_string());
+        }
+    }
+
+    #[test]
+    fn test_signature_length() {
+        let response = SignMessageResponse::parse(
+            &json!({ "method": "signMessage", "data": { "signature": "AAAA", "encoding": "base64" } })
+                .to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.signature(),
+            Err(ResponseError::InvalidLength {
+                field: "signature",
+                expected: 64,
+                actual: 3
+            })
+        );
+        assert_eq!(
+            SignMessageResponse::parse("{}").err(),
+            Some(ResponseError::Malformed("Privy signMessage response"))
+        );
+    }
+}
@@ -0,0 +1,63 @@
+//! Fallible decoding of remote signing service responses
+
+use crate::error::ResponseError;
+use crate::sdk_adapter::{Pubkey, Signature};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::de::DeserializeOwned;
+
+/// Parse a JSON response body, naming the response type in the error
+pub fn parse_json<T: DeserializeOwned>(body: &str, name: &'static str) -> Result<T, ResponseError> {
+    serde_json::from_str(body).map_err(|_e| {
+        #[cfg(feature = "unsafe-debug")]
+        log::error!("Failed to parse {name}: {_e}, body: {body}");
+
+        ResponseError::Malformed(name)
+    })
+}
+
+pub fn decode_base64(value: &str, field: &'static str) -> Result<Vec<u8>, ResponseError> {
+    STANDARD
+        .decode(value)
+        .map_err(|_| ResponseError::InvalidEncoding {
+            field,
+            encoding: "base64",
+        })
+}
+
+pub fn decode_base58(value: &str, field: &'static str) -> Result<Vec<u8>, ResponseError> {
+    bs58::decode(value)
+        .into_vec()
+        .map_err(|_| ResponseError::InvalidEncoding {
+            field,
+            encoding: "base58",
+        })
+}
+
+#[cfg(any(feature = "turnkey", feature = "fireblocks"))]
+pub fn decode_hex(value: &str, field: &'static str) -> Result<Vec<u8>, ResponseError> {
+    hex::decode(value).map_err(|_| ResponseError::InvalidEncoding {
+        field,
+        encoding: "hex",
+    })
+}
+
+/// Fixed-size array from decoded bytes
+pub fn to_array<const N: usize>(
+    bytes: Vec<u8>,
+    field: &'static str,
+) -> Result<[u8; N], ResponseError> {
+    let actual = bytes.len();
+    bytes.try_into().map_err(|_| ResponseError::InvalidLength {
+        field,
+        expected: N,
+        actual,
+    })
+}
+
+pub fn signature(bytes: Vec<u8>, field: &'static str) -> Result<Signature, ResponseError> {
+    Ok(Signature::from(to_array::<64>(bytes, field)?))
+}
+
+pub fn pubkey(bytes: Vec<u8>, field: &'static str) -> Result<Pubkey, ResponseError> {
+    Ok(Pubkey::from(to_array::<32>(bytes, field)?))
+}
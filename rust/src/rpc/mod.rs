@@ -0,0 +1,912 @@
+//! Transaction submission over Trezoa JSON-RPC
+//!
+//! [`TransactionSender::send_and_confirm`] builds a transaction from instructions,
+//! signs it, sends it and waits for confirmation. When the blockhash expires before
+//! the transaction lands, it is rebuilt with a fresh blockhash and signed again.
+
+mod types;
+
+use crate::error::SignerError;
+use crate::nonce_util::NonceUtil;
+use crate::sdk_adapter::{Hash, Instruction, Message, Pubkey, Signature, Transaction};
+use crate::traits::TrezoaSigner;
+use crate::transaction_util::TransactionUtil;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::str::FromStr;
+use types::{
+    JsonRpcRequest, JsonRpcResponse, LatestBlockhashValue, RpcContextResponse, SignatureStatusValue,
+};
+
+/// Commitment level used when querying the cluster
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CommitmentLevel {
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl CommitmentLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommitmentLevel::Processed => "processed",
+            CommitmentLevel::Confirmed => "confirmed",
+            CommitmentLevel::Finalized => "finalized",
+        }
+    }
+}
+
+impl FromStr for CommitmentLevel {
+    type Err = SignerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "processed" => Ok(CommitmentLevel::Processed),
+            "confirmed" => Ok(CommitmentLevel::Confirmed),
+            "finalized" => Ok(CommitmentLevel::Finalized),
+            _ => Err(SignerError::SerializationError(format!(
+                "Unknown commitment level: {s}"
+            ))),
+        }
+    }
+}
+
+/// Status of a submitted transaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureStatus {
+    /// Highest commitment level the transaction has reached
+    pub confirmation_status: Option<CommitmentLevel>,
+    /// Execution error, if the transaction failed
+    pub err: Option<serde_json::Value>,
+}
+
+/// Minimal RPC client needed to submit and confirm transactions
+#[async_trait]
+pub trait RpcSender: Send + Sync {
+    /// Get the latest blockhash and the last block height at which it is valid
+    async fn get_latest_blockhash(
+        &self,
+        commitment: CommitmentLevel,
+    ) -> Result<(Hash, u64), SignerError>;
+
+    /// Submit a base64-encoded signed transaction
+    async fn send_transaction(&self, serialized: &str) -> Result<Signature, SignerError>;
+
+    /// Get the status of a transaction, or `None` if the cluster hasn't seen it
+    async fn get_signature_status(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<SignatureStatus>, SignerError>;
+
+    /// Get the current block height
+    async fn get_block_height(&self, commitment: CommitmentLevel) -> Result<u64, SignerError>;
+}
+
+/// [`RpcSender`] implementation for the Trezoa JSON-RPC HTTP API
+#[derive(Clone)]
+pub struct JsonRpcSender {
+    rpc_url: String,
+    client: reqwest::Client,
+    skip_preflight: bool,
+}
+
+impl std::fmt::Debug for JsonRpcSender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonRpcSender")
+            .field("rpc_url", &self.rpc_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl JsonRpcSender {
+    /// Create a new JsonRpcSender
+    ///
+    /// # Arguments
+    ///
+    /// * `rpc_url` - Trezoa RPC endpoint (e.g., "https://api.devnet.trezoa.com")
+    pub fn new(rpc_url: String) -> Self {
+        Self {
+            rpc_url,
+            client: reqwest::Client::new(),
+            skip_preflight: false,
+        }
+    }
+
+    /// Skip the RPC node's preflight simulation when sending
+    pub fn with_skip_preflight(mut self, skip_preflight: bool) -> Self {
+        self.skip_preflight = skip_preflight;
+        self
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, SignerError> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: 1,
+            method,
+            params,
+        };
+
+        let response = self
+            .client
+            .post(&self.rpc_url)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let _error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Failed to read error response".to_string());
+
+            #[cfg(feature = "unsafe-debug")]
+            log::error!("RPC {method} error - status: {status}, response: {_error_text}");
+
+            #[cfg(not(feature = "unsafe-debug"))]
+            log::error!("RPC {method} error - status: {status}");
+
+            return Err(SignerError::RemoteApiError(format!("RPC error {status}")));
+        }
+
+        let response_text = response.text().await?;
+        let rpc_response: JsonRpcResponse<T> = serde_json::from_str(&response_text)?;
+
+        if let Some(error) = rpc_response.error {
+            return Err(SignerError::RemoteApiError(format!(
+                "RPC {method} failed ({}): {}",
+                error.code, error.message
+            )));
+        }
+
+        rpc_response
+            .result
+            .ok_or_else(|| SignerError::RemoteApiError(format!("RPC {method} returned no result")))
+    }
+}
+
+#[async_trait]
+impl RpcSender for JsonRpcSender {
+    async fn get_latest_blockhash(
+        &self,
+        commitment: CommitmentLevel,
+    ) -> Result<(Hash, u64), SignerError> {
+        let response: RpcContextResponse<LatestBlockhashValue> = self
+            .call(
+                "getLatestBlockhash",
+                json!([{ "commitment": commitment.as_str() }]),
+            )
+            .await?;
+
+        let blockhash = Hash::from_str(&response.value.blockhash).map_err(|_| {
+            SignerError::SerializationError("Invalid blockhash in RPC response".to_string())
+        })?;
+
+        Ok((blockhash, response.value.last_valid_block_height))
+    }
+
+    async fn send_transaction(&self, serialized: &str) -> Result<Signature, SignerError> {
+        let signature: String = self
+            .call(
+                "sendTransaction",
+                json!([
+                    serialized,
+                    {
+                        "encoding": "base64",
+                        "skipPreflight": self.skip_preflight
+                    }
+                ]),
+            )
+            .await?;
+
+        Signature::from_str(&signature).map_err(|_| {
+            SignerError::SerializationError("Invalid signature in RPC response".to_string())
+        })
+    }
+
+    async fn get_signature_status(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<SignatureStatus>, SignerError> {
+        let response: RpcContextResponse<Vec<Option<SignatureStatusValue>>> = self
+            .call(
+                "getSignatureStatuses",
+                json!([[signature.to_string()], { "searchTransactionHistory": false }]),
+            )
+            .await?;
+
+        let Some(status) = response.value.into_iter().next().flatten() else {
+            return Ok(None);
+        };
+
+        let confirmation_status = status
+            .confirmation_status
+            .as_deref()
+            .map(CommitmentLevel::from_str)
+            .transpose()?;
+
+        Ok(Some(SignatureStatus {
+            confirmation_status,
+            err: status.err,
+        }))
+    }
+
+    async fn get_block_height(&self, commitment: CommitmentLevel) -> Result<u64, SignerError> {
+        self.call(
+            "getBlockHeight",
+            json!([{ "commitment": commitment.as_str() }]),
+        )
+        .await
+    }
+}
+
+/// Configuration for creating a TransactionSender
+#[derive(Clone, Default)]
+pub struct TransactionSenderConfig {
+    /// Commitment to wait for (default: confirmed)
+    pub commitment: Option<CommitmentLevel>,
+    /// Number of times to rebuild and re-sign after the blockhash expires (default: 3)
+    pub max_blockhash_retries: Option<u32>,
+    /// Delay between confirmation polls (default: 500)
+    pub poll_interval_ms: Option<u64>,
+    /// Poll attempts per blockhash before giving up without re-signing (default: 300)
+    pub max_poll_attempts: Option<u32>,
+}
+
+/// Builds, signs, sends and confirms transactions through an [`RpcSender`]
+pub struct TransactionSender<R: RpcSender> {
+    rpc: R,
+    commitment: CommitmentLevel,
+    max_blockhash_retries: u32,
+    poll_interval_ms: u64,
+    max_poll_attempts: u32,
+}
+
+impl<R: RpcSender> TransactionSender<R> {
+    /// Create a new TransactionSender
+    ///
+    /// # Arguments
+    ///
+    /// * `rpc` - RPC client used to fetch blockhashes, send and confirm
+    /// * `config` - Commitment and retry budgets
+    pub fn new(rpc: R, config: TransactionSenderConfig) -> Self {
+        Self {
+            rpc,
+            commitment: config.commitment.unwrap_or(CommitmentLevel::Confirmed),
+            max_blockhash_retries: config.max_blockhash_retries.unwrap_or(3),
+            poll_interval_ms: config.poll_interval_ms.unwrap_or(500),
+            max_poll_attempts: config.max_poll_attempts.unwrap_or(300),
+        }
+    }
+
+    /// Get the underlying RPC client
+    pub fn rpc(&self) -> &R {
+        &self.rpc
+    }
+
+    /// Build a transaction from `instructions`, sign it and wait until it is confirmed
+    ///
+    /// `payer` pays the fees and may be the same signer as `signer`. Every required
+    /// signer of the resulting message must be one of the two. A transaction is only
+    /// rebuilt once the cluster has passed the last valid block height of its blockhash,
+    /// so an earlier attempt can never land alongside the new one.
+    ///
+    /// Durable nonce instructions are rejected, since every attempt uses the latest
+    /// blockhash rather than the stored nonce; sign and send those transactions directly.
+    ///
+    /// # Returns
+    ///
+    /// The signature of the confirmed transaction
+    pub async fn send_and_confirm(
+        &self,
+        signer: &dyn TrezoaSigner,
+        instructions: &[Instruction],
+        payer: &dyn TrezoaSigner,
+    ) -> Result<Signature, SignerError> {
+        let payer_pubkey = payer.pubkey();
+        let signer_pubkey = signer.pubkey();
+
+        let unsigned =
+            Self::build_transaction(instructions, &payer_pubkey, &signer_pubkey, Hash::default())?;
+        if NonceUtil::is_nonce_transaction(&unsigned) {
+            return Err(SignerError::ConfigError(
+                "send_and_confirm refreshes the blockhash and cannot send durable nonce transactions"
+                    .to_string(),
+            ));
+        }
+
+        for _attempt in 0..=self.max_blockhash_retries {
+            let (blockhash, last_valid_block_height) =
+                self.rpc.get_latest_blockhash(self.commitment).await?;
+
+            let mut transaction =
+                Self::build_transaction(instructions, &payer_pubkey, &signer_pubkey, blockhash)?;
+
+            if payer_pubkey != signer_pubkey {
+                payer.sign_partial_transaction(&mut transaction).await?;
+            }
+            let (serialized, _) = signer.sign_transaction(&mut transaction).await?;
+
+            TransactionUtil::ensure_no_placeholder_signatures(&transaction)?;
+
+            // The fee payer's signature identifies the transaction
+            let signature = transaction.signatures[0];
+            self.rpc.send_transaction(&serialized).await?;
+
+            if self
+                .confirm_until_expired(&signature, last_valid_block_height)
+                .await?
+            {
+                return Ok(signature);
+            }
+
+            log::warn!("Blockhash expired before transaction {signature} landed, re-signing");
+        }
+
+        Err(SignerError::RemoteApiError(format!(
+            "Transaction not confirmed after {} blockhash retries",
+            self.max_blockhash_retries
+        )))
+    }
+
+    fn build_transaction(
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signer: &Pubkey,
+        blockhash: Hash,
+    ) -> Result<Transaction, SignerError> {
+        let mut message = Message::new(instructions, Some(payer));
+        message.recent_blockhash = blockhash;
+
+        let num_required_signatures = message.header.num_required_signatures as usize;
+        if let Some(missing) = message.account_keys[..num_required_signatures]
+            .iter()
+            .find(|key| *key != payer && *key != signer)
+        {
+            return Err(SignerError::ConfigError(format!(
+                "Transaction requires a signature from {missing}, which is neither the signer nor the payer"
+            )));
+        }
+
+        Ok(Transaction::new_unsigned(message))
+    }
+
+    /// Poll until the transaction reaches the configured commitment
+    ///
+    /// Returns `Ok(false)` once the blockhash has expired without the transaction landing.
+    async fn confirm_until_expired(
+        &self,
+        signature: &Signature,
+        last_valid_block_height: u64,
+    ) -> Result<bool, SignerError> {
+        for _attempt in 0..self.max_poll_attempts {
+            if self.check_confirmed(signature).await? {
+                return Ok(true);
+            }
+
+            let block_height = self.rpc.get_block_height(self.commitment).await?;
+            if block_height > last_valid_block_height {
+                // It may have landed between the two queries
+                return self.check_confirmed(signature).await;
+            }
+
+            tokio::time::sleep(tokio::time::Duration::from_millis(self.poll_interval_ms)).await;
+        }
+
+        // The blockhash may still be valid, so re-signing now could land the transfer twice
+        Err(SignerError::RemoteApiError(format!(
+            "Confirmation polling timeout after {} attempts - transaction {signature} may still land",
+            self.max_poll_attempts
+        )))
+    }
+
+    async fn check_confirmed(&self, signature: &Signature) -> Result<bool, SignerError> {
+        match self.rpc.get_signature_status(signature).await? {
+            Some(SignatureStatus { err: Some(err), .. }) => Err(SignerError::RemoteApiError(
+                format!("Transaction {signature} failed: {err}"),
+            )),
+            Some(SignatureStatus {
+                confirmation_status: Some(level),
+                ..
+            }) => Ok(level >= self.commitment),
+            _ => Ok(false),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "memory"))]
+mod tests {
+    use super::*;
+    use crate::memory::MemorySigner;
+    use crate::sdk_adapter::{keypair_sign_message, Keypair};
+    use crate::test_util::create_transfer_instruction;
+    use wiremock::{
+        matchers::{body_partial_json, body_string_contains, method},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn rpc_result(result: serde_json::Value) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": result
+        }))
+    }
+
+    async fn mock_rpc(
+        server: &MockServer,
+        rpc_method: &str,
+        result: serde_json::Value,
+        times: Option<u64>,
+    ) {
+        let mock = Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": rpc_method })))
+            .respond_with(rpc_result(result));
+        match times {
+            Some(n) => mock.up_to_n_times(n).mount(server).await,
+            None => mock.mount(server).await,
+        }
+    }
+
+    fn blockhash_result(blockhash: &Hash, last_valid_block_height: u64) -> serde_json::Value {
+        json!({
+            "context": { "slot": 1 },
+            "value": {
+                "blockhash": blockhash.to_string(),
+                "lastValidBlockHeight": last_valid_block_height
+            }
+        })
+    }
+
+    fn status_result(status: serde_json::Value) -> serde_json::Value {
+        json!({ "context": { "slot": 1 }, "value": [status] })
+    }
+
+    fn create_sender(server: &MockServer) -> TransactionSender<JsonRpcSender> {
+        TransactionSender::new(
+            JsonRpcSender::new(server.uri()),
+            TransactionSenderConfig {
+                poll_interval_ms: Some(1),
+                max_poll_attempts: Some(5),
+                ..Default::default()
+            },
+        )
+    }
+
+    fn expected_signature(
+        keypair: &Keypair,
+        instructions: &[Instruction],
+        blockhash: Hash,
+    ) -> Signature {
+        let payer = crate::sdk_adapter::keypair_pubkey(keypair);
+        let mut message = Message::new(instructions, Some(&payer));
+        message.recent_blockhash = blockhash;
+        keypair_sign_message(keypair, &message.serialize())
+    }
+
+    #[tokio::test]
+    async fn test_send_and_confirm() {
+        let server = MockServer::start().await;
+        let keypair = Keypair::new();
+        let signer = MemorySigner::new(keypair.insecure_clone());
+        let blockhash = Hash::new_from_array([1u8; 32]);
+        let instructions = [create_transfer_instruction(
+            &signer.pubkey(),
+            &Pubkey::new_unique(),
+            1_000,
+        )];
+        let signature = expected_signature(&keypair, &instructions, blockhash);
+
+        mock_rpc(
+            &server,
+            "getLatestBlockhash",
+            blockhash_result(&blockhash, 100),
+            None,
+        )
+        .await;
+        mock_rpc(
+            &server,
+            "sendTransaction",
+            json!(signature.to_string()),
+            None,
+        )
+        .await;
+        mock_rpc(
+            &server,
+            "getSignatureStatuses",
+            status_result(json!({ "confirmationStatus": "confirmed", "err": null })),
+            None,
+        )
+        .await;
+
+        let sender = create_sender(&server);
+        let result = sender
+            .send_and_confirm(&signer, &instructions, &signer)
+            .await;
+
+        assert_eq!(result.unwrap(), signature);
+    }
+
+    #[tokio::test]
+    async fn test_send_and_confirm_resigns_after_expiry() {
+        let server = MockServer::start().await;
+        let keypair = Keypair::new();
+        let signer = MemorySigner::new(keypair.insecure_clone());
+        let expired_blockhash = Hash::new_from_array([1u8; 32]);
+        let fresh_blockhash = Hash::new_from_array([2u8; 32]);
+        let instructions = [create_transfer_instruction(
+            &signer.pubkey(),
+            &Pubkey::new_unique(),
+            1_000,
+        )];
+        let expired_signature = expected_signature(&keypair, &instructions, expired_blockhash);
+        let fresh_signature = expected_signature(&keypair, &instructions, fresh_blockhash);
+
+        mock_rpc(
+            &server,
+            "getLatestBlockhash",
+            blockhash_result(&expired_blockhash, 100),
+            Some(1),
+        )
+        .await;
+        mock_rpc(
+            &server,
+            "getLatestBlockhash",
+            blockhash_result(&fresh_blockhash, 200),
+            None,
+        )
+        .await;
+        mock_rpc(
+            &server,
+            "sendTransaction",
+            json!(fresh_signature.to_string()),
+            None,
+        )
+        .await;
+        mock_rpc(&server, "getBlockHeight", json!(150), None).await;
+
+        // Only the transaction signed over the fresh blockhash lands
+        Mock::given(method("POST"))
+            .and(body_partial_json(
+                json!({ "method": "getSignatureStatuses" }),
+            ))
+            .and(body_string_contains(fresh_signature.to_string()))
+            .respond_with(rpc_result(status_result(
+                json!({ "confirmationStatus": "finalized", "err": null }),
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(
+                json!({ "method": "getSignatureStatuses" }),
+            ))
+            .and(body_string_contains(expired_signature.to_string()))
+            .respond_with(rpc_result(status_result(json!(null))))
+            .mount(&server)
+            .await;
+
+        let sender = create_sender(&server);
+        let result = sender
+            .send_and_confirm(&signer, &instructions, &signer)
+            .await;
+
+        assert_eq!(result.unwrap(), fresh_signature);
+    }
+
+    #[tokio::test]
+    async fn test_send_and_confirm_separate_payer_resigns_after_expiry() {
+        let server = MockServer::start().await;
+        let payer_keypair = Keypair::new();
+        let payer = MemorySigner::new(payer_keypair.insecure_clone());
+        let signer = MemorySigner::new(Keypair::new());
+        let expired_blockhash = Hash::new_from_array([1u8; 32]);
+        let fresh_blockhash = Hash::new_from_array([2u8; 32]);
+        let instructions = [create_transfer_instruction(
+            &signer.pubkey(),
+            &Pubkey::new_unique(),
+            1_000,
+        )];
+        let expired_signature =
+            expected_signature(&payer_keypair, &instructions, expired_blockhash);
+        let fresh_signature = expected_signature(&payer_keypair, &instructions, fresh_blockhash);
+
+        mock_rpc(
+            &server,
+            "getLatestBlockhash",
+            blockhash_result(&expired_blockhash, 100),
+            Some(1),
+        )
+        .await;
+        mock_rpc(
+            &server,
+            "getLatestBlockhash",
+            blockhash_result(&fresh_blockhash, 200),
+            None,
+        )
+        .await;
+        mock_rpc(
+            &server,
+            "sendTransaction",
+            json!(fresh_signature.to_string()),
+            None,
+        )
+        .await;
+        mock_rpc(&server, "getBlockHeight", json!(150), None).await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(
+                json!({ "method": "getSignatureStatuses" }),
+            ))
+            .and(body_string_contains(fresh_signature.to_string()))
+            .respond_with(rpc_result(status_result(
+                json!({ "confirmationStatus": "finalized", "err": null }),
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(
+                json!({ "method": "getSignatureStatuses" }),
+            ))
+            .and(body_string_contains(expired_signature.to_string()))
+            .respond_with(rpc_result(status_result(json!(null))))
+            .mount(&server)
+            .await;
+
+        let sender = create_sender(&server);
+        let result = sender
+            .send_and_confirm(&signer, &instructions, &payer)
+            .await;
+
+        // The payer's signature identifies the transaction
+        assert_eq!(result.unwrap(), fresh_signature);
+
+        // The re-signed transaction carries both signatures over the fresh blockhash
+        let sent: Vec<Transaction> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter_map(|r| {
+                let body: serde_json::Value = serde_json::from_slice(&r.body).unwrap();
+                (body["method"] == "sendTransaction").then(|| {
+                    TransactionUtil::deserialize_transaction(body["params"][0].as_str().unwrap())
+                        .unwrap()
+                })
+            })
+            .collect();
+        assert_eq!(sent.len(), 2);
+        let transaction = &sent[1];
+        assert_eq!(transaction.message.recent_blockhash, fresh_blockhash);
+        assert_eq!(
+            &transaction.message.account_keys[..2],
+            &[payer.pubkey(), signer.pubkey()]
+        );
+        assert_eq!(transaction.signatures.len(), 2);
+        assert_eq!(transaction.signatures[0], fresh_signature);
+        assert!(transaction.verify().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_send_and_confirm_retries_exhausted() {
+        let server = MockServer::start().await;
+        let signer = MemorySigner::new(Keypair::new());
+        let instructions = [create_transfer_instruction(
+            &signer.pubkey(),
+            &Pubkey::new_unique(),
+            1_000,
+        )];
+
+        mock_rpc(
+            &server,
+            "getLatestBlockhash",
+            blockhash_result(&Hash::new_from_array([1u8; 32]), 100),
+            None,
+        )
+        .await;
+        mock_rpc(
+            &server,
+            "sendTransaction",
+            json!(Signature::default().to_string()),
+            None,
+        )
+        .await;
+        mock_rpc(
+            &server,
+            "getSignatureStatuses",
+            status_result(json!(null)),
+            None,
+        )
+        .await;
+        mock_rpc(&server, "getBlockHeight", json!(101), None).await;
+
+        let sender = TransactionSender::new(
+            JsonRpcSender::new(server.uri()),
+            TransactionSenderConfig {
+                max_blockhash_retries: Some(2),
+                poll_interval_ms: Some(1),
+                ..Default::default()
+            },
+        );
+        let result = sender
+            .send_and_confirm(&signer, &instructions, &signer)
+            .await;
+
+        assert!(matches!(result, Err(SignerError::RemoteApiError(_))));
+        let send_requests = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|r| String::from_utf8_lossy(&r.body).contains("sendTransaction"))
+            .count();
+        assert_eq!(send_requests, 3);
+    }
+
+    #[tokio::test]
+    async fn test_send_and_confirm_rejects_nonce_transaction() {
+        let server = MockServer::start().await;
+        let signer = MemorySigner::new(Keypair::new());
+        let instructions = [
+            NonceUtil::advance_nonce_instruction(&Pubkey::new_unique(), &signer.pubkey()),
+            create_transfer_instruction(&signer.pubkey(), &Pubkey::new_unique(), 1_000),
+        ];
+
+        let result = create_sender(&server)
+            .send_and_confirm(&signer, &instructions, &signer)
+            .await;
+
+        assert!(matches!(result, Err(SignerError::ConfigError(_))));
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_send_and_confirm_transaction_error() {
+        let server = MockServer::start().await;
+        let signer = MemorySigner::new(Keypair::new());
+        let instructions = [create_transfer_instruction(
+            &signer.pubkey(),
+            &Pubkey::new_unique(),
+            1_000,
+        )];
+
+        mock_rpc(
+            &server,
+            "getLatestBlockhash",
+            blockhash_result(&Hash::new_from_array([1u8; 32]), 100),
+            None,
+        )
+        .await;
+        mock_rpc(
+            &server,
+            "sendTransaction",
+            json!(Signature::default().to_string()),
+            None,
+        )
+        .await;
+        mock_rpc(
+            &server,
+            "getSignatureStatuses",
+            status_result(json!({
+                "confirmationStatus": "processed",
+                "err": { "InstructionError": [0, "Custom"] }
+            })),
+            None,
+        )
+        .await;
+
+        let sender = create_sender(&server);
+        let result = sender
+            .send_and_confirm(&signer, &instructions, &signer)
+            .await;
+
+        assert!(matches!(result, Err(SignerError::RemoteApiError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_send_and_confirm_missing_signer() {
+        let server = MockServer::start().await;
+        let signer = MemorySigner::new(Keypair::new());
+        let other = Pubkey::new_unique();
+        let instructions = [create_transfer_instruction(
+            &other,
+            &Pubkey::new_unique(),
+            1_000,
+        )];
+
+        mock_rpc(
+            &server,
+            "getLatestBlockhash",
+            blockhash_result(&Hash::new_from_array([1u8; 32]), 100),
+            None,
+        )
+        .await;
+
+        let sender = create_sender(&server);
+        let result = sender
+            .send_and_confirm(&signer, &instructions, &signer)
+            .await;
+
+        assert!(matches!(result, Err(SignerError::ConfigError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_json_rpc_error_response() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": -32002, "message": "Blockhash not found" }
+            })))
+            .mount(&server)
+            .await;
+
+        let rpc = JsonRpcSender::new(server.uri());
+        let result = rpc.send_transaction("AAAA").await;
+
+        assert!(matches!(result, Err(SignerError::RemoteApiError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_json_rpc_http_error() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let rpc = JsonRpcSender::new(server.uri());
+        let result = rpc.get_block_height(CommitmentLevel::Confirmed).await;
+
+        assert!(matches!(result, Err(SignerError::RemoteApiError(_))));
+    }
+
+    #[test]
+    fn test_commitment_ordering() {
+        assert!(CommitmentLevel::Finalized > CommitmentLevel::Confirmed);
+        assert!(CommitmentLevel::Confirmed > CommitmentLevel::Processed);
+        assert_eq!(
+            CommitmentLevel::from_str("finalized").unwrap(),
+            CommitmentLevel::Finalized
+        );
+        assert!(CommitmentLevel::from_str("rooted").is_err());
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "watch_only")]
+    async fn test_send_and_confirm_refuses_watch_only_signature() {
+        let server = MockServer::start().await;
+        let signer = crate::watch_only::WatchOnlySigner::new(Pubkey::new_unique());
+        let instructions = [create_transfer_instruction(
+            &signer.pubkey(),
+            &Pubkey::new_unique(),
+            1_000,
+        )];
+
+        mock_rpc(
+            &server,
+            "getLatestBlockhash",
+            blockhash_result(&Hash::new_from_array([1u8; 32]), 100),
+            None,
+        )
+        .await;
+
+        let sender = create_sender(&server);
+        let result = sender
+            .send_and_confirm(&signer, &instructions, &signer)
+            .await;
+
+        assert!(matches!(result, Err(SignerError::SigningFailed(_))));
+        let requests = server.received_requests().await.unwrap();
+        assert!(requests
+            .iter()
+            .all(|r| !String::from_utf8_lossy(&r.body).contains("sendTransaction")));
+    }
+}
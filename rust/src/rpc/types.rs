@@ -0,0 +1,45 @@
+//! Trezoa JSON-RPC types
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+pub struct JsonRpcRequest<'a> {
+    pub jsonrpc: &'static str,
+    pub id: u64,
+    pub method: &'a str,
+    pub params: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+pub struct JsonRpcResponse<T> {
+    pub result: Option<T>,
+    pub error: Option<JsonRpcError>,
+}
+
+#[derive(Deserialize)]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+/// Wrapper for RPC results that carry a context slot
+#[derive(Deserialize)]
+pub struct RpcContextResponse<T> {
+    pub value: T,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatestBlockhashValue {
+    pub blockhash: String,
+    pub last_valid_block_height: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureStatusValue {
+    #[serde(default)]
+    pub confirmation_status: Option<String>,
+    #[serde(default)]
+    pub err: Option<serde_json::Value>,
+}
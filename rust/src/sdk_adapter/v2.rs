@@ -2,15 +2,19 @@
 
 // Re-export core types from trezoa-sdk v2
 #[allow(unused_imports)]
-pub use trezoa_sdk::hash::Hash;
+pub use trezoa_sdk::hash::{hashv, Hash};
 #[allow(unused_imports)]
 pub use trezoa_sdk::instruction::{AccountMeta, Instruction};
 #[allow(unused_imports)]
+pub use trezoa_sdk::message::compiled_instruction::CompiledInstruction;
+#[allow(unused_imports)]
 pub use trezoa_sdk::message::Message;
+#[allow(unused_imports)]
+pub use trezoa_sdk::message::{v0, VersionedMessage};
 pub use trezoa_sdk::pubkey::Pubkey;
 pub use trezoa_sdk::signature::{Keypair, Signature};
 pub use trezoa_sdk::signer::Signer;
-pub use trezoa_sdk::transaction::Transaction;
+pub use trezoa_sdk::transaction::{Transaction, VersionedTransaction};
 
 /// Parse a keypair from bytes (v2 adapter)
 pub fn keypair_from_bytes(bytes: &[u8]) -> Result<Keypair, String> {
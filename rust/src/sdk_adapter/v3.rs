@@ -2,16 +2,20 @@
 
 // Re-export core types from trezoa-sdk v3
 #[allow(unused_imports)]
-pub use trezoa_sdk_v3::hash::Hash;
+pub use trezoa_sdk_v3::hash::{hashv, Hash};
 #[allow(unused_imports)]
 pub use trezoa_sdk_v3::instruction::{AccountMeta, Instruction};
 #[allow(unused_imports)]
+pub use trezoa_sdk_v3::message::compiled_instruction::CompiledInstruction;
+#[allow(unused_imports)]
 pub use trezoa_sdk_v3::message::Message;
+#[allow(unused_imports)]
+pub use trezoa_sdk_v3::message::{v0, VersionedMessage};
 pub use trezoa_sdk_v3::pubkey::Pubkey;
 pub use trezoa_sdk_v3::signature::{Keypair, Signature};
 #[allow(unused_imports)]
 pub use trezoa_sdk_v3::signer::Signer;
-pub use trezoa_sdk_v3::transaction::Transaction;
+pub use trezoa_sdk_v3::transaction::{Transaction, VersionedTransaction};
 
 /// Parse a keypair from bytes (v3 adapter)
 pub fn keypair_from_bytes(bytes: &[u8]) -> Result<Keypair, String> {
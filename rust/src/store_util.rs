@@ -0,0 +1,111 @@
+//! Helpers shared by the in-memory and JSON file stores
+//!
+//! File stores rewrite the whole file on every change and only serialize access
+//! within one process, so a given file must not be shared between processes.
+
+use crate::error::SignerError;
+use serde::{de::DeserializeOwned, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Current Unix timestamp in seconds
+pub(crate) fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Lock a store mutex, recovering the data if a previous holder panicked
+///
+/// Store operations leave their maps consistent between statements, so the data is
+/// still usable after a panic elsewhere.
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Read a JSON file, or the default value if it does not exist yet
+pub(crate) async fn read_json<T: DeserializeOwned + Default>(
+    path: &Path,
+) -> Result<T, SignerError> {
+    match tokio::fs::read(path).await {
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Replace a JSON file atomically
+///
+/// The value is written to a uniquely named file next to `path` and renamed over it,
+/// so a crash never leaves a truncated file and concurrent writers never share a
+/// temporary file.
+pub(crate) async fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), SignerError> {
+    let bytes = serde_json::to_vec(value)?;
+    let tmp_path = temp_path(path);
+
+    if let Err(e) = tokio::fs::write(&tmp_path, bytes).await {
+        let _ = tokio::fs::remove_file(&tmp_path).await;
+        return Err(e.into());
+    }
+    if let Err(e) = tokio::fs::rename(&tmp_path, path).await {
+        let _ = tokio::fs::remove_file(&tmp_path).await;
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(
+        ".{file_name}.{}.{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_temp_paths_are_unique() {
+        let path = Path::new("/tmp/store.json");
+
+        let first = temp_path(path);
+        let second = temp_path(path);
+
+        assert_ne!(first, second);
+        assert_eq!(first.parent(), path.parent());
+    }
+
+    #[tokio::test]
+    async fn test_json_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "trezoa-keychain-store-{}.json",
+            rand::random::<u64>()
+        ));
+
+        let missing: HashMap<String, u64> = read_json(&path).await.unwrap();
+        assert!(missing.is_empty());
+
+        let value = HashMap::from([("a".to_string(), 1u64)]);
+        write_json(&path, &value).await.unwrap();
+        assert_eq!(
+            read_json::<HashMap<String, u64>>(&path).await.unwrap(),
+            value
+        );
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use crate::sdk_adapter::{AccountMeta, Hash, Instruction, Message, Pubkey, Transaction};
 
-fn create_transfer_instruction(from: &Pubkey, to: &Pubkey, lamports: u64) -> Instruction {
+pub fn create_transfer_instruction(from: &Pubkey, to: &Pubkey, lamports: u64) -> Instruction {
     Instruction {
         program_id: Pubkey::from_str("11111111111111111111111111111111").unwrap(),
         accounts: vec![AccountMeta::new(*from, true), AccountMeta::new(*to, false)],
@@ -23,6 +23,47 @@ pub fn create_test_transaction_with_recipient(from: &Pubkey, to: &Pubkey) -> Tra
     let instruction = create_transfer_instruction(from, to, 1_000_000);
     let message = Message::new(&[instruction], Some(from));
     let mut tx = Transaction::new_unsigned(message);
-    tx.message.recent_blockhash = Hash::default();
+    tx.message.recent_blockhash = Hash::new_from_array([1u8; 32]);
     tx
 }
+
+/// Strings that are often valid base64, base58 or hex of arbitrary bytes, so that
+/// response parsers get past decoding and into their length checks
+pub fn arb_encoded_string() -> impl proptest::strategy::Strategy<Value = String> {
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+    use proptest::prelude::*;
+
+    let bytes = || prop::collection::vec(any::<u8>(), 0..100);
+    prop_oneof![
+        ".*",
+        bytes().prop_map(|bytes| STANDARD.encode(bytes)),
+        bytes().prop_map(|bytes| bs58::encode(bytes).into_string()),
+        bytes().prop_map(|bytes| bytes.iter().map(|b| format!("{b:02x}")).collect()),
+    ]
+}
+
+/// Arbitrary JSON whose object keys are mostly drawn from `keys`, so that values
+/// regularly land in the fields a parser looks at
+pub fn arb_json(
+    keys: &'static [&'static str],
+) -> impl proptest::strategy::Strategy<Value = serde_json::Value> {
+    use proptest::prelude::*;
+    use serde_json::Value;
+
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        any::<i64>().prop_map(Value::from),
+        any::<f64>().prop_map(Value::from),
+        arb_encoded_string().prop_map(Value::String),
+    ];
+    let key = prop_oneof![3 => prop::sample::select(keys).prop_map(str::to_string), 1 => ".*"];
+
+    leaf.prop_recursive(5, 64, 6, move |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..6).prop_map(Value::Array),
+            prop::collection::vec((key.clone(), inner), 0..6)
+                .prop_map(|members| Value::Object(members.into_iter().collect())),
+        ]
+    })
+}
@@ -1,8 +1,18 @@
 #[cfg(test)]
 pub mod litesvm_util;
+#[cfg(feature = "integration-tests")]
 pub mod rpc_util;
+// Runs against LiteSVM only, like test_nonce_integration
+pub mod test_ed25519_integration;
+#[cfg(feature = "integration-tests")]
 pub mod test_fireblocks_integration;
+#[cfg(feature = "integration-tests")]
 pub mod test_kms_integration;
+// Runs against LiteSVM without credentials, so it is part of the default test run
+pub mod test_nonce_integration;
+#[cfg(feature = "integration-tests")]
 pub mod test_privy_integration;
+#[cfg(feature = "integration-tests")]
 pub mod test_turnkey_integration;
+#[cfg(feature = "integration-tests")]
 pub mod test_vault_integration;
@@ -0,0 +1,75 @@
+// Ed25519 precompile tests run entirely against LiteSVM with sigverify enabled.
+
+#[cfg(feature = "memory")]
+#[cfg(test)]
+mod tests {
+    use crate::ed25519_util::Ed25519Util;
+    use crate::memory::MemorySigner;
+    use crate::sdk_adapter::{Keypair, Message, Transaction};
+    use crate::tests::litesvm_util::{get_latest_blockhash, start_litesvm};
+    use crate::traits::TrezoaSigner;
+
+    #[tokio::test]
+    async fn test_ed25519_instruction_verifies_on_chain() {
+        let payer = MemorySigner::new(Keypair::new());
+        let attester = MemorySigner::new(Keypair::new());
+        let cosigner = MemorySigner::new(Keypair::new());
+
+        let mut svm = start_litesvm(&payer.pubkey())
+            .await
+            .expect("Failed to start LiteSVM");
+
+        let instruction = Ed25519Util::sign_and_build_multi_instruction(&[
+            (&attester, b"price=42".as_slice()),
+            (&cosigner, b"price=42;cosigned".as_slice()),
+        ])
+        .await
+        .expect("Failed to build Ed25519 instruction");
+
+        let mut message = Message::new(&[instruction], Some(&payer.pubkey()));
+        message.recent_blockhash = get_latest_blockhash(&svm)
+            .await
+            .expect("Failed to get latest blockhash");
+        let mut transaction = Transaction::new_unsigned(message);
+
+        payer
+            .sign_transaction(&mut transaction)
+            .await
+            .expect("Failed to sign transaction");
+
+        svm.send_transaction(transaction)
+            .expect("Ed25519 instruction should verify");
+    }
+
+    #[tokio::test]
+    async fn test_ed25519_instruction_rejects_bad_signature() {
+        let payer = MemorySigner::new(Keypair::new());
+        let attester = MemorySigner::new(Keypair::new());
+
+        let mut svm = start_litesvm(&payer.pubkey())
+            .await
+            .expect("Failed to start LiteSVM");
+
+        let mut instruction = Ed25519Util::sign_and_build_instruction(&attester, b"price=42")
+            .await
+            .expect("Failed to build Ed25519 instruction");
+
+        // Change the signed message after signing
+        let last = instruction.data.len() - 1;
+        instruction.data[last] = b'3';
+        assert!(Ed25519Util::verify_instruction(&instruction).is_err());
+
+        let mut message = Message::new(&[instruction], Some(&payer.pubkey()));
+        message.recent_blockhash = get_latest_blockhash(&svm)
+            .await
+            .expect("Failed to get latest blockhash");
+        let mut transaction = Transaction::new_unsigned(message);
+
+        payer
+            .sign_transaction(&mut transaction)
+            .await
+            .expect("Failed to sign transaction");
+
+        assert!(svm.send_transaction(transaction).is_err());
+    }
+}
@@ -0,0 +1,142 @@
+// Durable nonce tests run entirely against LiteSVM and need no credentials.
+
+#[cfg(feature = "memory")]
+#[cfg(test)]
+mod tests {
+    use crate::memory::MemorySigner;
+    use crate::nonce_util::{NonceUtil, NONCE_ACCOUNT_LENGTH};
+    use crate::sdk_adapter::{Keypair, Pubkey};
+    use crate::test_util::create_transfer_instruction;
+    use crate::tests::litesvm_util::{get_latest_blockhash, start_litesvm};
+    use crate::traits::TrezoaSigner;
+
+    #[cfg(feature = "sdk-v2")]
+    use litesvm::LiteSVM;
+    #[cfg(feature = "sdk-v3")]
+    use litesvm_v3::LiteSVM;
+
+    async fn create_nonce_account(
+        svm: &mut LiteSVM,
+        payer: &MemorySigner,
+        nonce_signer: &MemorySigner,
+    ) {
+        let lamports = svm.minimum_balance_for_rent_exemption(NONCE_ACCOUNT_LENGTH as usize);
+        let blockhash = get_latest_blockhash(svm)
+            .await
+            .expect("Failed to get latest blockhash");
+
+        let mut transaction = NonceUtil::create_nonce_account_transaction(
+            &payer.pubkey(),
+            &nonce_signer.pubkey(),
+            &payer.pubkey(),
+            lamports,
+            blockhash,
+        );
+
+        payer
+            .sign_partial_transaction(&mut transaction)
+            .await
+            .expect("Failed to sign with payer");
+        nonce_signer
+            .sign_transaction(&mut transaction)
+            .await
+            .expect("Failed to sign with nonce account");
+
+        svm.send_transaction(transaction)
+            .expect("Failed to create nonce account");
+    }
+
+    fn read_nonce_account(
+        svm: &LiteSVM,
+        nonce_account: &Pubkey,
+    ) -> crate::nonce_util::NonceAccountState {
+        let account = svm
+            .get_account(nonce_account)
+            .expect("Nonce account should exist");
+        NonceUtil::parse_nonce_account(&account.data).expect("Failed to parse nonce account")
+    }
+
+    #[tokio::test]
+    async fn test_nonce_create_and_advance() {
+        let payer = MemorySigner::new(Keypair::new());
+        let nonce_signer = MemorySigner::new(Keypair::new());
+        let recipient = Pubkey::new_unique();
+
+        let mut svm = start_litesvm(&payer.pubkey())
+            .await
+            .expect("Failed to start LiteSVM");
+
+        create_nonce_account(&mut svm, &payer, &nonce_signer).await;
+
+        let nonce_state = read_nonce_account(&svm, &nonce_signer.pubkey());
+        assert_eq!(nonce_state.authority, payer.pubkey());
+
+        // The blockhash the nonce was derived from expires, the nonce does not
+        svm.expire_blockhash();
+
+        let mut transaction = NonceUtil::build_nonce_transaction(
+            &[create_transfer_instruction(
+                &payer.pubkey(),
+                &recipient,
+                1_000_000,
+            )],
+            &payer.pubkey(),
+            &nonce_signer.pubkey(),
+            &payer.pubkey(),
+            nonce_state.blockhash,
+        );
+        assert!(NonceUtil::is_nonce_transaction(&transaction));
+
+        let (_, signature) = payer
+            .sign_transaction(&mut transaction)
+            .await
+            .expect("Failed to sign nonce transaction");
+        assert!(signature.verify(&payer.pubkey().to_bytes(), &transaction.message_data()));
+
+        svm.send_transaction(transaction.clone())
+            .expect("Nonce transaction should land after the blockhash expired");
+
+        assert_eq!(svm.get_balance(&recipient), Some(1_000_000));
+
+        // Advancing the nonce invalidates the signed transaction
+        let advanced_state = read_nonce_account(&svm, &nonce_signer.pubkey());
+        assert_ne!(advanced_state.blockhash, nonce_state.blockhash);
+
+        svm.expire_blockhash();
+        assert!(svm.send_transaction(transaction).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_nonce_authorize() {
+        let payer = MemorySigner::new(Keypair::new());
+        let nonce_signer = MemorySigner::new(Keypair::new());
+        let new_authority = Pubkey::new_unique();
+
+        let mut svm = start_litesvm(&payer.pubkey())
+            .await
+            .expect("Failed to start LiteSVM");
+
+        create_nonce_account(&mut svm, &payer, &nonce_signer).await;
+
+        let mut transaction = NonceUtil::authorize_nonce_account_transaction(
+            &payer.pubkey(),
+            &nonce_signer.pubkey(),
+            &payer.pubkey(),
+            &new_authority,
+            get_latest_blockhash(&svm)
+                .await
+                .expect("Failed to get latest blockhash"),
+        );
+
+        payer
+            .sign_transaction(&mut transaction)
+            .await
+            .expect("Failed to sign authorize transaction");
+
+        svm.send_transaction(transaction)
+            .expect("Failed to authorize nonce account");
+
+        let nonce_state = read_nonce_account(&svm, &nonce_signer.pubkey());
+        assert_eq!(nonce_state.authority, new_authority);
+    }
+}
@@ -54,7 +54,7 @@ mod tests {
         let message = transaction.message_data();
 
         let signature = signer
-            .sign_message(&message)
+            .sign_message_unchecked(&message)
             .await
             .expect("Failed to sign message with Turnkey");
 
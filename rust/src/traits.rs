@@ -3,10 +3,185 @@
 use async_trait::async_trait;
 
 use crate::error::SignerError;
-use crate::sdk_adapter::{Pubkey, Signature, Transaction};
+use crate::offchain_message::OffchainMessage;
+use crate::sdk_adapter::{Pubkey, Signature, Transaction, VersionedTransaction};
+use crate::transaction_util::TransactionUtil;
 
 pub type SignedTransaction = (String, Signature);
 
+/// Anything a signer can be asked to sign
+#[derive(Debug, Clone)]
+pub enum SigningRequest {
+    /// Legacy transaction that must be fully signed
+    Transaction(Transaction),
+    /// Legacy transaction that other parties will also sign
+    PartialTransaction(Transaction),
+    /// Versioned (v0 or legacy) transaction
+    VersionedTransaction(VersionedTransaction),
+    /// Arbitrary message bytes
+    Message(Vec<u8>),
+    /// Arbitrary message bytes, including transaction messages
+    /// (see [`TrezoaSigner::sign_message_unchecked`])
+    UncheckedMessage(Vec<u8>),
+    /// Off-chain message, signed with its signing domain prefix
+    OffchainMessage(OffchainMessage),
+    /// Base64-encoded legacy transaction
+    SerializedTransaction(String),
+}
+
+impl SigningRequest {
+    /// Decode a [`SerializedTransaction`](Self::SerializedTransaction) into a
+    /// [`Transaction`](Self::Transaction); other requests are returned unchanged
+    ///
+    /// Both are signed the same way, so wrappers that inspect requests call this first
+    /// and only need to handle the decoded form.
+    pub fn decoded(self) -> Result<Self, SignerError> {
+        match self {
+            SigningRequest::SerializedTransaction(encoded) => Ok(SigningRequest::Transaction(
+                TransactionUtil::deserialize_transaction(&encoded)?,
+            )),
+            request => Ok(request),
+        }
+    }
+
+    /// The bytes the signature covers
+    pub fn message_bytes(&self) -> Result<Vec<u8>, SignerError> {
+        Ok(match self {
+            SigningRequest::Transaction(transaction)
+            | SigningRequest::PartialTransaction(transaction) => transaction.message_data(),
+            SigningRequest::VersionedTransaction(transaction) => transaction.message.serialize(),
+            SigningRequest::Message(message) | SigningRequest::UncheckedMessage(message) => {
+                message.clone()
+            }
+            SigningRequest::OffchainMessage(message) => message.serialize(),
+            SigningRequest::SerializedTransaction(encoded) => {
+                TransactionUtil::deserialize_transaction(encoded)?.message_data()
+            }
+        })
+    }
+
+    /// Refuse [`UncheckedMessage`](Self::UncheckedMessage) requests, for wrappers that only
+    /// check transactions and so can't tell what raw message bytes would authorise
+    pub(crate) fn ensure_inspectable(&self, wrapper: &str) -> Result<(), SignerError> {
+        if let SigningRequest::UncheckedMessage(_) = self {
+            return Err(SignerError::PolicyViolation(format!(
+                "{wrapper} cannot inspect unchecked messages"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Build the response for a signature by `signer` that was obtained elsewhere, such
+    /// as from a cache
+    pub fn into_response(
+        self,
+        signer: &Pubkey,
+        signature: Signature,
+    ) -> Result<SigningResponse, SignerError> {
+        match self.decoded()? {
+            SigningRequest::Transaction(mut transaction)
+            | SigningRequest::PartialTransaction(mut transaction) => {
+                TransactionUtil::add_signature_to_transaction(&mut transaction, signer, signature)?;
+                Ok(SigningResponse::Transaction {
+                    serialized: TransactionUtil::serialize_transaction(&transaction)?,
+                    transaction,
+                    signature,
+                })
+            }
+            SigningRequest::VersionedTransaction(mut transaction) => {
+                TransactionUtil::add_signature_to_versioned_transaction(
+                    &mut transaction,
+                    signer,
+                    signature,
+                )?;
+                Ok(SigningResponse::VersionedTransaction {
+                    serialized: TransactionUtil::serialize_versioned_transaction(&transaction)?,
+                    transaction,
+                    signature,
+                })
+            }
+            _ => Ok(SigningResponse::Signature(signature)),
+        }
+    }
+}
+
+/// Result of a [`SigningRequest`]
+#[derive(Debug, Clone)]
+pub enum SigningResponse {
+    /// Signed legacy transaction with its base64 encoding
+    Transaction {
+        transaction: Transaction,
+        serialized: String,
+        signature: Signature,
+    },
+    /// Signed versioned transaction with its base64 encoding
+    VersionedTransaction {
+        transaction: VersionedTransaction,
+        serialized: String,
+        signature: Signature,
+    },
+    /// Signature over a message
+    Signature(Signature),
+}
+
+impl SigningResponse {
+    /// The signature produced by this signer
+    pub fn signature(&self) -> Signature {
+        match self {
+            SigningResponse::Transaction { signature, .. }
+            | SigningResponse::VersionedTransaction { signature, .. }
+            | SigningResponse::Signature(signature) => *signature,
+        }
+    }
+
+    /// Base64 encoding of the signed transaction, for transaction responses
+    pub fn serialized(&self) -> Option<&str> {
+        match self {
+            SigningResponse::Transaction { serialized, .. }
+            | SigningResponse::VersionedTransaction { serialized, .. } => Some(serialized),
+            SigningResponse::Signature(_) => None,
+        }
+    }
+
+    /// Write a signed legacy transaction back to `tx`, as `sign_transaction` does
+    pub fn into_transaction(self, tx: &mut Transaction) -> Result<SignedTransaction, SignerError> {
+        match self {
+            SigningResponse::Transaction {
+                transaction,
+                serialized,
+                signature,
+            } => {
+                *tx = transaction;
+                Ok((serialized, signature))
+            }
+            _ => Err(SignerError::Other(
+                "Expected a signed legacy transaction".to_string(),
+            )),
+        }
+    }
+
+    /// Write a signed versioned transaction back to `tx`, as `sign_versioned_transaction`
+    /// does
+    pub fn into_versioned_transaction(
+        self,
+        tx: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        match self {
+            SigningResponse::VersionedTransaction {
+                transaction,
+                serialized,
+                signature,
+            } => {
+                *tx = transaction;
+                Ok((serialized, signature))
+            }
+            _ => Err(SignerError::Other(
+                "Expected a signed versioned transaction".to_string(),
+            )),
+        }
+    }
+}
+
 /// Trait for signing Trezoa transactions
 ///
 /// All signer implementations must implement this trait to provide
@@ -41,6 +216,17 @@ pub trait TrezoaSigner: Send + Sync {
     /// The signature produced by signing the message
     async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError>;
 
+    /// Sign arbitrary message bytes, including transaction messages
+    ///
+    /// `sign_message` refuses input that parses as a transaction message listing this
+    /// signer as a required signer, since that would produce a transaction signature
+    /// without any transaction-level checks. Use this only when that is the intent.
+    ///
+    /// The default implementation forwards to `sign_message`.
+    async fn sign_message_unchecked(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        self.sign_message(message).await
+    }
+
     /// Partially sign a transaction and return it as a base64-encoded string
     ///
     /// This method signs the transaction and serializes it with `requireAllSignatures: false`,
@@ -59,10 +245,366 @@ pub trait TrezoaSigner: Send + Sync {
         tx: &mut Transaction,
     ) -> Result<SignedTransaction, SignerError>;
 
+    /// Sign a versioned (v0 or legacy) transaction
+    ///
+    /// The default implementation runs [`TransactionUtil::validate_versioned_for_signing`]
+    /// and signs the message via `sign_message_unchecked`. The backends in this crate
+    /// override it to honour their `with_transaction_validation` setting.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - The transaction to sign (will be modified in place)
+    ///
+    /// # Returns
+    ///
+    /// The base64 encoded transaction and signature
+    async fn sign_versioned_transaction(
+        &self,
+        tx: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        TransactionUtil::validate_versioned_for_signing(tx, &self.pubkey())?;
+
+        let signature = self.sign_message_unchecked(&tx.message.serialize()).await?;
+
+        TransactionUtil::add_signature_to_versioned_transaction(tx, &self.pubkey(), signature)?;
+
+        Ok((
+            TransactionUtil::serialize_versioned_transaction(tx)?,
+            signature,
+        ))
+    }
+
     /// Check if the signer is available and healthy
     ///
     /// # Returns
     ///
     /// `true` if the signer can be used, `false` otherwise
     async fn is_available(&self) -> bool;
+
+    /// Sign any kind of [`SigningRequest`]
+    ///
+    /// This is the one method that wrappers such as policies, caches and approval queues
+    /// override. The crate's `forward_to_sign!` macro forwards their per-method entry points
+    /// here, so every request passes through it.
+    /// Backends keep the default, which dispatches to the per-method entry points.
+    async fn sign(&self, request: SigningRequest) -> Result<SigningResponse, SignerError> {
+        match request {
+            SigningRequest::Transaction(mut transaction) => {
+                let (serialized, signature) = self.sign_transaction(&mut transaction).await?;
+                Ok(SigningResponse::Transaction {
+                    transaction,
+                    serialized,
+                    signature,
+                })
+            }
+            SigningRequest::PartialTransaction(mut transaction) => {
+                let (serialized, signature) =
+                    self.sign_partial_transaction(&mut transaction).await?;
+                Ok(SigningResponse::Transaction {
+                    transaction,
+                    serialized,
+                    signature,
+                })
+            }
+            SigningRequest::VersionedTransaction(mut transaction) => {
+                let (serialized, signature) =
+                    self.sign_versioned_transaction(&mut transaction).await?;
+                Ok(SigningResponse::VersionedTransaction {
+                    transaction,
+                    serialized,
+                    signature,
+                })
+            }
+            SigningRequest::Message(message) => Ok(SigningResponse::Signature(
+                self.sign_message(&message).await?,
+            )),
+            SigningRequest::UncheckedMessage(message) => Ok(SigningResponse::Signature(
+                self.sign_message_unchecked(&message).await?,
+            )),
+            SigningRequest::OffchainMessage(message) => Ok(SigningResponse::Signature(
+                self.sign_message(&message.serialize()).await?,
+            )),
+            SigningRequest::SerializedTransaction(encoded) => {
+                let mut transaction = TransactionUtil::deserialize_transaction(&encoded)?;
+                let (serialized, signature) = self.sign_transaction(&mut transaction).await?;
+                Ok(SigningResponse::Transaction {
+                    transaction,
+                    serialized,
+                    signature,
+                })
+            }
+        }
+    }
+}
+
+/// Implement [`TrezoaSigner`] for a wrapper signer by routing every entry point through its
+/// inherent `sign_request` method
+///
+/// The wrapper must be generic over the wrapped signer and keep it in an `inner` field.
+/// `sign_request` gets the [`decoded`](SigningRequest::decoded) request, so every wrapper
+/// sees each [`SigningRequest`] variant the same way, whichever entry point was called.
+macro_rules! forward_to_sign {
+    ($wrapper:ident) => {
+        #[async_trait::async_trait]
+        impl<S: $crate::traits::TrezoaSigner> $crate::traits::TrezoaSigner for $wrapper<S> {
+            fn pubkey(&self) -> $crate::sdk_adapter::Pubkey {
+                $crate::traits::TrezoaSigner::pubkey(&self.inner)
+            }
+
+            async fn sign_transaction(
+                &self,
+                tx: &mut $crate::sdk_adapter::Transaction,
+            ) -> Result<$crate::traits::SignedTransaction, $crate::error::SignerError> {
+                let request = $crate::traits::SigningRequest::Transaction(tx.clone());
+                $crate::traits::TrezoaSigner::sign(self, request)
+                    .await?
+                    .into_transaction(tx)
+            }
+
+            async fn sign_message(
+                &self,
+                message: &[u8],
+            ) -> Result<$crate::sdk_adapter::Signature, $crate::error::SignerError> {
+                let request = $crate::traits::SigningRequest::Message(message.to_vec());
+                Ok($crate::traits::TrezoaSigner::sign(self, request)
+                    .await?
+                    .signature())
+            }
+
+            async fn sign_message_unchecked(
+                &self,
+                message: &[u8],
+            ) -> Result<$crate::sdk_adapter::Signature, $crate::error::SignerError> {
+                let request = $crate::traits::SigningRequest::UncheckedMessage(message.to_vec());
+                Ok($crate::traits::TrezoaSigner::sign(self, request)
+                    .await?
+                    .signature())
+            }
+
+            async fn sign_partial_transaction(
+                &self,
+                tx: &mut $crate::sdk_adapter::Transaction,
+            ) -> Result<$crate::traits::SignedTransaction, $crate::error::SignerError> {
+                let request = $crate::traits::SigningRequest::PartialTransaction(tx.clone());
+                $crate::traits::TrezoaSigner::sign(self, request)
+                    .await?
+                    .into_transaction(tx)
+            }
+
+            async fn sign_versioned_transaction(
+                &self,
+                tx: &mut $crate::sdk_adapter::VersionedTransaction,
+            ) -> Result<$crate::traits::SignedTransaction, $crate::error::SignerError> {
+                let request = $crate::traits::SigningRequest::VersionedTransaction(tx.clone());
+                $crate::traits::TrezoaSigner::sign(self, request)
+                    .await?
+                    .into_versioned_transaction(tx)
+            }
+
+            async fn is_available(&self) -> bool {
+                $crate::traits::TrezoaSigner::is_available(&self.inner).await
+            }
+
+            async fn sign(
+                &self,
+                request: $crate::traits::SigningRequest,
+            ) -> Result<$crate::traits::SigningResponse, $crate::error::SignerError> {
+                self.sign_request(request.decoded()?).await
+            }
+        }
+    };
+}
+
+pub(crate) use forward_to_sign;
+
+#[cfg(all(test, feature = "memory"))]
+mod tests {
+    use super::*;
+    use crate::memory::MemorySigner;
+    use crate::sdk_adapter::Keypair;
+    use crate::test_util::create_test_transaction;
+
+    #[tokio::test]
+    async fn test_sign_transaction_request() {
+        let signer = MemorySigner::new(Keypair::new());
+        let tx = create_test_transaction(&signer.pubkey());
+
+        let response = signer
+            .sign(SigningRequest::Transaction(tx.clone()))
+            .await
+            .unwrap();
+
+        let SigningResponse::Transaction {
+            transaction,
+            serialized,
+            signature,
+        } = response
+        else {
+            panic!("Expected transaction response");
+        };
+        assert_eq!(transaction.signatures[0], signature);
+        assert!(signature.verify(signer.pubkey().as_ref(), &tx.message_data()));
+        assert_eq!(
+            TransactionUtil::deserialize_transaction(&serialized).unwrap(),
+            transaction
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sign_serialized_transaction_request() {
+        let signer = MemorySigner::new(Keypair::new());
+        let tx = create_test_transaction(&signer.pubkey());
+        let encoded = TransactionUtil::serialize_transaction(&tx).unwrap();
+
+        let response = signer
+            .sign(SigningRequest::SerializedTransaction(encoded))
+            .await
+            .unwrap();
+
+        assert!(matches!(response, SigningResponse::Transaction { .. }));
+        assert!(response
+            .signature()
+            .verify(signer.pubkey().as_ref(), &tx.message_data()));
+    }
+
+    #[tokio::test]
+    async fn test_sign_versioned_transaction_request() {
+        let signer = MemorySigner::new(Keypair::new());
+        let tx = VersionedTransaction::from(create_test_transaction(&signer.pubkey()));
+
+        let response = signer
+            .sign(SigningRequest::VersionedTransaction(tx.clone()))
+            .await
+            .unwrap();
+
+        let SigningResponse::VersionedTransaction {
+            transaction,
+            signature,
+            ..
+        } = response
+        else {
+            panic!("Expected versioned transaction response");
+        };
+        assert_eq!(transaction.signatures[0], signature);
+        assert!(signature.verify(signer.pubkey().as_ref(), &tx.message.serialize()));
+    }
+
+    #[tokio::test]
+    async fn test_sign_versioned_transaction_request_is_validated() {
+        let signer = MemorySigner::new(Keypair::new());
+
+        let mut zero_blockhash = create_test_transaction(&signer.pubkey());
+        zero_blockhash.message.recent_blockhash = Default::default();
+        let result = signer
+            .sign(SigningRequest::VersionedTransaction(
+                VersionedTransaction::from(zero_blockhash),
+            ))
+            .await;
+        assert!(matches!(
+            result,
+            Err(SignerError::InvalidTransaction(
+                crate::error::TransactionValidationError::ZeroBlockhash
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_versioned_transaction_honours_validation_opt_out() {
+        let signer = MemorySigner::new(Keypair::new()).with_transaction_validation(false);
+        let mut zero_blockhash = create_test_transaction(&signer.pubkey());
+        zero_blockhash.message.recent_blockhash = Default::default();
+
+        let response = signer
+            .sign(SigningRequest::VersionedTransaction(
+                VersionedTransaction::from(zero_blockhash),
+            ))
+            .await;
+
+        assert!(matches!(
+            response,
+            Ok(SigningResponse::VersionedTransaction { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_unchecked_message_request() {
+        let signer = MemorySigner::new(Keypair::new());
+        let message = create_test_transaction(&signer.pubkey()).message_data();
+
+        assert!(signer
+            .sign(SigningRequest::Message(message.clone()))
+            .await
+            .is_err());
+        let response = signer
+            .sign(SigningRequest::UncheckedMessage(message.clone()))
+            .await
+            .unwrap();
+        assert!(response
+            .signature()
+            .verify(signer.pubkey().as_ref(), &message));
+    }
+
+    #[tokio::test]
+    async fn test_into_response() {
+        let signer = MemorySigner::new(Keypair::new());
+        let tx = create_test_transaction(&signer.pubkey());
+        let request = SigningRequest::SerializedTransaction(
+            TransactionUtil::serialize_transaction(&tx).unwrap(),
+        );
+        let signature = signer
+            .sign_message_unchecked(&tx.message_data())
+            .await
+            .unwrap();
+
+        let mut signed = tx.clone();
+        let (serialized, returned) = request
+            .into_response(&signer.pubkey(), signature)
+            .unwrap()
+            .into_transaction(&mut signed)
+            .unwrap();
+
+        assert_eq!(returned, signature);
+        assert_eq!(signed.signatures[0], signature);
+        assert!(signed.verify().is_ok());
+        assert_eq!(
+            TransactionUtil::deserialize_transaction(&serialized).unwrap(),
+            signed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sign_message_requests() {
+        let signer = MemorySigner::new(Keypair::new());
+
+        let raw = signer
+            .sign(SigningRequest::Message(b"hello".to_vec()))
+            .await
+            .unwrap();
+        assert!(raw.signature().verify(signer.pubkey().as_ref(), b"hello"));
+
+        let offchain = OffchainMessage::new(b"hello").unwrap();
+        let response = signer
+            .sign(SigningRequest::OffchainMessage(offchain.clone()))
+            .await
+            .unwrap();
+        assert!(response
+            .signature()
+            .verify(signer.pubkey().as_ref(), &offchain.serialize()));
+        assert_ne!(response.signature(), raw.signature());
+    }
+
+    #[tokio::test]
+    async fn test_sign_rejects_non_signer() {
+        let signer = MemorySigner::new(Keypair::new());
+        let tx = VersionedTransaction::from(create_test_transaction(&Pubkey::new_unique()));
+
+        let result = signer.sign(SigningRequest::VersionedTransaction(tx)).await;
+
+        assert!(matches!(
+            result,
+            Err(SignerError::InvalidTransaction(
+                crate::error::TransactionValidationError::SignerNotFound(_)
+            ))
+        ));
+    }
 }
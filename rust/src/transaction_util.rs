@@ -1,9 +1,32 @@
-use crate::error::SignerError;
-use crate::sdk_adapter::{Pubkey, Signature, Transaction};
+use crate::error::{SignerError, TransactionValidationError};
+use crate::sdk_adapter::{Pubkey, Signature, Transaction, VersionedMessage, VersionedTransaction};
 use base64::{engine::general_purpose::STANDARD, Engine};
+use bincode::Options;
+use std::collections::{HashMap, HashSet};
 
 pub struct TransactionUtil;
 
+/// Maximum size of a serialized transaction (one network packet)
+pub const PACKET_DATA_SIZE: usize = 1232;
+
+/// Prefix of the placeholder signatures produced by watch-only signers
+const PLACEHOLDER_SIGNATURE_MARKER: &[u8; 32] = b"watch-only:unsigned-placeholder\0";
+
+/// Result of merging independently signed copies of a transaction
+#[derive(Debug, Clone)]
+pub struct MergedTransaction {
+    pub transaction: Transaction,
+    /// Required signers whose signature slot is still empty
+    pub missing_signers: Vec<Pubkey>,
+}
+
+impl MergedTransaction {
+    /// Whether every required signature is present
+    pub fn is_fully_signed(&self) -> bool {
+        self.missing_signers.is_empty()
+    }
+}
+
 impl TransactionUtil {
     /// Encodes a Transaction to a base64 serialized String
     pub fn serialize_transaction(transaction: &Transaction) -> Result<String, SignerError> {
@@ -59,4 +82,580 @@ impl TransactionUtil {
 
         Ok(())
     }
+
+    /// Check that a transaction could land once signed by `signer`.
+    ///
+    /// Rejects a zero recent blockhash, a wire size over [`PACKET_DATA_SIZE`] with every
+    /// signature filled in, duplicate account keys, and a `signer` that is missing from
+    /// the transaction or only present as a non-signer.
+    ///
+    /// Every backend runs this before signing a transaction unless it was built with
+    /// `with_transaction_validation(false)`, which is only meant for tests.
+    pub fn validate_for_signing(
+        transaction: &Transaction,
+        signer: &Pubkey,
+    ) -> Result<(), TransactionValidationError> {
+        let message = &transaction.message;
+        Self::validate_message(
+            message.recent_blockhash.as_ref(),
+            &message.account_keys,
+            message.header.num_required_signatures as usize,
+            transaction.message_data().len(),
+            signer,
+        )
+    }
+
+    /// [`validate_for_signing`](Self::validate_for_signing) for a versioned transaction
+    ///
+    /// Only the static account keys are checked; address lookup table entries cannot
+    /// be signers.
+    pub fn validate_versioned_for_signing(
+        transaction: &VersionedTransaction,
+        signer: &Pubkey,
+    ) -> Result<(), TransactionValidationError> {
+        let message = &transaction.message;
+        Self::validate_message(
+            message.recent_blockhash().as_ref(),
+            message.static_account_keys(),
+            message.header().num_required_signatures as usize,
+            message.serialize().len(),
+            signer,
+        )
+    }
+
+    fn validate_message(
+        recent_blockhash: &[u8],
+        account_keys: &[Pubkey],
+        num_required_signatures: usize,
+        message_len: usize,
+        signer: &Pubkey,
+    ) -> Result<(), TransactionValidationError> {
+        if recent_blockhash.iter().all(|b| *b == 0) {
+            return Err(TransactionValidationError::ZeroBlockhash);
+        }
+
+        let mut seen = HashSet::with_capacity(account_keys.len());
+        for key in account_keys {
+            if !seen.insert(key) {
+                return Err(TransactionValidationError::DuplicateAccountKey(
+                    key.to_string(),
+                ));
+            }
+        }
+
+        match account_keys.iter().position(|key| key == signer) {
+            Some(index) if index < num_required_signatures => {}
+            Some(_) => {
+                return Err(TransactionValidationError::SignerNotRequired(
+                    signer.to_string(),
+                ))
+            }
+            None => {
+                return Err(TransactionValidationError::SignerNotFound(
+                    signer.to_string(),
+                ))
+            }
+        }
+
+        // Signatures are prefixed with a compact-u16 length
+        let signatures_len_prefix = match num_required_signatures {
+            0..=0x7f => 1,
+            0x80..=0x3fff => 2,
+            _ => 3,
+        };
+        let size = signatures_len_prefix + num_required_signatures * 64 + message_len;
+        if size > PACKET_DATA_SIZE {
+            return Err(TransactionValidationError::TooLarge {
+                size,
+                max: PACKET_DATA_SIZE,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Parse `message` as a serialized legacy or v0 transaction message, rejecting
+    /// trailing bytes
+    pub fn parse_transaction_message(message: &[u8]) -> Option<VersionedMessage> {
+        bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .reject_trailing_bytes()
+            .deserialize::<VersionedMessage>(message)
+            .ok()
+    }
+
+    /// Whether `message` parses as a legacy or v0 transaction message that lists
+    /// `signer` as a required signer.
+    ///
+    /// A signature over such bytes is a valid transaction signature, so signing them
+    /// as an arbitrary message bypasses every transaction-level check.
+    pub fn is_transaction_message_for(message: &[u8], signer: &Pubkey) -> bool {
+        let Some(parsed) = Self::parse_transaction_message(message) else {
+            return false;
+        };
+
+        let num_required_signatures = parsed.header().num_required_signatures as usize;
+        parsed
+            .static_account_keys()
+            .iter()
+            .take(num_required_signatures)
+            .any(|key| key == signer)
+    }
+
+    /// Reject `sign_message` input that is a transaction message for `signer`.
+    ///
+    /// Callers that really mean to sign raw transaction message bytes should use
+    /// `TrezoaSigner::sign_message_unchecked`.
+    pub fn ensure_not_transaction_message(
+        message: &[u8],
+        signer: &Pubkey,
+    ) -> Result<(), SignerError> {
+        if Self::is_transaction_message_for(message, signer) {
+            return Err(SignerError::SigningFailed(
+                "Refusing to blind-sign a transaction message; use sign_transaction or sign_message_unchecked".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Encodes a VersionedTransaction to a base64 serialized String
+    pub fn serialize_versioned_transaction(
+        transaction: &VersionedTransaction,
+    ) -> Result<String, SignerError> {
+        Ok(
+            STANDARD.encode(bincode::serialize(transaction).map_err(|e| {
+                SignerError::SerializationError(format!("Failed to serialize transaction: {e}"))
+            })?),
+        )
+    }
+
+    /// Decodes a base64 serialized String into a VersionedTransaction
+    pub fn deserialize_versioned_transaction(
+        encoded: &str,
+    ) -> Result<VersionedTransaction, SignerError> {
+        let bytes = STANDARD.decode(encoded).map_err(|e| {
+            SignerError::SerializationError(format!("Failed to decode transaction: {e}"))
+        })?;
+        bincode::deserialize(&bytes).map_err(|e| {
+            SignerError::SerializationError(format!("Failed to deserialize transaction: {e}"))
+        })
+    }
+
+    /// [`get_signing_keypair_position`](Self::get_signing_keypair_position) for a
+    /// versioned transaction
+    pub fn get_versioned_signing_keypair_position(
+        transaction: &VersionedTransaction,
+        pubkey: &Pubkey,
+    ) -> Result<usize, SignerError> {
+        let num_required_signatures = transaction.message.header().num_required_signatures as usize;
+
+        transaction
+            .message
+            .static_account_keys()
+            .iter()
+            .take(num_required_signatures)
+            .position(|key| key == pubkey)
+            .ok_or_else(|| {
+                SignerError::SigningFailed(format!(
+                    "Pubkey {} not found in transaction signers",
+                    pubkey
+                ))
+            })
+    }
+
+    /// Add a signature to a versioned transaction at the signer's position.
+    pub fn add_signature_to_versioned_transaction(
+        transaction: &mut VersionedTransaction,
+        pubkey: &Pubkey,
+        signature: Signature,
+    ) -> Result<(), SignerError> {
+        let position = Self::get_versioned_signing_keypair_position(transaction, pubkey)?;
+
+        let num_required_signatures = transaction.message.header().num_required_signatures as usize;
+        if transaction.signatures.len() < num_required_signatures {
+            transaction
+                .signatures
+                .resize(num_required_signatures, Signature::default());
+        }
+        transaction.signatures[position] = signature;
+
+        Ok(())
+    }
+
+    /// Deterministic placeholder signature for a signer that holds no key.
+    ///
+    /// The placeholder never verifies, so a transaction carrying one cannot land on chain.
+    pub fn placeholder_signature(pubkey: &Pubkey) -> Signature {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(PLACEHOLDER_SIGNATURE_MARKER);
+        bytes[32..].copy_from_slice(pubkey.as_ref());
+        Signature::from(bytes)
+    }
+
+    /// Whether a signature is a watch-only placeholder
+    pub fn is_placeholder_signature(signature: &Signature) -> bool {
+        signature.as_ref().starts_with(PLACEHOLDER_SIGNATURE_MARKER)
+    }
+
+    /// Whether any signature slot of the transaction holds a watch-only placeholder
+    pub fn has_placeholder_signatures(transaction: &Transaction) -> bool {
+        transaction
+            .signatures
+            .iter()
+            .any(Self::is_placeholder_signature)
+    }
+
+    /// Refuse to broadcast a transaction carrying a watch-only placeholder
+    pub fn ensure_no_placeholder_signatures(transaction: &Transaction) -> Result<(), SignerError> {
+        Self::ensure_no_placeholders(&transaction.signatures)
+    }
+
+    /// [`ensure_no_placeholder_signatures`](Self::ensure_no_placeholder_signatures) for a
+    /// versioned transaction
+    pub fn ensure_no_versioned_placeholder_signatures(
+        transaction: &VersionedTransaction,
+    ) -> Result<(), SignerError> {
+        Self::ensure_no_placeholders(&transaction.signatures)
+    }
+
+    fn ensure_no_placeholders(signatures: &[Signature]) -> Result<(), SignerError> {
+        if signatures.iter().any(Self::is_placeholder_signature) {
+            return Err(SignerError::SigningFailed(
+                "Refusing to send a transaction signed by a watch-only signer".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Decodes a base64 serialized String into a Transaction
+    pub fn deserialize_transaction(encoded: &str) -> Result<Transaction, SignerError> {
+        let bytes = STANDARD.decode(encoded).map_err(|e| {
+            SignerError::SerializationError(format!("Failed to decode transaction: {e}"))
+        })?;
+        bincode::deserialize(&bytes).map_err(|e| {
+            SignerError::SerializationError(format!("Failed to deserialize transaction: {e}"))
+        })
+    }
+
+    /// Merge copies of the same transaction that were partially signed independently.
+    ///
+    /// Every copy must carry an identical message. Non-default signatures are verified
+    /// and combined into their slots; two different signatures for the same slot are
+    /// rejected. Slots that no copy has signed are reported in `missing_signers`.
+    pub fn merge_partially_signed(
+        transactions: &[String],
+    ) -> Result<MergedTransaction, SignerError> {
+        let mut decoded = transactions
+            .iter()
+            .map(|encoded| Self::deserialize_transaction(encoded))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter();
+
+        let mut merged = decoded.next().ok_or_else(|| {
+            SignerError::ConfigError("No transactions provided to merge".to_string())
+        })?;
+
+        let message_bytes = merged.message_data();
+        let num_required_signatures = merged.message.header.num_required_signatures as usize;
+        if merged.message.account_keys.len() < num_required_signatures {
+            return Err(SignerError::SigningFailed(
+                "Invalid account index: not enough account keys".to_string(),
+            ));
+        }
+        let signer_keys = merged.message.account_keys[..num_required_signatures].to_vec();
+
+        let mut copies = vec![std::mem::take(&mut merged.signatures)];
+        for tx in decoded {
+            if tx.message_data() != message_bytes {
+                return Err(SignerError::SigningFailed(
+                    "Cannot merge transactions with different messages".to_string(),
+                ));
+            }
+            copies.push(tx.signatures);
+        }
+
+        let mut slots: HashMap<usize, Signature> = HashMap::new();
+        for signatures in copies {
+            if signatures.len() > num_required_signatures {
+                return Err(SignerError::SigningFailed(format!(
+                    "Transaction has {} signatures but only {} are required",
+                    signatures.len(),
+                    num_required_signatures
+                )));
+            }
+
+            for (position, signature) in signatures.into_iter().enumerate() {
+                if signature == Signature::default() {
+                    continue;
+                }
+
+                let pubkey = &signer_keys[position];
+                match slots.get(&position) {
+                    Some(existing) if *existing == signature => continue,
+                    Some(_) => {
+                        return Err(SignerError::SigningFailed(format!(
+                            "Conflicting signatures for signer {pubkey}"
+                        )));
+                    }
+                    None => {}
+                }
+
+                if !signature.verify(pubkey.as_ref(), &message_bytes) {
+                    return Err(SignerError::SigningFailed(format!(
+                        "Invalid signature for signer {pubkey}"
+                    )));
+                }
+
+                slots.insert(position, signature);
+            }
+        }
+
+        merged.signatures = vec![Signature::default(); num_required_signatures];
+        let mut missing_signers = Vec::new();
+        for (position, pubkey) in signer_keys.iter().enumerate() {
+            match slots.get(&position) {
+                Some(signature) => merged.signatures[position] = *signature,
+                None => missing_signers.push(*pubkey),
+            }
+        }
+
+        Ok(MergedTransaction {
+            transaction: merged,
+            missing_signers,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "memory"))]
+mod tests {
+    use super::*;
+    use crate::memory::MemorySigner;
+    use crate::sdk_adapter::{Hash, Keypair, Message};
+    use crate::test_util::create_transfer_instruction;
+    use crate::traits::TrezoaSigner;
+
+    fn create_multi_signer_transaction(signers: &[&MemorySigner]) -> Transaction {
+        let instructions: Vec<_> = signers
+            .iter()
+            .map(|signer| create_transfer_instruction(&signer.pubkey(), &Pubkey::new_unique(), 1))
+            .collect();
+        let mut message = Message::new(&instructions, Some(&signers[0].pubkey()));
+        message.recent_blockhash = Hash::new_from_array([7u8; 32]);
+        Transaction::new_unsigned(message)
+    }
+
+    async fn partially_sign(tx: &Transaction, signer: &MemorySigner) -> String {
+        let mut copy = tx.clone();
+        signer.sign_partial_transaction(&mut copy).await.unwrap().0
+    }
+
+    #[tokio::test]
+    async fn test_merge_partially_signed_combines_signatures() {
+        let a = MemorySigner::new(Keypair::new());
+        let b = MemorySigner::new(Keypair::new());
+        let tx = create_multi_signer_transaction(&[&a, &b]);
+
+        let merged = TransactionUtil::merge_partially_signed(&[
+            partially_sign(&tx, &b).await,
+            partially_sign(&tx, &a).await,
+        ])
+        .unwrap();
+
+        assert!(merged.is_fully_signed());
+        assert!(merged.transaction.verify().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_merge_partially_signed_reports_missing() {
+        let a = MemorySigner::new(Keypair::new());
+        let b = MemorySigner::new(Keypair::new());
+        let c = MemorySigner::new(Keypair::new());
+        let tx = create_multi_signer_transaction(&[&a, &b, &c]);
+
+        let merged =
+            TransactionUtil::merge_partially_signed(&[partially_sign(&tx, &a).await]).unwrap();
+
+        assert!(!merged.is_fully_signed());
+        // Non-payer signers are ordered by the message compiler
+        assert_eq!(
+            merged.missing_signers,
+            tx.message.account_keys[1..3].to_vec()
+        );
+        assert_eq!(merged.transaction.signatures[1], Signature::default());
+    }
+
+    #[tokio::test]
+    async fn test_merge_partially_signed_accepts_duplicate_signature() {
+        let a = MemorySigner::new(Keypair::new());
+        let tx = create_multi_signer_transaction(&[&a]);
+        let signed = partially_sign(&tx, &a).await;
+
+        let merged = TransactionUtil::merge_partially_signed(&[signed.clone(), signed]).unwrap();
+
+        assert!(merged.is_fully_signed());
+    }
+
+    #[tokio::test]
+    async fn test_merge_partially_signed_rejects_different_messages() {
+        let a = MemorySigner::new(Keypair::new());
+        let tx = create_multi_signer_transaction(&[&a]);
+        let mut other = tx.clone();
+        other.message.recent_blockhash = Hash::new_from_array([8u8; 32]);
+
+        let result = TransactionUtil::merge_partially_signed(&[
+            partially_sign(&tx, &a).await,
+            partially_sign(&other, &a).await,
+        ]);
+
+        assert!(matches!(result, Err(SignerError::SigningFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_merge_partially_signed_rejects_invalid_signature() {
+        let a = MemorySigner::new(Keypair::new());
+        let b = MemorySigner::new(Keypair::new());
+        let tx = create_multi_signer_transaction(&[&a, &b]);
+
+        let mut forged = tx.clone();
+        forged.signatures[1] = b.sign_message(b"something else").await.unwrap();
+        let forged = TransactionUtil::serialize_transaction(&forged).unwrap();
+
+        let result = TransactionUtil::merge_partially_signed(&[forged]);
+
+        assert!(matches!(result, Err(SignerError::SigningFailed(msg)) if msg.contains("Invalid")));
+    }
+
+    #[tokio::test]
+    async fn test_merge_partially_signed_rejects_conflicting_signatures() {
+        let a = MemorySigner::new(Keypair::new());
+        let b = MemorySigner::new(Keypair::new());
+        let tx = create_multi_signer_transaction(&[&a, &b]);
+
+        let mut conflicting = tx.clone();
+        conflicting.signatures[0] = a.sign_message(b"something else").await.unwrap();
+        let conflicting = TransactionUtil::serialize_transaction(&conflicting).unwrap();
+
+        let result =
+            TransactionUtil::merge_partially_signed(&[partially_sign(&tx, &a).await, conflicting]);
+
+        assert!(
+            matches!(result, Err(SignerError::SigningFailed(msg)) if msg.contains("Conflicting"))
+        );
+    }
+
+    #[test]
+    fn test_validate_for_signing_accepts_valid_transaction() {
+        let payer = Pubkey::new_unique();
+        let tx = crate::test_util::create_test_transaction(&payer);
+
+        assert_eq!(TransactionUtil::validate_for_signing(&tx, &payer), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_for_signing_rejects_zero_blockhash() {
+        let payer = Pubkey::new_unique();
+        let mut tx = crate::test_util::create_test_transaction(&payer);
+        tx.message.recent_blockhash = Hash::default();
+
+        assert_eq!(
+            TransactionUtil::validate_for_signing(&tx, &payer),
+            Err(TransactionValidationError::ZeroBlockhash)
+        );
+    }
+
+    #[test]
+    fn test_validate_for_signing_rejects_oversized_transaction() {
+        let payer = Pubkey::new_unique();
+        let instructions: Vec<_> = (0..40)
+            .map(|_| create_transfer_instruction(&payer, &Pubkey::new_unique(), 1))
+            .collect();
+        let mut message = Message::new(&instructions, Some(&payer));
+        message.recent_blockhash = Hash::new_from_array([1u8; 32]);
+        let tx = Transaction::new_unsigned(message);
+
+        assert!(matches!(
+            TransactionUtil::validate_for_signing(&tx, &payer),
+            Err(TransactionValidationError::TooLarge {
+                max: PACKET_DATA_SIZE,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_validate_for_signing_rejects_duplicate_keys() {
+        let payer = Pubkey::new_unique();
+        let mut tx = crate::test_util::create_test_transaction(&payer);
+        let duplicate = tx.message.account_keys[1];
+        tx.message.account_keys.push(duplicate);
+
+        assert_eq!(
+            TransactionUtil::validate_for_signing(&tx, &payer),
+            Err(TransactionValidationError::DuplicateAccountKey(
+                duplicate.to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_for_signing_rejects_non_signer() {
+        let payer = Pubkey::new_unique();
+        let tx = crate::test_util::create_test_transaction(&payer);
+        let recipient = tx.message.account_keys[1];
+
+        assert_eq!(
+            TransactionUtil::validate_for_signing(&tx, &recipient),
+            Err(TransactionValidationError::SignerNotRequired(
+                recipient.to_string()
+            ))
+        );
+        assert!(matches!(
+            TransactionUtil::validate_for_signing(&tx, &Pubkey::new_unique()),
+            Err(TransactionValidationError::SignerNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_is_transaction_message_for() {
+        let payer = Pubkey::new_unique();
+        let tx = valid_transaction(&payer);
+        let legacy = tx.message_data();
+        let v0 = VersionedMessage::V0(crate::sdk_adapter::v0::Message {
+            header: tx.message.header,
+            account_keys: tx.message.account_keys.clone(),
+            recent_blockhash: tx.message.recent_blockhash,
+            instructions: tx.message.instructions.clone(),
+            address_table_lookups: vec![],
+        })
+        .serialize();
+
+        assert!(TransactionUtil::is_transaction_message_for(&legacy, &payer));
+        assert!(TransactionUtil::is_transaction_message_for(&v0, &payer));
+
+        // Listed only as a non-signer
+        let recipient = tx.message.account_keys[1];
+        assert!(!TransactionUtil::is_transaction_message_for(
+            &legacy, &recipient
+        ));
+
+        // Trailing bytes make it something other than a transaction message
+        let mut extended = legacy.clone();
+        extended.push(0);
+        assert!(!TransactionUtil::is_transaction_message_for(
+            &extended, &payer
+        ));
+
+        assert!(!TransactionUtil::is_transaction_message_for(
+            b"Hello Trezoa!",
+            &payer
+        ));
+    }
+
+    #[test]
+    fn test_merge_partially_signed_rejects_empty_input() {
+        assert!(matches!(
+            TransactionUtil::merge_partially_signed(&[]),
+            Err(SignerError::ConfigError(_))
+        ));
+    }
 }
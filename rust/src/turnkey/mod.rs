@@ -2,24 +2,49 @@
 
 mod types;
 
-use crate::sdk_adapter::{Pubkey, Signature, Transaction};
+use crate::sdk_adapter::{Pubkey, Signature, Transaction, VersionedTransaction};
 pub use crate::traits::SignedTransaction;
 use crate::{error::SignerError, traits::TrezoaSigner, transaction_util::TransactionUtil};
 use base64::Engine;
 use p256::ecdsa::signature::Signer as P256Signer;
 use std::str::FromStr;
-use types::{ActivityResponse, SignParameters, SignRequest, WhoAmIRequest};
+use types::{
+    ActivityResponse, GetPrivateKeyRequest, GetPrivateKeyResponse, GetWalletAccountRequest,
+    GetWalletAccountResponse, SignParameters, SignRequest, WhoAmIRequest,
+};
+
+const DEFAULT_API_BASE_URL: &str = "https://api.turnkey.com";
+
+/// The Turnkey key a [`TurnkeySigner`] signs with
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TurnkeyKey {
+    /// A standalone private key, by ID
+    PrivateKey(String),
+    /// An account of an HD wallet, by wallet ID and Trezoa address (recommended by Turnkey)
+    WalletAccount { wallet_id: String, address: String },
+}
+
+impl TurnkeyKey {
+    /// The `signWith` value identifying this key
+    fn sign_with(&self) -> &str {
+        match self {
+            Self::PrivateKey(private_key_id) => private_key_id,
+            Self::WalletAccount { address, .. } => address,
+        }
+    }
+}
 
 /// Turnkey-based signer using Turnkey's API
 #[derive(Clone)]
 pub struct TurnkeySigner {
     organization_id: String,
-    private_key_id: String,
+    sign_with: String,
     api_public_key: String,
     api_private_key: String,
     public_key: Pubkey,
     api_base_url: String,
     client: reqwest::Client,
+    validate_transactions: bool,
 }
 
 impl std::fmt::Debug for TurnkeySigner {
@@ -33,32 +58,147 @@ impl std::fmt::Debug for TurnkeySigner {
 impl TurnkeySigner {
     /// Create a new TurnkeySigner
     ///
+    /// Use [`TurnkeySigner::connect`] to look up the public key instead of supplying it.
+    ///
     /// # Arguments
     ///
     /// * `api_public_key` - Turnkey API public key
     /// * `api_private_key` - Turnkey API private key (hex-encoded)
     /// * `organization_id` - Turnkey organization ID
-    /// * `private_key_id` - Turnkey private key ID
+    /// * `sign_with` - Turnkey private key ID or wallet account address
     /// * `public_key` - Trezoa public key (base58-encoded)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `sign_with` is a Trezoa address other than `public_key`.
     pub fn new(
         api_public_key: String,
         api_private_key: String,
         organization_id: String,
-        private_key_id: String,
+        sign_with: String,
         public_key: String,
     ) -> Result<Self, SignerError> {
         let pubkey = Pubkey::from_str(&public_key)
             .map_err(|e| SignerError::InvalidPublicKey(format!("Invalid public key: {e}")))?;
 
-        Ok(Self {
+        // Private key IDs are UUIDs, so anything parsing as a pubkey is an account address
+        if let Ok(address) = Pubkey::from_str(&sign_with) {
+            Self::ensure_matches(Some(pubkey), address)?;
+        }
+
+        Ok(Self::with_base_url(
+            DEFAULT_API_BASE_URL.to_string(),
+            api_public_key,
+            api_private_key,
+            organization_id,
+            sign_with,
+            pubkey,
+        ))
+    }
+
+    /// Create a TurnkeySigner, resolving the public key of `key` from Turnkey
+    ///
+    /// Queries `get_private_key` or `get_wallet_account`, which also checks that the API
+    /// credentials can read the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is not an Ed25519 key, or if `public_key` is set and
+    /// does not match it.
+    pub async fn connect(
+        api_public_key: String,
+        api_private_key: String,
+        organization_id: String,
+        key: TurnkeyKey,
+        public_key: Option<String>,
+    ) -> Result<Self, SignerError> {
+        let mut signer = Self::with_base_url(
+            DEFAULT_API_BASE_URL.to_string(),
             api_public_key,
             api_private_key,
             organization_id,
-            private_key_id,
-            public_key: pubkey,
-            api_base_url: "https://api.turnkey.com".to_string(),
+            key.sign_with().to_string(),
+            Pubkey::default(),
+        );
+        signer.resolve_public_key(&key, public_key).await?;
+        Ok(signer)
+    }
+
+    fn with_base_url(
+        api_base_url: String,
+        api_public_key: String,
+        api_private_key: String,
+        organization_id: String,
+        sign_with: String,
+        public_key: Pubkey,
+    ) -> Self {
+        Self {
+            api_public_key,
+            api_private_key,
+            organization_id,
+            sign_with,
+            public_key,
+            api_base_url,
             client: reqwest::Client::new(),
-        })
+            validate_transactions: true,
+        }
+    }
+
+    /// Look up the public key of `key` and check it against `expected`
+    async fn resolve_public_key(
+        &mut self,
+        key: &TurnkeyKey,
+        expected: Option<String>,
+    ) -> Result<(), SignerError> {
+        let expected = expected
+            .map(|public_key| {
+                Pubkey::from_str(&public_key)
+                    .map_err(|e| SignerError::InvalidPublicKey(format!("Invalid public key: {e}")))
+            })
+            .transpose()?;
+
+        let pubkey = match key {
+            TurnkeyKey::PrivateKey(private_key_id) => {
+                let request = GetPrivateKeyRequest {
+                    organization_id: self.organization_id.clone(),
+                    private_key_id: private_key_id.clone(),
+                };
+                let body = self.post("query/get_private_key", &request).await?;
+                GetPrivateKeyResponse::parse(&body)?.private_key.pubkey()?
+            }
+            TurnkeyKey::WalletAccount { wallet_id, address } => {
+                let request = GetWalletAccountRequest {
+                    organization_id: self.organization_id.clone(),
+                    wallet_id: wallet_id.clone(),
+                    address: address.clone(),
+                };
+                let body = self.post("query/get_wallet_account", &request).await?;
+                let pubkey = GetWalletAccountResponse::parse(&body)?.account.pubkey()?;
+                if let Ok(address) = Pubkey::from_str(address) {
+                    Self::ensure_matches(Some(address), pubkey)?;
+                }
+                pubkey
+            }
+        };
+
+        Self::ensure_matches(expected, pubkey)?;
+        self.public_key = pubkey;
+        Ok(())
+    }
+
+    fn ensure_matches(expected: Option<Pubkey>, pubkey: Pubkey) -> Result<(), SignerError> {
+        match expected {
+            Some(expected) if expected != pubkey => Err(SignerError::InvalidPublicKey(format!(
+                "Configured public key {expected} does not match Turnkey key {pubkey}"
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Enable or disable [`TransactionUtil::validate_for_signing`] (enabled by default)
+    pub fn with_transaction_validation(mut self, enabled: bool) -> Self {
+        self.validate_transactions = enabled;
+        self
     }
 
     /// Sign message bytes using Turnkey API and return just the signature
@@ -70,17 +210,27 @@ impl TurnkeySigner {
             timestamp_ms: chrono::Utc::now().timestamp_millis().to_string(),
             organization_id: self.organization_id.clone(),
             parameters: SignParameters {
-                sign_with: self.private_key_id.clone(),
+                sign_with: self.sign_with.clone(),
                 payload: hex_message,
                 encoding: "PAYLOAD_ENCODING_HEXADECIMAL".to_string(),
                 hash_function: "HASH_FUNCTION_NOT_APPLICABLE".to_string(),
             },
         };
 
-        let body = serde_json::to_string(&request)?;
+        let body = self.post("submit/sign_raw_payload", &request).await?;
+        Ok(ActivityResponse::parse(&body)?.signature()?)
+    }
+
+    /// POST a stamped request to `/public/v1/{endpoint}` and return the response body
+    async fn post(
+        &self,
+        endpoint: &str,
+        request: &impl serde::Serialize,
+    ) -> Result<String, SignerError> {
+        let body = serde_json::to_string(request)?;
         let stamp = self.create_stamp(&body)?;
 
-        let url = format!("{}/public/v1/submit/sign_raw_payload", self.api_base_url);
+        let url = format!("{}/public/v1/{endpoint}", self.api_base_url);
         let response = self
             .client
             .post(&url)
@@ -98,64 +248,25 @@ impl TurnkeySigner {
                 .unwrap_or_else(|_| "Failed to read error response".to_string());
 
             #[cfg(feature = "unsafe-debug")]
-            log::error!("Turnkey API error - status: {status}, response: {_error_text}");
+            log::error!("Turnkey API {endpoint} error - status: {status}, response: {_error_text}");
 
             #[cfg(not(feature = "unsafe-debug"))]
-            log::error!("Turnkey API error - status: {status}");
+            log::error!("Turnkey API {endpoint} error - status: {status}");
 
             return Err(SignerError::RemoteApiError(format!("API error {status}")));
         }
 
-        let response_text = response.text().await?;
-        let response: ActivityResponse = serde_json::from_str(&response_text)?;
-
-        if let Some(result) = response.activity.result {
-            if let Some(sign_result) = result.sign_raw_payload_result {
-                // Decode r and s components
-                let r_bytes = hex::decode(&sign_result.r).map_err(|e| {
-                    SignerError::SerializationError(format!("Failed to decode r: {e}"))
-                })?;
-                let s_bytes = hex::decode(&sign_result.s).map_err(|e| {
-                    SignerError::SerializationError(format!("Failed to decode s: {e}"))
-                })?;
-
-                // Ensure each component is exactly 32 bytes
-                if r_bytes.len() > 32 || s_bytes.len() > 32 {
-                    return Err(SignerError::SigningFailed(
-                        "Invalid signature component length".to_string(),
-                    ));
-                }
-
-                // Create properly padded 32-byte arrays
-                let mut final_r = [0u8; 32];
-                let mut final_s = [0u8; 32];
-
-                // Copy bytes with proper padding (right-aligned)
-                final_r[32 - r_bytes.len()..].copy_from_slice(&r_bytes);
-                final_s[32 - s_bytes.len()..].copy_from_slice(&s_bytes);
-
-                // Combine r and s into final 64-byte signature
-                let mut signature = Vec::with_capacity(64);
-                signature.extend_from_slice(&final_r);
-                signature.extend_from_slice(&final_s);
-
-                let sig_bytes: [u8; 64] = signature.try_into().map_err(|_| {
-                    SignerError::SigningFailed("Invalid signature length".to_string())
-                })?;
-
-                return Ok(Signature::from(sig_bytes));
-            }
-        }
-
-        Err(SignerError::SigningFailed(
-            "Invalid response from Turnkey API".to_string(),
-        ))
+        Ok(response.text().await?)
     }
 
     async fn sign_and_serialize(
         &self,
         transaction: &mut Transaction,
     ) -> Result<SignedTransaction, SignerError> {
+        if self.validate_transactions {
+            TransactionUtil::validate_for_signing(transaction, &self.public_key)?;
+        }
+
         let signature = self.sign_bytes(&transaction.message_data()).await?;
 
         TransactionUtil::add_signature_to_transaction(transaction, &self.public_key, signature)?;
@@ -166,6 +277,28 @@ impl TurnkeySigner {
         ))
     }
 
+    async fn sign_versioned_and_serialize(
+        &self,
+        transaction: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        if self.validate_transactions {
+            TransactionUtil::validate_versioned_for_signing(transaction, &self.public_key)?;
+        }
+
+        let signature = self.sign_bytes(&transaction.message.serialize()).await?;
+
+        TransactionUtil::add_signature_to_versioned_transaction(
+            transaction,
+            &self.public_key,
+            signature,
+        )?;
+
+        Ok((
+            TransactionUtil::serialize_versioned_transaction(transaction)?,
+            signature,
+        ))
+    }
+
     /// Create X-Stamp header for Turnkey API authentication
     fn create_stamp(&self, message: &str) -> Result<String, SignerError> {
         let private_key_bytes = hex::decode(&self.api_private_key).map_err(|e| {
@@ -241,6 +374,11 @@ impl TrezoaSigner for TurnkeySigner {
     }
 
     async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        TransactionUtil::ensure_not_transaction_message(message, &self.pubkey())?;
+        self.sign_bytes(message).await
+    }
+
+    async fn sign_message_unchecked(&self, message: &[u8]) -> Result<Signature, SignerError> {
         self.sign_bytes(message).await
     }
 
@@ -251,6 +389,13 @@ impl TrezoaSigner for TurnkeySigner {
         self.sign_and_serialize(tx).await
     }
 
+    async fn sign_versioned_transaction(
+        &self,
+        tx: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        self.sign_versioned_and_serialize(tx).await
+    }
+
     async fn is_available(&self) -> bool {
         // Verify Turnkey API is reachable and credentials are valid
         self.check_availability().await
@@ -263,7 +408,7 @@ mod tests {
     use crate::sdk_adapter::{keypair_pubkey, Keypair, Signer};
     use crate::test_util::create_test_transaction;
     use wiremock::{
-        matchers::{header, method, path},
+        matchers::{body_partial_json, header, method, path},
         Mock, MockServer, ResponseTemplate,
     };
 
@@ -296,7 +441,7 @@ mod tests {
         assert!(signer.is_ok());
         let signer = signer.unwrap();
         assert_eq!(signer.organization_id, "test-org-id");
-        assert_eq!(signer.private_key_id, "test-key-id");
+        assert_eq!(signer.sign_with, "test-key-id");
         assert_eq!(signer.public_key, keypair.pubkey());
     }
 
@@ -499,8 +644,12 @@ mod tests {
         signer.api_base_url = mock_server.uri();
 
         let result = signer.sign_message(b"test").await;
-        assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), SignerError::SigningFailed(_)));
+        assert!(matches!(
+            result,
+            Err(SignerError::InvalidResponse(
+                crate::error::ResponseError::MissingField(_)
+            ))
+        ));
     }
 
     #[tokio::test]
@@ -537,10 +686,11 @@ mod tests {
         signer.api_base_url = mock_server.uri();
 
         let result = signer.sign_message(b"test").await;
-        assert!(result.is_err());
         assert!(matches!(
-            result.unwrap_err(),
-            SignerError::SerializationError(_)
+            result,
+            Err(SignerError::InvalidResponse(
+                crate::error::ResponseError::InvalidEncoding { field: "r", .. }
+            ))
         ));
     }
 
@@ -671,7 +821,170 @@ mod tests {
         signer.api_base_url = mock_server.uri();
 
         let result = signer.sign_message(b"test").await;
-        assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), SignerError::SigningFailed(_)));
+        assert!(matches!(
+            result,
+            Err(SignerError::InvalidResponse(
+                crate::error::ResponseError::InvalidLength { field: "r", .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_turnkey_new_account_address_mismatch() {
+        let keypair = create_test_keypair();
+        let (api_public_key, api_private_key) = create_test_api_keys();
+
+        let signer = TurnkeySigner::new(
+            api_public_key.clone(),
+            api_private_key.clone(),
+            "test-org-id".to_string(),
+            keypair.pubkey().to_string(),
+            keypair.pubkey().to_string(),
+        );
+        assert!(signer.is_ok());
+
+        let result = TurnkeySigner::new(
+            api_public_key,
+            api_private_key,
+            "test-org-id".to_string(),
+            Keypair::new().pubkey().to_string(),
+            keypair.pubkey().to_string(),
+        );
+        assert!(matches!(result, Err(SignerError::InvalidPublicKey(_))));
+    }
+
+    fn unresolved_signer(api_base_url: String, key: &TurnkeyKey) -> TurnkeySigner {
+        let (api_public_key, api_private_key) = create_test_api_keys();
+        TurnkeySigner::with_base_url(
+            api_base_url,
+            api_public_key,
+            api_private_key,
+            "test-org-id".to_string(),
+            key.sign_with().to_string(),
+            Pubkey::default(),
+        )
+    }
+
+    async fn mock_private_key(server: &MockServer, curve: &str, public_key: &Pubkey) {
+        Mock::given(method("POST"))
+            .and(path("/public/v1/query/get_private_key"))
+            .and(body_partial_json(serde_json::json!({
+                "organizationId": "test-org-id",
+                "privateKeyId": "test-key-id"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "privateKey": {
+                    "privateKeyId": "test-key-id",
+                    "curve": curve,
+                    "publicKey": hex::encode(public_key),
+                    "addresses": [{ "format": "ADDRESS_FORMAT_SOLANA", "address": public_key.to_string() }]
+                }
+            })))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_turnkey_resolve_private_key() {
+        let mock_server = MockServer::start().await;
+        let keypair = create_test_keypair();
+        mock_private_key(&mock_server, "CURVE_ED25519", &keypair.pubkey()).await;
+
+        let key = TurnkeyKey::PrivateKey("test-key-id".to_string());
+        let mut signer = unresolved_signer(mock_server.uri(), &key);
+        signer.resolve_public_key(&key, None).await.unwrap();
+        assert_eq!(signer.pubkey(), keypair.pubkey());
+
+        // A matching configured public key is accepted
+        let mut signer = unresolved_signer(mock_server.uri(), &key);
+        signer
+            .resolve_public_key(&key, Some(keypair.pubkey().to_string()))
+            .await
+            .unwrap();
+        assert_eq!(signer.pubkey(), keypair.pubkey());
+    }
+
+    #[tokio::test]
+    async fn test_turnkey_resolve_mismatch() {
+        let mock_server = MockServer::start().await;
+        let keypair = create_test_keypair();
+        mock_private_key(&mock_server, "CURVE_ED25519", &keypair.pubkey()).await;
+
+        let key = TurnkeyKey::PrivateKey("test-key-id".to_string());
+        let mut signer = unresolved_signer(mock_server.uri(), &key);
+        let result = signer
+            .resolve_public_key(&key, Some(Keypair::new().pubkey().to_string()))
+            .await;
+        assert!(matches!(result, Err(SignerError::InvalidPublicKey(_))));
+    }
+
+    #[tokio::test]
+    async fn test_turnkey_resolve_wrong_curve() {
+        let mock_server = MockServer::start().await;
+        mock_private_key(&mock_server, "CURVE_SECP256K1", &Pubkey::new_unique()).await;
+
+        let key = TurnkeyKey::PrivateKey("test-key-id".to_string());
+        let mut signer = unresolved_signer(mock_server.uri(), &key);
+        let result = signer.resolve_public_key(&key, None).await;
+        assert!(matches!(result, Err(SignerError::ConfigError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_turnkey_wallet_account() {
+        let mock_server = MockServer::start().await;
+        let keypair = create_test_keypair();
+        let address = keypair.pubkey().to_string();
+
+        Mock::given(method("POST"))
+            .and(path("/public/v1/query/get_wallet_account"))
+            .and(body_partial_json(serde_json::json!({
+                "organizationId": "test-org-id",
+                "walletId": "test-wallet-id",
+                "address": address
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "account": {
+                    "walletId": "test-wallet-id",
+                    "address": address,
+                    "addressFormat": "ADDRESS_FORMAT_SOLANA",
+                    "curve": "CURVE_ED25519",
+                    "path": "m/44'/501'/0'/0'",
+                    "publicKey": hex::encode(keypair.pubkey())
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let message = b"test message";
+        let signature = keypair.sign_message(message);
+        Mock::given(method("POST"))
+            .and(path("/public/v1/submit/sign_raw_payload"))
+            .and(body_partial_json(serde_json::json!({
+                "parameters": { "signWith": address }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "activity": {
+                    "result": {
+                        "signRawPayloadResult": {
+                            "r": hex::encode(&signature.as_ref()[..32]),
+                            "s": hex::encode(&signature.as_ref()[32..])
+                        }
+                    }
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let key = TurnkeyKey::WalletAccount {
+            wallet_id: "test-wallet-id".to_string(),
+            address: address.clone(),
+        };
+        let mut signer = unresolved_signer(mock_server.uri(), &key);
+        signer.resolve_public_key(&key, None).await.unwrap();
+
+        assert_eq!(signer.pubkey(), keypair.pubkey());
+        assert_eq!(signer.sign_message(message).await.unwrap(), signature);
     }
 }
@@ -1,5 +1,8 @@
 //! Turnkey API types
 
+use crate::error::{ResponseError, SignerError};
+use crate::response;
+use crate::sdk_adapter::{Pubkey, Signature};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize)]
@@ -46,8 +49,176 @@ pub struct SignResult {
     pub s: String,
 }
 
+impl ActivityResponse {
+    pub fn parse(body: &str) -> Result<Self, ResponseError> {
+        response::parse_json(body, "Turnkey activity response")
+    }
+
+    /// Ed25519 signature from a completed sign_raw_payload activity
+    pub fn signature(&self) -> Result<Signature, ResponseError> {
+        let result = self
+            .activity
+            .result
+            .as_ref()
+            .and_then(|result| result.sign_raw_payload_result.as_ref())
+            .ok_or(ResponseError::MissingField("signRawPayloadResult"))?;
+
+        // r and s may omit leading zero bytes; left-pad each to 32 bytes
+        let mut signature = [0u8; 64];
+        for (half, (value, field)) in signature
+            .chunks_exact_mut(32)
+            .zip([(&result.r, "r"), (&result.s, "s")])
+        {
+            let bytes = response::decode_hex(value, field)?;
+            if bytes.len() > 32 {
+                return Err(ResponseError::InvalidLength {
+                    field,
+                    expected: 32,
+                    actual: bytes.len(),
+                });
+            }
+            half[32 - bytes.len()..].copy_from_slice(&bytes);
+        }
+
+        Ok(Signature::from(signature))
+    }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WhoAmIRequest {
     pub organization_id: String,
 }
+
+/// Curve of keys usable for Trezoa signing
+pub const ED25519_CURVE: &str = "CURVE_ED25519";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPrivateKeyRequest {
+    pub organization_id: String,
+    pub private_key_id: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetWalletAccountRequest {
+    pub organization_id: String,
+    pub wallet_id: String,
+    pub address: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPrivateKeyResponse {
+    pub private_key: KeyDetails,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetWalletAccountResponse {
+    pub account: KeyDetails,
+}
+
+/// Fields shared by private keys and wallet accounts
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyDetails {
+    pub curve: String,
+    pub public_key: String,
+}
+
+impl GetPrivateKeyResponse {
+    pub fn parse(body: &str) -> Result<Self, ResponseError> {
+        response::parse_json(body, "Turnkey get_private_key response")
+    }
+}
+
+impl GetWalletAccountResponse {
+    pub fn parse(body: &str) -> Result<Self, ResponseError> {
+        response::parse_json(body, "Turnkey get_wallet_account response")
+    }
+}
+
+impl KeyDetails {
+    /// Trezoa public key, if this is an Ed25519 key
+    pub fn pubkey(&self) -> Result<Pubkey, SignerError> {
+        if self.curve != ED25519_CURVE {
+            return Err(SignerError::ConfigError(format!(
+                "Turnkey key curve is {}, expected {ED25519_CURVE}",
+                self.curve
+            )));
+        }
+        let bytes = response::decode_hex(&self.public_key, "publicKey")?;
+        Ok(response::pubkey(bytes, "publicKey")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{arb_encoded_string, arb_json};
+    use proptest::prelude::*;
+    use serde_json::json;
+
+    const FIELDS: &[&str] = &[
+        "activity",
+        "result",
+        "signRawPayloadResult",
+        "r",
+        "s",
+        "privateKey",
+        "account",
+        "curve",
+        "publicKey",
+    ];
+
+    fn parse_all(body: &str) {
+        if let Ok(response) = ActivityResponse::parse(body) {
+            let _ = response.signature();
+        }
+        if let Ok(response) = GetPrivateKeyResponse::parse(body) {
+            let _ = response.private_key.pubkey();
+        }
+        if let Ok(response) = GetWalletAccountResponse::parse(body) {
+            let _ = response.account.pubkey();
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_parser_never_panics(value in arb_json(FIELDS), raw in ".*") {
+            parse_all(&value.to_string());
+            parse_all(&raw);
+        }
+
+        #[test]
+        fn test_field_decoding_never_panics(r in arb_encoded_string(), s in arb_encoded_string()) {
+            parse_all(&json!({
+                "activity": { "result": { "signRawPayloadResult": { "r": r, "s": s } } }
+            }).to_string());
+            parse_all(&json!({
+                "privateKey": { "curve": ED25519_CURVE, "publicKey": r }
+            }).to_string());
+            parse_all(&json!({
+                "account": { "curve": ED25519_CURVE, "publicKey": s }
+            }).to_string());
+        }
+    }
+
+    #[test]
+    fn test_short_components_are_left_padded() {
+        let response = ActivityResponse::parse(
+            &json!({
+                "activity": { "result": { "signRawPayloadResult": { "r": "01", "s": "02ff" } } }
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let signature = response.signature().unwrap();
+        let bytes: &[u8] = signature.as_ref();
+        assert_eq!(bytes[..32], [[0; 31].as_slice(), &[1]].concat());
+        assert_eq!(bytes[32..], [[0; 30].as_slice(), &[2, 0xff]].concat());
+    }
+}
@@ -0,0 +1,319 @@
+//! Vault authentication and token lifecycle
+
+use crate::error::SignerError;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// Service account token mounted into Kubernetes pods
+pub const DEFAULT_KUBERNETES_JWT_PATH: &str = "/var/run/secrets/kubernetes.io/serviceaccount/token";
+
+/// How [`VaultSigner`](super::VaultSigner) obtains its Vault token
+#[derive(Clone)]
+pub enum VaultAuth {
+    /// A fixed token, used as-is and never renewed
+    Token(String),
+    /// AppRole login with a role ID and secret ID
+    AppRole {
+        role_id: String,
+        secret_id: String,
+        mount: String,
+    },
+    /// Kubernetes login with the pod's service account token, re-read on every login
+    Kubernetes {
+        role: String,
+        jwt_path: PathBuf,
+        mount: String,
+    },
+    /// JWT/OIDC login with a fixed JWT
+    Jwt {
+        role: String,
+        jwt: String,
+        mount: String,
+    },
+}
+
+impl std::fmt::Debug for VaultAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Token(_) => f.debug_tuple("Token").finish_non_exhaustive(),
+            Self::AppRole { mount, .. } => f
+                .debug_struct("AppRole")
+                .field("mount", mount)
+                .finish_non_exhaustive(),
+            Self::Kubernetes {
+                role,
+                jwt_path,
+                mount,
+            } => f
+                .debug_struct("Kubernetes")
+                .field("role", role)
+                .field("jwt_path", jwt_path)
+                .field("mount", mount)
+                .finish(),
+            Self::Jwt { role, mount, .. } => f
+                .debug_struct("Jwt")
+                .field("role", role)
+                .field("mount", mount)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+impl VaultAuth {
+    pub fn token(token: impl Into<String>) -> Self {
+        Self::Token(token.into())
+    }
+
+    /// AppRole login at the default `approle` mount
+    pub fn app_role(role_id: impl Into<String>, secret_id: impl Into<String>) -> Self {
+        Self::AppRole {
+            role_id: role_id.into(),
+            secret_id: secret_id.into(),
+            mount: "approle".to_string(),
+        }
+    }
+
+    /// Kubernetes login at the default `kubernetes` mount, reading [`DEFAULT_KUBERNETES_JWT_PATH`]
+    pub fn kubernetes(role: impl Into<String>) -> Self {
+        Self::Kubernetes {
+            role: role.into(),
+            jwt_path: PathBuf::from(DEFAULT_KUBERNETES_JWT_PATH),
+            mount: "kubernetes".to_string(),
+        }
+    }
+
+    /// JWT login at the default `jwt` mount
+    pub fn jwt(role: impl Into<String>, jwt: impl Into<String>) -> Self {
+        Self::Jwt {
+            role: role.into(),
+            jwt: jwt.into(),
+            mount: "jwt".to_string(),
+        }
+    }
+
+    /// Use a non-default auth mount path (ignored for static tokens)
+    pub fn with_mount(mut self, mount_path: impl Into<String>) -> Self {
+        match &mut self {
+            Self::Token(_) => {}
+            Self::AppRole { mount, .. }
+            | Self::Kubernetes { mount, .. }
+            | Self::Jwt { mount, .. } => *mount = mount_path.into(),
+        }
+        self
+    }
+
+    fn can_login(&self) -> bool {
+        !matches!(self, Self::Token(_))
+    }
+}
+
+#[derive(Deserialize)]
+struct AuthResponse {
+    auth: AuthData,
+}
+
+#[derive(Deserialize)]
+struct AuthData {
+    client_token: String,
+    #[serde(default)]
+    lease_duration: u64,
+    #[serde(default)]
+    renewable: bool,
+}
+
+struct TokenState {
+    token: String,
+    /// When to renew or log in again; `None` for tokens that never expire
+    refresh_at: Option<Instant>,
+    renewable: bool,
+    /// Lease granted at login, to notice when renewals hit the token's max TTL
+    login_lease: Duration,
+}
+
+/// Point in a lease at which the token is refreshed: two thirds of the way through
+fn refresh_at(lease: Duration) -> Option<Instant> {
+    (!lease.is_zero()).then(|| Instant::now() + lease * 2 / 3)
+}
+
+/// Shared token source for a signer and all its clones
+///
+/// The state lock is held while refreshing, so concurrent callers wait for a
+/// single login or renewal instead of starting their own.
+pub(super) struct TokenProvider {
+    client: Arc<Client>,
+    vault_addr: String,
+    namespace: Option<String>,
+    auth: VaultAuth,
+    state: Mutex<Option<TokenState>>,
+}
+
+impl TokenProvider {
+    pub(super) fn new(
+        client: Arc<Client>,
+        vault_addr: String,
+        namespace: Option<String>,
+        auth: VaultAuth,
+    ) -> Self {
+        let state = match &auth {
+            VaultAuth::Token(token) => Some(TokenState {
+                token: token.clone(),
+                refresh_at: None,
+                renewable: false,
+                login_lease: Duration::ZERO,
+            }),
+            _ => None,
+        };
+
+        Self {
+            client,
+            vault_addr,
+            namespace,
+            auth,
+            state: Mutex::new(state),
+        }
+    }
+
+    /// A valid token, logging in or renewing first if needed
+    pub(super) async fn token(&self) -> Result<String, SignerError> {
+        let mut state = self.state.lock().await;
+
+        let needs_refresh = match state.as_ref() {
+            None => true,
+            Some(current) => current
+                .refresh_at
+                .is_some_and(|refresh_at| Instant::now() >= refresh_at),
+        };
+
+        if needs_refresh {
+            let renewed = match state.as_ref() {
+                Some(current) if current.renewable => {
+                    match self.renew(&current.token, current.login_lease).await {
+                        Ok(renewed) => Some(renewed),
+                        Err(e) => {
+                            log::warn!("Vault token renewal failed, logging in again: {e}");
+                            None
+                        }
+                    }
+                }
+                _ => None,
+            };
+
+            *state = Some(match renewed {
+                Some(renewed) => renewed,
+                None => self.login().await?,
+            });
+        }
+
+        state
+            .as_ref()
+            .map(|current| current.token.clone())
+            .ok_or_else(|| SignerError::NotAvailable("No Vault token".to_string()))
+    }
+
+    /// Drop `token` after Vault refused it, so the next call logs in again
+    ///
+    /// # Returns
+    ///
+    /// Whether a retry can get a different token
+    pub(super) async fn invalidate(&self, token: &str) -> bool {
+        if !self.auth.can_login() {
+            return false;
+        }
+
+        let mut state = self.state.lock().await;
+        // Another caller may have replaced the token already
+        if state.as_ref().is_some_and(|current| current.token == token) {
+            *state = None;
+        }
+        true
+    }
+
+    async fn login(&self) -> Result<TokenState, SignerError> {
+        let (mount, body) = match &self.auth {
+            VaultAuth::Token(_) => {
+                return Err(SignerError::ConfigError(
+                    "Static Vault tokens cannot log in".to_string(),
+                ))
+            }
+            VaultAuth::AppRole {
+                role_id,
+                secret_id,
+                mount,
+            } => (mount, json!({ "role_id": role_id, "secret_id": secret_id })),
+            VaultAuth::Kubernetes {
+                role,
+                jwt_path,
+                mount,
+            } => {
+                let jwt = tokio::fs::read_to_string(jwt_path).await?;
+                (mount, json!({ "role": role, "jwt": jwt.trim() }))
+            }
+            VaultAuth::Jwt { role, jwt, mount } => (mount, json!({ "role": role, "jwt": jwt })),
+        };
+
+        let url = format!("{}/v1/auth/{}/login", self.vault_addr, mount);
+        let auth = self.post_auth(&url, None, &body).await?;
+        let lease = Duration::from_secs(auth.lease_duration);
+
+        log::debug!("Logged in to Vault via {mount}");
+
+        Ok(TokenState {
+            token: auth.client_token,
+            refresh_at: refresh_at(lease),
+            renewable: auth.renewable,
+            login_lease: lease,
+        })
+    }
+
+    async fn renew(&self, token: &str, login_lease: Duration) -> Result<TokenState, SignerError> {
+        let url = format!("{}/v1/auth/token/renew-self", self.vault_addr);
+        let auth = self.post_auth(&url, Some(token), &json!({})).await?;
+        let lease = Duration::from_secs(auth.lease_duration);
+
+        Ok(TokenState {
+            token: auth.client_token,
+            refresh_at: refresh_at(lease),
+            // Near the max TTL Vault grants ever shorter leases: log in next time instead
+            renewable: auth.renewable && lease >= login_lease / 3,
+            login_lease,
+        })
+    }
+
+    async fn post_auth(
+        &self,
+        url: &str,
+        token: Option<&str>,
+        body: &serde_json::Value,
+    ) -> Result<AuthData, SignerError> {
+        let mut request = self.client.post(url).json(body);
+        if let Some(token) = token {
+            request = request.header("X-Vault-Token", token);
+        }
+        if let Some(namespace) = &self.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            SignerError::RemoteApiError(format!("Failed to send request to Vault: {e}"))
+        })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            log::error!("Vault auth error - status: {status}");
+            return Err(SignerError::RemoteApiError(format!(
+                "Vault auth error {status}"
+            )));
+        }
+
+        let response: AuthResponse = response.json().await.map_err(|_| {
+            SignerError::SerializationError("Failed to parse Vault auth response".to_string())
+        })?;
+        Ok(response.auth)
+    }
+}
@@ -0,0 +1,128 @@
+//! Authenticated access to a Vault server, shared by signers and the key manager
+
+use super::auth::{TokenProvider, VaultAuth};
+use super::types::{TransitKey, TransitKeyResponse};
+use super::DEFAULT_TRANSIT_MOUNT;
+use crate::error::SignerError;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use reqwest::{Client, Method, Response, StatusCode, Url};
+use std::sync::Arc;
+
+pub(super) struct VaultClient {
+    http: Arc<Client>,
+    vault_addr: String,
+    namespace: Option<String>,
+    transit_mount: String,
+    auth: TokenProvider,
+}
+
+impl VaultClient {
+    pub(super) fn new(
+        vault_addr: String,
+        auth: VaultAuth,
+        namespace: Option<String>,
+        transit_mount: Option<&str>,
+    ) -> Self {
+        let http = Arc::new(Client::new());
+        let transit_mount = transit_mount
+            .map(|mount| mount.trim_matches('/'))
+            .filter(|mount| !mount.is_empty())
+            .unwrap_or(DEFAULT_TRANSIT_MOUNT)
+            .to_string();
+
+        Self {
+            auth: TokenProvider::new(http.clone(), vault_addr.clone(), namespace.clone(), auth),
+            http,
+            vault_addr,
+            namespace,
+            transit_mount,
+        }
+    }
+
+    /// URL of a path under the transit mount, e.g. `sign/my-key`
+    pub(super) fn transit_url(&self, path: &str) -> String {
+        format!("{}/v1/{}/{}", self.vault_addr, self.transit_mount, path)
+    }
+
+    /// Send a request with the current token, logging in again once if Vault refuses it
+    pub(super) async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<Response, SignerError> {
+        let send = |token: String| {
+            let mut request = self
+                .http
+                .request(method.clone(), url)
+                .header("X-Vault-Token", token);
+            if let Some(namespace) = &self.namespace {
+                request = request.header("X-Vault-Namespace", namespace);
+            }
+            if let Some(body) = body {
+                request = request.json(body);
+            }
+            async move {
+                request.send().await.map_err(|e| {
+                    SignerError::RemoteApiError(format!("Failed to send request to Vault: {e}"))
+                })
+            }
+        };
+
+        let token = self.auth.token().await?;
+        let response = send(token.clone()).await?;
+
+        if response.status() == StatusCode::FORBIDDEN && self.auth.invalidate(&token).await {
+            log::warn!("Vault refused the token, logging in again");
+            return send(self.auth.token().await?).await;
+        }
+
+        Ok(response)
+    }
+
+    /// Read a transit key's metadata
+    ///
+    /// For derived keys, `context` selects the public keys returned.
+    pub(super) async fn read_key(
+        &self,
+        key_name: &str,
+        context: Option<&[u8]>,
+    ) -> Result<TransitKey, SignerError> {
+        let mut url = self.transit_url(&format!("keys/{key_name}"));
+        if let Some(context) = context {
+            url = Url::parse_with_params(&url, [("context", STANDARD.encode(context))])
+                .map_err(|e| SignerError::ConfigError(format!("Invalid Vault address: {e}")))?
+                .to_string();
+        }
+
+        let response = error_for_status(self.send(Method::GET, &url, None).await?).await?;
+        let response_text = response.text().await?;
+
+        Ok(TransitKeyResponse::parse(&response_text)?.data)
+    }
+}
+
+/// Turn a non-success Vault response into an error, logging the status
+pub(super) async fn error_for_status(response: Response) -> Result<Response, SignerError> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+
+    let status = response.status();
+
+    let _error_text = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Unknown error".to_string());
+
+    #[cfg(feature = "unsafe-debug")]
+    log::error!("Vault API error - status: {status}, response: {_error_text}");
+
+    #[cfg(not(feature = "unsafe-debug"))]
+    log::error!("Vault API error - status: {status}");
+
+    Err(SignerError::RemoteApiError(format!(
+        "Vault API error {}",
+        status
+    )))
+}
@@ -0,0 +1,44 @@
+//! Key wrapping for transit BYOK import
+//!
+//! Vault expects `base64(RSA-OAEP(ephemeral AES key) || AES-KWP(ephemeral AES key, key))`,
+//! where the RSA key is the transit wrapping key and the wrapped key is PKCS#8 DER.
+
+use crate::error::SignerError;
+use aes_kw::KekAes256;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use rsa::pkcs8::DecodePublicKey;
+use rsa::rand_core::{OsRng, RngCore};
+use rsa::{Oaep, RsaPublicKey};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+/// PKCS#8 v1 prefix of an Ed25519 private key, followed by the 32-byte seed (RFC 8410)
+pub(super) const ED25519_PKCS8_PREFIX: [u8; 16] = [
+    0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x04, 0x22, 0x04, 0x20,
+];
+
+/// PKCS#8 DER encoding of an Ed25519 private key seed, zeroed on drop
+pub(super) fn ed25519_pkcs8(seed: &[u8; 32]) -> Zeroizing<Vec<u8>> {
+    let mut der = Zeroizing::new(Vec::with_capacity(ED25519_PKCS8_PREFIX.len() + seed.len()));
+    der.extend_from_slice(&ED25519_PKCS8_PREFIX);
+    der.extend_from_slice(seed);
+    der
+}
+
+/// Wrap `key` for `/keys/{name}/import` with the PEM-encoded transit wrapping key
+pub(super) fn wrap_key(wrapping_key_pem: &str, key: &[u8]) -> Result<String, SignerError> {
+    let wrapping_key = RsaPublicKey::from_public_key_pem(wrapping_key_pem.trim())
+        .map_err(|e| SignerError::SerializationError(format!("Invalid Vault wrapping key: {e}")))?;
+
+    let mut ephemeral_key = Zeroizing::new([0u8; 32]);
+    OsRng.fill_bytes(ephemeral_key.as_mut());
+
+    let wrapped_ephemeral_key = wrapping_key
+        .encrypt(&mut OsRng, Oaep::new::<Sha256>(), ephemeral_key.as_ref())
+        .map_err(|e| SignerError::Other(format!("Failed to wrap ephemeral key: {e}")))?;
+    let wrapped_key = KekAes256::try_from(ephemeral_key.as_slice())
+        .and_then(|kek| kek.wrap_with_padding_vec(key))
+        .map_err(|e| SignerError::Other(format!("Failed to wrap key: {e}")))?;
+
+    Ok(STANDARD.encode([wrapped_ephemeral_key, wrapped_key].concat()))
+}
@@ -0,0 +1,408 @@
+//! Transit key lifecycle management
+
+use super::client::{error_for_status, VaultClient};
+use super::import::{ed25519_pkcs8, wrap_key};
+use super::types::{ListKeysResponse, WrappingKeyResponse};
+use super::{parse_pubkey, VaultAuth, VaultSigner};
+use crate::error::SignerError;
+use crate::sdk_adapter::{Keypair, Signer};
+use crate::traits::TrezoaSigner;
+use reqwest::{Method, StatusCode};
+use serde::Serialize;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Key settings updated through `/keys/{name}/config`
+///
+/// Unset fields are left unchanged.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VaultKeyConfig {
+    /// Oldest key version allowed to sign
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_encryption_version: Option<u32>,
+    /// Oldest key version allowed to verify
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_decryption_version: Option<u32>,
+    /// Whether the key may be deleted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deletion_allowed: Option<bool>,
+}
+
+/// Message signed after an import to check that Vault holds the expected key
+const IMPORT_CHECK_MESSAGE: &[u8] = b"trezoa-keychain vault import check";
+
+/// Creates, rotates and configures transit keys
+///
+/// Signers returned by the manager share its HTTP client and Vault token.
+#[derive(Clone)]
+pub struct VaultKeyManager {
+    client: Arc<VaultClient>,
+}
+
+impl std::fmt::Debug for VaultKeyManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VaultKeyManager").finish_non_exhaustive()
+    }
+}
+
+impl VaultKeyManager {
+    /// Creates a key manager for the transit engine at the default mount
+    ///
+    /// # Arguments
+    ///
+    /// * `vault_addr` - Vault server address (e.g., "https://vault.example.com")
+    /// * `auth` - Static token or login method
+    pub fn new(vault_addr: String, auth: VaultAuth) -> Self {
+        Self::with_options(vault_addr, auth, None, None)
+    }
+
+    /// Creates a key manager with a Vault Enterprise namespace and/or a custom transit mount
+    pub fn with_options(
+        vault_addr: String,
+        auth: VaultAuth,
+        namespace: Option<String>,
+        transit_mount: Option<&str>,
+    ) -> Self {
+        Self {
+            client: Arc::new(VaultClient::new(vault_addr, auth, namespace, transit_mount)),
+        }
+    }
+
+    /// Create an Ed25519 transit key and return a signer for it
+    pub async fn create_key(&self, key_name: &str) -> Result<VaultSigner, SignerError> {
+        let url = self.client.transit_url(&format!("keys/{key_name}"));
+        let body = json!({ "type": "ed25519" });
+
+        error_for_status(self.client.send(Method::POST, &url, Some(&body)).await?).await?;
+
+        self.signer(key_name, None).await
+    }
+
+    /// Create an Ed25519 transit key with `derived: true`
+    ///
+    /// Each context passed to [`derived_signer`](Self::derived_signer) yields a
+    /// distinct Trezoa address from this one key.
+    pub async fn create_derived_key(&self, key_name: &str) -> Result<(), SignerError> {
+        let url = self.client.transit_url(&format!("keys/{key_name}"));
+        let body = json!({ "type": "ed25519", "derived": true });
+
+        error_for_status(self.client.send(Method::POST, &url, Some(&body)).await?).await?;
+        Ok(())
+    }
+
+    /// Import an existing keypair as a new transit key (BYOK)
+    ///
+    /// The key is wrapped with the engine's RSA wrapping key before upload, so it never
+    /// leaves this process in the clear. The returned signer's public key has been checked
+    /// against `keypair` with a test signature.
+    pub async fn import_key(
+        &self,
+        key_name: &str,
+        keypair: &Keypair,
+    ) -> Result<VaultSigner, SignerError> {
+        let wrapping_key = self.wrapping_key().await?;
+        let ciphertext = wrap_key(&wrapping_key, &ed25519_pkcs8(keypair.secret_bytes()))?;
+
+        let url = self.client.transit_url(&format!("keys/{key_name}/import"));
+        let body = json!({
+            "ciphertext": ciphertext,
+            "type": "ed25519",
+            "hash_function": "SHA256"
+        });
+        error_for_status(self.client.send(Method::POST, &url, Some(&body)).await?).await?;
+
+        let pubkey = keypair.pubkey();
+        let signer = VaultSigner::connect_with_client(
+            self.client.clone(),
+            key_name.to_string(),
+            None,
+            None,
+            Some(pubkey),
+        )
+        .await?;
+
+        let signature = signer.sign_message_unchecked(IMPORT_CHECK_MESSAGE).await?;
+        if !signature.verify(pubkey.as_ref(), IMPORT_CHECK_MESSAGE) {
+            return Err(SignerError::InvalidPublicKey(format!(
+                "Vault key {key_name} does not sign for {pubkey}"
+            )));
+        }
+
+        Ok(signer)
+    }
+
+    /// Import a private key given as base58, a `[u8]` array or a keypair file path,
+    /// as accepted by [`MemorySigner::from_private_key_string`](crate::MemorySigner::from_private_key_string)
+    #[cfg(feature = "memory")]
+    pub async fn import_private_key(
+        &self,
+        key_name: &str,
+        private_key: &str,
+    ) -> Result<VaultSigner, SignerError> {
+        let keypair =
+            crate::memory::keypair_util::KeypairUtil::from_private_key_string(private_key)?;
+        self.import_key(key_name, &keypair).await
+    }
+
+    /// PEM-encoded RSA key that imported keys are wrapped with
+    async fn wrapping_key(&self) -> Result<String, SignerError> {
+        let url = self.client.transit_url("wrapping_key");
+
+        let response = error_for_status(self.client.send(Method::GET, &url, None).await?).await?;
+        let response_text = response.text().await?;
+
+        Ok(WrappingKeyResponse::parse(&response_text)?.data.public_key)
+    }
+
+    /// Names of all keys in the transit engine
+    pub async fn list_keys(&self) -> Result<Vec<String>, SignerError> {
+        let url = self.client.transit_url("keys");
+        let method = Method::from_bytes(b"LIST")
+            .map_err(|e| SignerError::ConfigError(format!("Invalid HTTP method: {e}")))?;
+
+        let response = self.client.send(method, &url, None).await?;
+        // Vault answers 404 when the engine has no keys
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(vec![]);
+        }
+
+        let response_text = error_for_status(response).await?.text().await?;
+        Ok(ListKeysResponse::parse(&response_text)?.data.keys)
+    }
+
+    /// Add a new version to a key
+    ///
+    /// # Returns
+    ///
+    /// The new latest version. Existing signers stay pinned to the version they were
+    /// created with; fetch a new one with [`signer`](Self::signer) to use the new version.
+    pub async fn rotate_key(&self, key_name: &str) -> Result<u32, SignerError> {
+        let url = self.client.transit_url(&format!("keys/{key_name}/rotate"));
+
+        error_for_status(self.client.send(Method::POST, &url, None).await?).await?;
+
+        Ok(self.client.read_key(key_name, None).await?.latest_version)
+    }
+
+    /// Update a key's version bounds and deletion flag
+    pub async fn configure_key(
+        &self,
+        key_name: &str,
+        config: &VaultKeyConfig,
+    ) -> Result<(), SignerError> {
+        let url = self.client.transit_url(&format!("keys/{key_name}/config"));
+        let body = serde_json::to_value(config)?;
+
+        error_for_status(self.client.send(Method::POST, &url, Some(&body)).await?).await?;
+        Ok(())
+    }
+
+    /// A signer for `key_name`, pinned to `key_version` or the current latest version
+    ///
+    /// The public key is read from Vault, as in [`VaultSigner::connect`].
+    pub async fn signer(
+        &self,
+        key_name: &str,
+        key_version: Option<u32>,
+    ) -> Result<VaultSigner, SignerError> {
+        VaultSigner::connect_with_client(
+            self.client.clone(),
+            key_name.to_string(),
+            key_version,
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// A signer for one context of a derived key
+    ///
+    /// The public key is the one Vault derives for `context`.
+    pub async fn derived_signer(
+        &self,
+        key_name: &str,
+        key_version: Option<u32>,
+        context: impl Into<Vec<u8>>,
+    ) -> Result<VaultSigner, SignerError> {
+        VaultSigner::connect_with_client(
+            self.client.clone(),
+            key_name.to_string(),
+            key_version,
+            Some(context.into()),
+            None,
+        )
+        .await
+    }
+
+    /// Like [`signer`](Self::signer), failing unless the key's public key is `pubkey`
+    pub async fn signer_with_pubkey(
+        &self,
+        key_name: &str,
+        key_version: Option<u32>,
+        pubkey: &str,
+    ) -> Result<VaultSigner, SignerError> {
+        VaultSigner::connect_with_client(
+            self.client.clone(),
+            key_name.to_string(),
+            key_version,
+            None,
+            Some(parse_pubkey(pubkey)?),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_server::FakeVault;
+
+    #[tokio::test]
+    async fn test_key_lifecycle() {
+        let vault = FakeVault::start().await;
+        let manager = VaultKeyManager::new(vault.uri(), VaultAuth::token(FakeVault::TOKEN));
+
+        assert!(manager.list_keys().await.unwrap().is_empty());
+
+        let signer = manager.create_key("hot-wallet").await.unwrap();
+        let v1_pubkey = signer.pubkey();
+        let signature = signer.sign_message(b"hello").await.unwrap();
+        assert!(signature.verify(v1_pubkey.as_ref(), b"hello"));
+        assert_eq!(manager.list_keys().await.unwrap(), ["hot-wallet"]);
+
+        assert_eq!(manager.rotate_key("hot-wallet").await.unwrap(), 2);
+
+        // Existing signers keep the version they were created with
+        let signature = signer.sign_message(b"hello").await.unwrap();
+        assert!(signature.verify(v1_pubkey.as_ref(), b"hello"));
+        assert_eq!(signer.key_version(), Some(1));
+
+        let latest = manager.signer("hot-wallet", None).await.unwrap();
+        assert_ne!(latest.pubkey(), v1_pubkey);
+        let signature = latest.sign_message(b"hello").await.unwrap();
+        assert!(signature.verify(latest.pubkey().as_ref(), b"hello"));
+
+        let pinned = manager
+            .signer_with_pubkey("hot-wallet", Some(1), &v1_pubkey.to_string())
+            .await
+            .unwrap();
+        let signature = pinned.sign_message(b"hello").await.unwrap();
+        assert!(signature.verify(v1_pubkey.as_ref(), b"hello"));
+
+        // Version 1 can no longer sign once the minimum version moves past it
+        manager
+            .configure_key(
+                "hot-wallet",
+                &VaultKeyConfig {
+                    min_encryption_version: Some(2),
+                    deletion_allowed: Some(true),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert!(pinned.sign_message(b"hello").await.is_err());
+        assert!(latest.sign_message(b"hello").await.is_ok());
+        assert!(vault.key_config("hot-wallet").deletion_allowed);
+    }
+
+    #[tokio::test]
+    async fn test_signer_rejects_pubkey_mismatch() {
+        let vault = FakeVault::start().await;
+        let manager = VaultKeyManager::new(vault.uri(), VaultAuth::token(FakeVault::TOKEN));
+        manager.create_key("hot-wallet").await.unwrap();
+
+        let result = manager
+            .signer_with_pubkey(
+                "hot-wallet",
+                None,
+                "2vfDxWYbhRt7GXiRYKf1Dr5Z8y7zVQCSERbDTKyBaAqQ",
+            )
+            .await;
+
+        assert!(matches!(result, Err(SignerError::InvalidPublicKey(_))));
+    }
+
+    #[tokio::test]
+    async fn test_derived_key_contexts() {
+        let vault = FakeVault::start().await;
+        let manager = VaultKeyManager::new(vault.uri(), VaultAuth::token(FakeVault::TOKEN));
+        manager.create_derived_key("deposits").await.unwrap();
+        assert!(vault.key_config("deposits").derived);
+
+        let alice = manager
+            .derived_signer("deposits", None, b"user-1".to_vec())
+            .await
+            .unwrap();
+        let bob = alice.for_context(b"user-2".to_vec()).await.unwrap();
+        assert_ne!(alice.pubkey(), bob.pubkey());
+        assert_eq!(bob.context(), Some(&b"user-2"[..]));
+        assert_eq!(alice.derive_pubkey(b"user-2").await.unwrap(), bob.pubkey());
+
+        for signer in [&alice, &bob] {
+            let signature = signer.sign_message(b"hello").await.unwrap();
+            assert!(signature.verify(signer.pubkey().as_ref(), b"hello"));
+        }
+        assert!(bob.is_available().await);
+    }
+
+    #[tokio::test]
+    async fn test_derived_key_requires_context() {
+        let vault = FakeVault::start().await;
+        let manager = VaultKeyManager::new(vault.uri(), VaultAuth::token(FakeVault::TOKEN));
+        manager.create_derived_key("deposits").await.unwrap();
+        manager.create_key("hot-wallet").await.unwrap();
+
+        let result = manager.signer("deposits", None).await;
+        assert!(matches!(result, Err(SignerError::ConfigError(_))));
+
+        let result = manager
+            .derived_signer("hot-wallet", None, b"user-1".to_vec())
+            .await;
+        assert!(matches!(result, Err(SignerError::ConfigError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_import_key() {
+        let vault = FakeVault::start().await;
+        let manager = VaultKeyManager::new(vault.uri(), VaultAuth::token(FakeVault::TOKEN));
+        let keypair = Keypair::new();
+
+        let signer = manager.import_key("imported", &keypair).await.unwrap();
+        assert_eq!(signer.pubkey(), keypair.pubkey());
+
+        let signature = signer.sign_message(b"hello").await.unwrap();
+        assert_eq!(signature, keypair.sign_message(b"hello"));
+
+        // Importing over an existing key is refused
+        assert!(manager.import_key("imported", &keypair).await.is_err());
+    }
+
+    #[cfg(feature = "memory")]
+    #[tokio::test]
+    async fn test_import_private_key() {
+        let vault = FakeVault::start().await;
+        let manager = VaultKeyManager::new(vault.uri(), VaultAuth::token(FakeVault::TOKEN));
+        let keypair = Keypair::new();
+
+        let signer = manager
+            .import_private_key("imported", &keypair.to_base58_string())
+            .await
+            .unwrap();
+        assert_eq!(signer.pubkey(), keypair.pubkey());
+
+        assert!(manager
+            .import_private_key("other", "not-a-key")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_missing_key() {
+        let vault = FakeVault::start().await;
+        let manager = VaultKeyManager::new(vault.uri(), VaultAuth::token(FakeVault::TOKEN));
+
+        assert!(manager.signer("missing", None).await.is_err());
+        assert!(manager.rotate_key("missing").await.is_err());
+    }
+}
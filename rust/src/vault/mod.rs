@@ -1,21 +1,66 @@
 //! HashiCorp Vault signer integration
 
-use crate::sdk_adapter::{Pubkey, Signature, Transaction};
+mod auth;
+mod client;
+mod import;
+mod key_manager;
+#[cfg(test)]
+mod test_server;
+mod types;
+
+pub use auth::{VaultAuth, DEFAULT_KUBERNETES_JWT_PATH};
+pub use key_manager::{VaultKeyConfig, VaultKeyManager};
+
+use crate::sdk_adapter::{Pubkey, Signature, Transaction, VersionedTransaction};
 use crate::traits::SignedTransaction;
 use crate::{error::SignerError, traits::TrezoaSigner, transaction_util::TransactionUtil};
 use base64::{engine::general_purpose::STANDARD, Engine as _};
-use reqwest::Client;
+use client::{error_for_status, VaultClient};
+use reqwest::Method;
 use serde_json::json;
 use std::sync::Arc;
+use types::SignResponse;
+
+/// Transit secrets engine mount path used when none is configured
+pub const DEFAULT_TRANSIT_MOUNT: &str = "transit";
+
+/// Configuration for creating a VaultSigner
+#[derive(Debug, Clone)]
+pub struct VaultSignerConfig {
+    /// Vault server address (e.g., "https://vault.example.com")
+    pub vault_addr: String,
+    pub auth: VaultAuth,
+    /// Key name in the transit engine
+    pub key_name: String,
+    /// Base58-encoded public key
+    ///
+    /// Required by [`VaultSigner::from_config`]; optional for [`VaultSigner::connect`],
+    /// which then checks it against the transit key.
+    pub pubkey: Option<String>,
+    /// Vault Enterprise namespace, sent as `X-Vault-Namespace`
+    pub namespace: Option<String>,
+    /// Transit engine mount path (default: "transit")
+    pub transit_mount: Option<String>,
+    /// Key version to sign with
+    ///
+    /// [`VaultSigner::connect`] defaults to the latest version at connect time and pins
+    /// it. Without it, other constructors sign with whatever version is latest.
+    pub key_version: Option<u32>,
+    /// Key derivation context, required for keys created with `derived: true`
+    ///
+    /// Each context yields a distinct Ed25519 keypair from the same transit key.
+    pub context: Option<Vec<u8>>,
+}
 
 /// Vault-based signer using HashiCorp Vault transit engine
 #[derive(Clone)]
 pub struct VaultSigner {
-    client: Arc<Client>,
-    vault_addr: String,
-    token: String,
+    client: Arc<VaultClient>,
     key_name: String,
+    key_version: Option<u32>,
+    context: Option<Vec<u8>>,
     pubkey: Pubkey,
+    validate_transactions: bool,
 }
 
 impl std::fmt::Debug for VaultSigner {
@@ -41,92 +86,232 @@ impl VaultSigner {
         key_name: String,
         pubkey: String,
     ) -> Result<Self, SignerError> {
-        let client = Client::new();
-
-        let pubkey = Pubkey::try_from(
-            bs58::decode(pubkey)
-                .into_vec()
-                .map_err(|e| {
-                    SignerError::InvalidPublicKey(format!(
-                        "Failed to decode base58 public key: {e}"
-                    ))
-                })?
-                .as_slice(),
-        )
-        .map_err(|e| SignerError::InvalidPublicKey(format!("Invalid public key bytes: {e}")))?;
+        Self::new_with_auth(vault_addr, VaultAuth::Token(token), key_name, pubkey)
+    }
 
-        Ok(Self {
-            client: Arc::new(client),
+    /// Creates a new Vault signer that logs in with `auth`
+    ///
+    /// Tokens obtained by logging in are renewed before their lease runs out and
+    /// replaced by a fresh login if Vault revokes them. Clones of the signer share
+    /// one token, and only one login or renewal runs at a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `vault_addr` - Vault server address (e.g., "https://vault.example.com")
+    /// * `auth` - Static token or login method
+    /// * `key_name` - Vault key name in transit engine
+    /// * `pubkey` - Base58-encoded public key
+    pub fn new_with_auth(
+        vault_addr: String,
+        auth: VaultAuth,
+        key_name: String,
+        pubkey: String,
+    ) -> Result<Self, SignerError> {
+        Self::from_config(VaultSignerConfig {
             vault_addr,
-            token,
+            auth,
             key_name,
-            pubkey,
+            pubkey: Some(pubkey),
+            namespace: None,
+            transit_mount: None,
+            key_version: None,
+            context: None,
         })
     }
 
-    async fn sign_bytes(&self, serialized: &[u8]) -> Result<Signature, SignerError> {
-        let url = format!("{}/v1/transit/sign/{}", self.vault_addr, self.key_name);
+    /// Creates a new Vault signer from a full configuration
+    ///
+    /// `config.pubkey` is required and trusted as-is; use [`connect`](Self::connect)
+    /// to read it from Vault instead.
+    pub fn from_config(config: VaultSignerConfig) -> Result<Self, SignerError> {
+        let pubkey = config.pubkey.as_deref().ok_or_else(|| {
+            SignerError::ConfigError(
+                "A public key is required, or use VaultSigner::connect to read it from Vault"
+                    .to_string(),
+            )
+        })?;
+        let pubkey = parse_pubkey(pubkey)?;
 
-        let payload = json!({
-            "input": STANDARD.encode(serialized)
-        });
+        Ok(Self::with_client(
+            Arc::new(Self::client_for(&config)),
+            config.key_name,
+            config.key_version,
+            config.context,
+            pubkey,
+        ))
+    }
 
-        let response = self
-            .client
-            .post(&url)
-            .header("X-Vault-Token", &self.token)
-            .json(&payload)
-            .send()
-            .await
-            .map_err(|e| {
-                SignerError::RemoteApiError(format!("Failed to send request to Vault: {e}"))
-            })?;
+    /// Creates a new Vault signer, reading its public key from the transit key
+    ///
+    /// Uses the configured `key_version`, or pins the latest version. Fails if the key is
+    /// not an Ed25519 signing key, if `config.context` is missing for a derived key
+    /// (or set for a non-derived one), or if `config.pubkey` is set and does not match.
+    pub async fn connect(config: VaultSignerConfig) -> Result<Self, SignerError> {
+        let expected = config.pubkey.as_deref().map(parse_pubkey).transpose()?;
+        let client = Arc::new(Self::client_for(&config));
+
+        Self::connect_with_client(
+            client,
+            config.key_name,
+            config.key_version,
+            config.context,
+            expected,
+        )
+        .await
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
+    fn client_for(config: &VaultSignerConfig) -> VaultClient {
+        VaultClient::new(
+            config.vault_addr.clone(),
+            config.auth.clone(),
+            config.namespace.clone(),
+            config.transit_mount.as_deref(),
+        )
+    }
 
-            let _error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
+    fn with_client(
+        client: Arc<VaultClient>,
+        key_name: String,
+        key_version: Option<u32>,
+        context: Option<Vec<u8>>,
+        pubkey: Pubkey,
+    ) -> Self {
+        Self {
+            client,
+            key_name,
+            key_version,
+            context,
+            pubkey,
+            validate_transactions: true,
+        }
+    }
 
-            #[cfg(feature = "unsafe-debug")]
-            log::error!("Vault API error - status: {status}, response: {_error_text}");
+    async fn connect_with_client(
+        client: Arc<VaultClient>,
+        key_name: String,
+        key_version: Option<u32>,
+        context: Option<Vec<u8>>,
+        expected: Option<Pubkey>,
+    ) -> Result<Self, SignerError> {
+        let key = client.read_key(&key_name, context.as_deref()).await?;
+        key.ensure_signing_key()?;
+        key.ensure_context(context.as_deref())?;
+        // Pin the resolved version so a later rotation cannot change the signing key
+        let key_version = key_version.unwrap_or(key.latest_version);
+        let pubkey = key.pubkey(Some(key_version))?;
+
+        if let Some(expected) = expected {
+            if expected != pubkey {
+                return Err(SignerError::InvalidPublicKey(format!(
+                    "Configured public key {expected} does not match Vault key {pubkey}"
+                )));
+            }
+        }
 
-            #[cfg(not(feature = "unsafe-debug"))]
-            log::error!("Vault API error - status: {status}");
+        Ok(Self::with_client(
+            client,
+            key_name,
+            Some(key_version),
+            context,
+            pubkey,
+        ))
+    }
+
+    /// Public key that a derived transit key yields for `context`
+    ///
+    /// Uses this signer's key version, or the latest version.
+    pub async fn derive_pubkey(&self, context: &[u8]) -> Result<Pubkey, SignerError> {
+        let key = self.client.read_key(&self.key_name, Some(context)).await?;
+        key.ensure_context(Some(context))?;
+        key.pubkey(self.key_version)
+    }
+
+    /// A signer for another context of the same derived key
+    ///
+    /// The new signer shares this signer's client and token, so one signer per user
+    /// or deposit address costs a single key read.
+    pub async fn for_context(&self, context: impl Into<Vec<u8>>) -> Result<Self, SignerError> {
+        let context = context.into();
+        let pubkey = self.derive_pubkey(&context).await?;
+
+        Ok(Self {
+            context: Some(context),
+            pubkey,
+            ..self.clone()
+        })
+    }
+
+    /// Name of the transit key
+    pub fn key_name(&self) -> &str {
+        &self.key_name
+    }
+
+    /// Key version used for signing
+    ///
+    /// Always set for signers from [`connect`](Self::connect). `None` lets Vault pick the
+    /// latest version when signing, which only [`from_config`](Self::from_config) allows.
+    pub fn key_version(&self) -> Option<u32> {
+        self.key_version
+    }
+
+    /// Key derivation context, for derived keys
+    pub fn context(&self) -> Option<&[u8]> {
+        self.context.as_deref()
+    }
+
+    /// Enable or disable [`TransactionUtil::validate_for_signing`] (enabled by default)
+    pub fn with_transaction_validation(mut self, enabled: bool) -> Self {
+        self.validate_transactions = enabled;
+        self
+    }
+
+    async fn sign_bytes(&self, serialized: &[u8]) -> Result<Signature, SignerError> {
+        let url = self.client.transit_url(&format!("sign/{}", self.key_name));
 
-            return Err(SignerError::RemoteApiError(format!(
-                "Vault API error {}",
-                status
-            )));
+        let mut payload = json!({
+            "input": STANDARD.encode(serialized)
+        });
+        if let Some(key_version) = self.key_version {
+            payload["key_version"] = json!(key_version);
+        }
+        if let Some(context) = &self.context {
+            payload["context"] = json!(STANDARD.encode(context));
         }
 
-        let result: serde_json::Value = response.json().await.map_err(|_| {
-            SignerError::SerializationError("Failed to parse Vault response".to_string())
-        })?;
+        let response = self.client.send(Method::POST, &url, Some(&payload)).await?;
+        let response = error_for_status(response).await?;
 
-        let signature_b64 = result["data"]["signature"].as_str().ok_or_else(|| {
-            SignerError::RemoteApiError("No signature in Vault response".to_string())
-        })?;
+        let response_text = response.text().await?;
+        let response = SignResponse::parse(&response_text)?;
 
-        // Remove the version prefix (e.g., "vault:v1:") if present
-        let signature_b64 = signature_b64
-            .strip_prefix("vault:v1:")
-            .unwrap_or(signature_b64);
+        if let (Some(expected), Some(version)) = (self.key_version, response.key_version()) {
+            if version != expected {
+                return Err(SignerError::SigningFailed(format!(
+                    "Vault signed with key version {version} instead of the pinned version {expected}"
+                )));
+            }
+        }
 
-        let sig_bytes = STANDARD.decode(signature_b64).map_err(|_| {
-            SignerError::SerializationError("Failed to decode signature".to_string())
-        })?;
+        // Without a pinned version Vault signs with the latest one, which stops matching
+        // the configured public key once the key is rotated
+        let signature = response.signature()?;
+        if !signature.verify(self.pubkey.as_ref(), serialized) {
+            return Err(SignerError::SigningFailed(
+                "Vault signature does not verify against the signer public key".to_string(),
+            ));
+        }
 
-        Signature::try_from(sig_bytes.as_slice())
-            .map_err(|_| SignerError::SigningFailed("Invalid signature format".to_string()))
+        Ok(signature)
     }
 
     async fn sign_and_serialize(
         &self,
         transaction: &mut Transaction,
     ) -> Result<SignedTransaction, SignerError> {
+        if self.validate_transactions {
+            TransactionUtil::validate_for_signing(transaction, &self.pubkey)?;
+        }
+
         let signature = self.sign_bytes(&transaction.message_data()).await?;
 
         TransactionUtil::add_signature_to_transaction(transaction, &self.pubkey, signature)?;
@@ -136,6 +321,50 @@ impl VaultSigner {
             signature,
         ))
     }
+
+    async fn sign_versioned_and_serialize(
+        &self,
+        transaction: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        if self.validate_transactions {
+            TransactionUtil::validate_versioned_for_signing(transaction, &self.pubkey)?;
+        }
+
+        let signature = self.sign_bytes(&transaction.message.serialize()).await?;
+
+        TransactionUtil::add_signature_to_versioned_transaction(
+            transaction,
+            &self.pubkey,
+            signature,
+        )?;
+
+        Ok((
+            TransactionUtil::serialize_versioned_transaction(transaction)?,
+            signature,
+        ))
+    }
+}
+
+fn parse_pubkey(pubkey: &str) -> Result<Pubkey, SignerError> {
+    Pubkey::try_from(
+        bs58::decode(pubkey)
+            .into_vec()
+            .map_err(|e| {
+                SignerError::InvalidPublicKey(format!("Failed to decode base58 public key: {e}"))
+            })?
+            .as_slice(),
+    )
+    .map_err(|e| SignerError::InvalidPublicKey(format!("Invalid public key bytes: {e}")))
+}
+
+/// Split the key version prefix (e.g. `vault:v2:`) off a transit signature, if present
+fn split_version_prefix(signature: &str) -> (Option<u32>, &str) {
+    signature
+        .strip_prefix("vault:v")
+        .and_then(|rest| rest.split_once(':'))
+        .filter(|(version, _)| version.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|(version, signature)| Some((Some(version.parse().ok()?), signature)))
+        .unwrap_or((None, signature))
 }
 
 #[async_trait::async_trait]
@@ -152,6 +381,11 @@ impl TrezoaSigner for VaultSigner {
     }
 
     async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        TransactionUtil::ensure_not_transaction_message(message, &self.pubkey())?;
+        self.sign_bytes(message).await
+    }
+
+    async fn sign_message_unchecked(&self, message: &[u8]) -> Result<Signature, SignerError> {
         self.sign_bytes(message).await
     }
 
@@ -162,19 +396,19 @@ impl TrezoaSigner for VaultSigner {
         self.sign_and_serialize(tx).await
     }
 
-    async fn is_available(&self) -> bool {
-        // Check if we can read the key metadata as a health check
-        let url = format!("{}/v1/transit/keys/{}", self.vault_addr, self.key_name);
-
-        let response = self
-            .client
-            .get(&url)
-            .header("X-Vault-Token", &self.token)
-            .send()
-            .await;
+    async fn sign_versioned_transaction(
+        &self,
+        tx: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        self.sign_versioned_and_serialize(tx).await
+    }
 
-        match response {
-            Ok(resp) => resp.status().is_success(),
+    async fn is_available(&self) -> bool {
+        // Check that the key metadata is readable and the key can still sign
+        match self.client.read_key(&self.key_name, self.context()).await {
+            Ok(key) => {
+                key.ensure_signing_key().is_ok() && key.ensure_context(self.context()).is_ok()
+            }
             Err(_) => false,
         }
     }
@@ -183,12 +417,21 @@ impl TrezoaSigner for VaultSigner {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sdk_adapter::{keypair_pubkey, Keypair};
+    use crate::vault::test_server::SignWith;
+    use std::sync::OnceLock;
 
     const TEST_VAULT_ADDR: &str = "http://127.0.0.1:8200";
     const TEST_VAULT_TOKEN: &str = "test-token";
     const TEST_KEY_NAME: &str = "test-key";
     const TEST_PUBKEY: &str = "2vfDxWYbhRt7GXiRYKf1Dr5Z8y7zVQCSERbDTKyBaAqQ";
 
+    /// Keypair behind the mocked transit key in signing tests
+    fn test_keypair() -> &'static Keypair {
+        static KEYPAIR: OnceLock<Keypair> = OnceLock::new();
+        KEYPAIR.get_or_init(Keypair::new)
+    }
+
     fn create_test_signer() -> VaultSigner {
         VaultSigner::new(
             TEST_VAULT_ADDR.to_string(),
@@ -235,4 +478,434 @@ mod tests {
         assert!(debug_str.contains("VaultSigner"));
         assert!(debug_str.contains("pubkey"));
     }
+
+    #[tokio::test]
+    async fn test_sign_transaction_validates_before_signing() {
+        // Nothing listens on the Vault address, so reaching Vault would fail differently
+        let signer = create_test_signer();
+        let mut tx = crate::test_util::create_test_transaction(&signer.pubkey());
+        tx.message.recent_blockhash = crate::sdk_adapter::Hash::default();
+
+        let result = signer.sign_transaction(&mut tx).await;
+
+        assert!(matches!(
+            result,
+            Err(SignerError::InvalidTransaction(
+                crate::error::TransactionValidationError::ZeroBlockhash
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_split_version_prefix() {
+        assert_eq!(split_version_prefix("vault:v1:c2ln"), (Some(1), "c2ln"));
+        assert_eq!(split_version_prefix("vault:v12:c2ln"), (Some(12), "c2ln"));
+        assert_eq!(split_version_prefix("c2ln"), (None, "c2ln"));
+        assert_eq!(
+            split_version_prefix("vault:vx:c2ln"),
+            (None, "vault:vx:c2ln")
+        );
+        assert_eq!(split_version_prefix("vault:v:c2ln"), (None, "vault:v:c2ln"));
+    }
+
+    #[tokio::test]
+    async fn test_namespace_mount_and_key_version() {
+        use wiremock::{
+            matchers::{body_partial_json, header, method, path},
+            Mock, MockServer,
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(format!(
+                "/v1/secrets/trezoa-transit/sign/{TEST_KEY_NAME}"
+            )))
+            .and(header("X-Vault-Namespace", "treasury/ops"))
+            .and(header("X-Vault-Token", TEST_VAULT_TOKEN))
+            .and(body_partial_json(json!({ "key_version": 2 })))
+            .respond_with(SignWith::new(test_keypair(), 2))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let signer = VaultSigner::from_config(VaultSignerConfig {
+            vault_addr: server.uri(),
+            auth: VaultAuth::token(TEST_VAULT_TOKEN),
+            key_name: TEST_KEY_NAME.to_string(),
+            pubkey: Some(keypair_pubkey(test_keypair()).to_string()),
+            namespace: Some("treasury/ops".to_string()),
+            transit_mount: Some("/secrets/trezoa-transit/".to_string()),
+            key_version: Some(2),
+            context: None,
+        })
+        .unwrap();
+
+        let signature = signer.sign_message_unchecked(b"hello").await.unwrap();
+
+        assert!(signature.verify(signer.pubkey().as_ref(), b"hello"));
+    }
+
+    mod signature_checks {
+        use super::*;
+        use wiremock::{
+            matchers::{method, path},
+            Mock, MockServer,
+        };
+
+        async fn mock_sign(server: &MockServer, response: SignWith) {
+            Mock::given(method("POST"))
+                .and(path(format!("/v1/transit/sign/{TEST_KEY_NAME}")))
+                .respond_with(response)
+                .mount(server)
+                .await;
+        }
+
+        fn signer_config(server: &MockServer, key_version: Option<u32>) -> VaultSignerConfig {
+            VaultSignerConfig {
+                vault_addr: server.uri(),
+                auth: VaultAuth::token(TEST_VAULT_TOKEN),
+                key_name: TEST_KEY_NAME.to_string(),
+                pubkey: Some(keypair_pubkey(test_keypair()).to_string()),
+                namespace: None,
+                transit_mount: None,
+                key_version,
+                context: None,
+            }
+        }
+
+        #[tokio::test]
+        async fn test_rejects_signature_from_rotated_key() {
+            let server = MockServer::start().await;
+            mock_sign(&server, SignWith::new(&Keypair::new(), 2)).await;
+
+            let signer = VaultSigner::from_config(signer_config(&server, None)).unwrap();
+            let mut tx = crate::test_util::create_test_transaction(&signer.pubkey());
+
+            assert!(matches!(
+                signer.sign_message_unchecked(b"hello").await,
+                Err(SignerError::SigningFailed(_))
+            ));
+            assert!(matches!(
+                signer.sign_transaction(&mut tx).await,
+                Err(SignerError::SigningFailed(_))
+            ));
+            assert_eq!(tx.signatures[0], Signature::default());
+        }
+
+        #[tokio::test]
+        async fn test_rejects_unpinned_key_version() {
+            let server = MockServer::start().await;
+            mock_sign(&server, SignWith::new(test_keypair(), 3)).await;
+
+            let signer = VaultSigner::from_config(signer_config(&server, Some(2))).unwrap();
+
+            assert!(matches!(
+                signer.sign_message_unchecked(b"hello").await,
+                Err(SignerError::SigningFailed(_))
+            ));
+        }
+    }
+
+    mod connect {
+        use super::*;
+        use wiremock::{
+            matchers::{method, path},
+            Mock, MockServer, ResponseTemplate,
+        };
+
+        fn connect_config(server: &MockServer, pubkey: Option<String>) -> VaultSignerConfig {
+            VaultSignerConfig {
+                vault_addr: server.uri(),
+                auth: VaultAuth::token(TEST_VAULT_TOKEN),
+                key_name: TEST_KEY_NAME.to_string(),
+                pubkey,
+                namespace: None,
+                transit_mount: None,
+                key_version: None,
+                context: None,
+            }
+        }
+
+        async fn mock_key(
+            server: &MockServer,
+            key_type: &str,
+            supports_signing: bool,
+        ) -> [Pubkey; 2] {
+            let versions = [Pubkey::new_unique(), Pubkey::new_unique()];
+            Mock::given(method("GET"))
+                .and(path(format!("/v1/transit/keys/{TEST_KEY_NAME}")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "data": {
+                        "name": TEST_KEY_NAME,
+                        "type": key_type,
+                        "latest_version": 2,
+                        "supports_signing": supports_signing,
+                        "keys": {
+                            "1": { "public_key": STANDARD.encode(versions[0]), "name": "ed25519" },
+                            "2": { "public_key": STANDARD.encode(versions[1]), "name": "ed25519" }
+                        }
+                    }
+                })))
+                .mount(server)
+                .await;
+            versions
+        }
+
+        #[tokio::test]
+        async fn test_connect_reads_latest_version() {
+            let server = MockServer::start().await;
+            let versions = mock_key(&server, "ed25519", true).await;
+
+            let signer = VaultSigner::connect(connect_config(&server, None))
+                .await
+                .unwrap();
+
+            assert_eq!(signer.pubkey(), versions[1]);
+            assert_eq!(signer.key_version(), Some(2));
+            assert!(signer.is_available().await);
+        }
+
+        #[tokio::test]
+        async fn test_connect_uses_configured_version() {
+            let server = MockServer::start().await;
+            let versions = mock_key(&server, "ed25519", true).await;
+
+            let signer = VaultSigner::connect(VaultSignerConfig {
+                key_version: Some(1),
+                context: None,
+                ..connect_config(&server, Some(versions[0].to_string()))
+            })
+            .await
+            .unwrap();
+
+            assert_eq!(signer.pubkey(), versions[0]);
+        }
+
+        #[tokio::test]
+        async fn test_connect_rejects_pubkey_mismatch() {
+            let server = MockServer::start().await;
+            mock_key(&server, "ed25519", true).await;
+
+            let result =
+                VaultSigner::connect(connect_config(&server, Some(TEST_PUBKEY.to_string()))).await;
+
+            assert!(matches!(result, Err(SignerError::InvalidPublicKey(_))));
+        }
+
+        #[tokio::test]
+        async fn test_connect_rejects_non_ed25519_key() {
+            let server = MockServer::start().await;
+            mock_key(&server, "ecdsa-p256", true).await;
+
+            let result = VaultSigner::connect(connect_config(&server, None)).await;
+
+            assert!(matches!(result, Err(SignerError::ConfigError(_))));
+        }
+
+        #[tokio::test]
+        async fn test_unavailable_when_signing_is_not_allowed() {
+            let server = MockServer::start().await;
+            mock_key(&server, "ed25519", false).await;
+
+            let signer =
+                VaultSigner::from_config(connect_config(&server, Some(TEST_PUBKEY.to_string())))
+                    .unwrap();
+
+            assert!(!signer.is_available().await);
+        }
+
+        #[test]
+        fn test_from_config_requires_pubkey() {
+            let config = VaultSignerConfig {
+                vault_addr: TEST_VAULT_ADDR.to_string(),
+                auth: VaultAuth::token(TEST_VAULT_TOKEN),
+                key_name: TEST_KEY_NAME.to_string(),
+                pubkey: None,
+                namespace: None,
+                transit_mount: None,
+                key_version: None,
+                context: None,
+            };
+
+            assert!(matches!(
+                VaultSigner::from_config(config),
+                Err(SignerError::ConfigError(_))
+            ));
+        }
+    }
+
+    mod auth {
+        use super::*;
+        use std::time::Duration;
+        use wiremock::{
+            matchers::{body_json, header, method, path},
+            Mock, MockServer, Respond, ResponseTemplate,
+        };
+
+        fn auth_response(token: &str, lease_duration: u64) -> ResponseTemplate {
+            ResponseTemplate::new(200).set_body_json(json!({
+                "auth": {
+                    "client_token": token,
+                    "lease_duration": lease_duration,
+                    "renewable": true
+                }
+            }))
+        }
+
+        fn sign_response() -> SignWith {
+            SignWith::new(test_keypair(), 1)
+        }
+
+        async fn mock_sign(server: &MockServer, token: &str, response: impl Respond + 'static) {
+            Mock::given(method("POST"))
+                .and(path(format!("/v1/transit/sign/{TEST_KEY_NAME}")))
+                .and(header("X-Vault-Token", token))
+                .respond_with(response)
+                .mount(server)
+                .await;
+        }
+
+        fn signer_with(server: &MockServer, auth: VaultAuth) -> VaultSigner {
+            VaultSigner::new_with_auth(
+                server.uri(),
+                auth,
+                TEST_KEY_NAME.to_string(),
+                keypair_pubkey(test_keypair()).to_string(),
+            )
+            .unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_app_role_login_is_shared_across_clones() {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/v1/auth/approle/login"))
+                .and(body_json(
+                    json!({ "role_id": "role", "secret_id": "secret" }),
+                ))
+                .respond_with(auth_response("approle-token", 3600))
+                .expect(1)
+                .mount(&server)
+                .await;
+            mock_sign(&server, "approle-token", sign_response()).await;
+
+            let signer = signer_with(&server, VaultAuth::app_role("role", "secret"));
+            let clone = signer.clone();
+
+            let (first, second) = tokio::join!(
+                signer.sign_message_unchecked(b"one"),
+                clone.sign_message_unchecked(b"two")
+            );
+
+            assert!(first.is_ok());
+            assert!(second.is_ok());
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn test_renews_token_before_lease_expires() {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/v1/auth/jwt/login"))
+                .and(body_json(
+                    json!({ "role": "signer", "jwt": "header.claims.sig" }),
+                ))
+                .respond_with(auth_response("jwt-token", 1))
+                .expect(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("POST"))
+                .and(path("/v1/auth/token/renew-self"))
+                .and(header("X-Vault-Token", "jwt-token"))
+                // The paused clock jumps ahead to pending connection pool timers while
+                // requests are in flight, so the renewed lease must outlast those jumps
+                .respond_with(auth_response("jwt-token", 365 * 86_400))
+                .expect(1)
+                .mount(&server)
+                .await;
+            mock_sign(&server, "jwt-token", sign_response()).await;
+
+            let signer = signer_with(&server, VaultAuth::jwt("signer", "header.claims.sig"));
+
+            signer.sign_message_unchecked(b"one").await.unwrap();
+            tokio::time::advance(Duration::from_millis(700)).await;
+            signer.sign_message_unchecked(b"two").await.unwrap();
+            signer.sign_message_unchecked(b"three").await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_logs_in_again_after_revocation() {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/v1/auth/approle/login"))
+                .respond_with(auth_response("revoked-token", 3600))
+                .up_to_n_times(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("POST"))
+                .and(path("/v1/auth/approle/login"))
+                .respond_with(auth_response("fresh-token", 3600))
+                .mount(&server)
+                .await;
+            mock_sign(&server, "revoked-token", ResponseTemplate::new(403)).await;
+            mock_sign(&server, "fresh-token", sign_response()).await;
+
+            let signer = signer_with(&server, VaultAuth::app_role("role", "secret"));
+
+            assert!(signer.sign_message_unchecked(b"hello").await.is_ok());
+        }
+
+        #[tokio::test]
+        async fn test_kubernetes_login_reads_service_account_token() {
+            let server = MockServer::start().await;
+            let jwt_path = std::env::temp_dir().join(format!(
+                "trezoa-keychain-k8s-token-{}",
+                rand::random::<u64>()
+            ));
+            std::fs::write(&jwt_path, "k8s-jwt\n").unwrap();
+
+            Mock::given(method("POST"))
+                .and(path("/v1/auth/k8s/login"))
+                .and(body_json(json!({ "role": "signer", "jwt": "k8s-jwt" })))
+                .respond_with(auth_response("k8s-token", 3600))
+                .mount(&server)
+                .await;
+            mock_sign(&server, "k8s-token", sign_response()).await;
+
+            let auth = VaultAuth::Kubernetes {
+                role: "signer".to_string(),
+                jwt_path: jwt_path.clone(),
+                mount: "kubernetes".to_string(),
+            }
+            .with_mount("k8s");
+            let signer = signer_with(&server, auth);
+
+            assert!(signer.sign_message_unchecked(b"hello").await.is_ok());
+            let _ = std::fs::remove_file(&jwt_path);
+        }
+
+        #[tokio::test]
+        async fn test_static_token_is_not_retried() {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path(format!("/v1/transit/sign/{TEST_KEY_NAME}")))
+                .respond_with(ResponseTemplate::new(403))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let signer = signer_with(&server, VaultAuth::token(TEST_VAULT_TOKEN));
+
+            assert!(matches!(
+                signer.sign_message_unchecked(b"hello").await,
+                Err(SignerError::RemoteApiError(_))
+            ));
+        }
+
+        #[test]
+        fn test_debug_redacts_secrets() {
+            let debug = format!("{:?}", VaultAuth::app_role("role-id", "secret-id"));
+            assert!(!debug.contains("secret-id"));
+            assert!(!debug.contains("role-id"));
+        }
+    }
 }
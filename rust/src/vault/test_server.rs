@@ -0,0 +1,322 @@
+//! In-process stand-in for a Vault dev server's transit engine
+
+use crate::sdk_adapter::{Keypair, Signer};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use rsa::pkcs8::{EncodePublicKey, LineEnding};
+use rsa::rand_core::OsRng;
+use rsa::traits::PublicKeyParts;
+use rsa::{Oaep, RsaPrivateKey};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use wiremock::{matchers::any, Mock, MockServer, Request, Respond, ResponseTemplate};
+
+#[derive(Default)]
+pub struct FakeKey {
+    /// Key versions, version N at index N - 1
+    pub versions: Vec<Keypair>,
+    pub min_encryption_version: u32,
+    pub deletion_allowed: bool,
+    pub derived: bool,
+    /// Keypairs of a derived key by version and context, created on first use
+    pub derived_keys: BTreeMap<(usize, Vec<u8>), Keypair>,
+}
+
+impl FakeKey {
+    /// Keypair of `version` (1-based), derived from `context` for derived keys
+    fn keypair(&mut self, version: usize, context: Option<&[u8]>) -> Option<&Keypair> {
+        if !self.derived {
+            return context.is_none().then(|| &self.versions[version - 1]);
+        }
+        let context = context?;
+        Some(
+            self.derived_keys
+                .entry((version, context.to_vec()))
+                .or_insert_with(Keypair::new),
+        )
+    }
+}
+
+#[derive(Clone, Default)]
+struct Transit {
+    keys: Arc<Mutex<BTreeMap<String, FakeKey>>>,
+}
+
+/// Shared by all fake servers, since generating it dominates test time
+static WRAPPING_KEY: OnceLock<RsaPrivateKey> = OnceLock::new();
+
+/// Transit engine at the default mount, accepting a single static token
+pub struct FakeVault {
+    server: MockServer,
+    transit: Transit,
+}
+
+impl FakeVault {
+    pub const TOKEN: &'static str = "dev-root-token";
+
+    pub async fn start() -> Self {
+        let server = MockServer::start().await;
+        let transit = Transit::default();
+        Mock::given(any())
+            .respond_with(transit.clone())
+            .mount(&server)
+            .await;
+        Self { server, transit }
+    }
+
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// Snapshot of a key's settings
+    pub fn key_config(&self, name: &str) -> FakeKey {
+        let keys = self.transit.keys.lock().unwrap();
+        let key = &keys[name];
+        FakeKey {
+            versions: vec![],
+            min_encryption_version: key.min_encryption_version,
+            deletion_allowed: key.deletion_allowed,
+            derived: key.derived,
+            derived_keys: BTreeMap::new(),
+        }
+    }
+}
+
+/// Transit sign response that signs the request input with a fixed keypair
+pub struct SignWith {
+    keypair: Keypair,
+    version: u32,
+}
+
+impl SignWith {
+    pub fn new(keypair: &Keypair, version: u32) -> Self {
+        Self {
+            keypair: keypair.insecure_clone(),
+            version,
+        }
+    }
+}
+
+impl Respond for SignWith {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let Some(input) = serde_json::from_slice::<Value>(&request.body)
+            .ok()
+            .and_then(|body| STANDARD.decode(body["input"].as_str()?).ok())
+        else {
+            return error(400, "invalid input");
+        };
+
+        let signature = self.keypair.sign_message(&input);
+        ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "signature": format!("vault:v{}:{}", self.version, STANDARD.encode(signature))
+            }
+        }))
+    }
+}
+
+fn error(status: u16, message: &str) -> ResponseTemplate {
+    ResponseTemplate::new(status).set_body_json(json!({ "errors": [message] }))
+}
+
+/// Key metadata; derived keys only list public keys when given a context
+fn key_data(name: &str, key: &mut FakeKey, context: Option<&[u8]>) -> Value {
+    let versions: serde_json::Map<String, Value> = (1..=key.versions.len())
+        .map(|version| {
+            let public_key = key
+                .keypair(version, context)
+                .map(|keypair| STANDARD.encode(keypair.pubkey()));
+            (
+                version.to_string(),
+                json!({ "name": "ed25519", "public_key": public_key }),
+            )
+        })
+        .collect();
+
+    json!({
+        "data": {
+            "name": name,
+            "type": "ed25519",
+            "latest_version": key.versions.len(),
+            "min_encryption_version": key.min_encryption_version,
+            "deletion_allowed": key.deletion_allowed,
+            "derived": key.derived,
+            "supports_signing": true,
+            "keys": versions
+        }
+    })
+}
+
+impl Transit {
+    /// RSA wrapping key, generated on first use (Vault uses 4096 bits, 2048 keeps tests fast)
+    fn wrapping_key(&self) -> &RsaPrivateKey {
+        WRAPPING_KEY.get_or_init(|| RsaPrivateKey::new(&mut OsRng, 2048).unwrap())
+    }
+
+    /// Reverse of `import::wrap_key`, returning the Ed25519 seed
+    fn unwrap_key(&self, ciphertext: &str) -> Option<[u8; 32]> {
+        let ciphertext = STANDARD.decode(ciphertext).ok()?;
+        let wrapping_key = self.wrapping_key();
+        let (ephemeral_key, wrapped_key) = ciphertext.split_at_checked(wrapping_key.size())?;
+
+        let ephemeral_key: [u8; 32] = wrapping_key
+            .decrypt(Oaep::new::<Sha256>(), ephemeral_key)
+            .ok()?
+            .try_into()
+            .ok()?;
+        let der = aes_kw::KekAes256::new(&ephemeral_key.into())
+            .unwrap_with_padding_vec(wrapped_key)
+            .ok()?;
+
+        let seed = der.strip_prefix(&super::import::ED25519_PKCS8_PREFIX)?;
+        seed.try_into().ok()
+    }
+
+    fn handle(
+        &self,
+        method: &str,
+        path: &[&str],
+        query_context: Option<Vec<u8>>,
+        body: &Value,
+    ) -> ResponseTemplate {
+        let mut keys = self.keys.lock().unwrap();
+
+        match (method, path) {
+            ("LIST", ["keys"]) if keys.is_empty() => error(404, "no keys"),
+            ("LIST", ["keys"]) => ResponseTemplate::new(200)
+                .set_body_json(json!({ "data": { "keys": keys.keys().collect::<Vec<_>>() } })),
+            ("POST", ["keys", name]) => {
+                if body["type"] != "ed25519" {
+                    return error(400, "unsupported key type");
+                }
+                keys.entry(name.to_string()).or_insert_with(|| FakeKey {
+                    versions: vec![Keypair::new()],
+                    derived: body["derived"] == true,
+                    ..Default::default()
+                });
+                ResponseTemplate::new(204)
+            }
+            ("GET", ["wrapping_key"]) => {
+                let public_key = self
+                    .wrapping_key()
+                    .to_public_key()
+                    .to_public_key_pem(LineEnding::LF)
+                    .unwrap();
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "data": { "public_key": public_key } }))
+            }
+            ("POST", ["keys", name, "import"]) => {
+                if keys.contains_key(*name) {
+                    return error(400, "the import path cannot be used with an existing key");
+                }
+                if body["type"] != "ed25519" || body["hash_function"] != "SHA256" {
+                    return error(400, "unsupported import parameters");
+                }
+                let Some(seed) = body["ciphertext"]
+                    .as_str()
+                    .and_then(|ciphertext| self.unwrap_key(ciphertext))
+                else {
+                    return error(400, "failed to unwrap key");
+                };
+                keys.insert(
+                    name.to_string(),
+                    FakeKey {
+                        versions: vec![Keypair::new_from_array(seed)],
+                        ..Default::default()
+                    },
+                );
+                ResponseTemplate::new(204)
+            }
+            ("GET", ["keys", name]) => match keys.get_mut(*name) {
+                Some(key) => ResponseTemplate::new(200).set_body_json(key_data(
+                    name,
+                    key,
+                    query_context.as_deref(),
+                )),
+                None => error(404, "key not found"),
+            },
+            ("POST", ["keys", name, "rotate"]) => match keys.get_mut(*name) {
+                Some(key) => {
+                    key.versions.push(Keypair::new());
+                    ResponseTemplate::new(200).set_body_json(key_data(name, key, None))
+                }
+                None => error(404, "key not found"),
+            },
+            ("POST", ["keys", name, "config"]) => match keys.get_mut(*name) {
+                Some(key) => {
+                    if let Some(version) = body["min_encryption_version"].as_u64() {
+                        key.min_encryption_version = version as u32;
+                    }
+                    if let Some(allowed) = body["deletion_allowed"].as_bool() {
+                        key.deletion_allowed = allowed;
+                    }
+                    ResponseTemplate::new(200).set_body_json(key_data(name, key, None))
+                }
+                None => error(404, "key not found"),
+            },
+            ("POST", ["sign", name]) => {
+                let Some(key) = keys.get_mut(*name) else {
+                    return error(404, "key not found");
+                };
+                let version = body["key_version"]
+                    .as_u64()
+                    .unwrap_or(key.versions.len() as u64) as usize;
+                if version == 0 || version > key.versions.len() {
+                    return error(400, "invalid key version");
+                }
+                if (version as u32) < key.min_encryption_version {
+                    return error(400, "requested version is below the minimum");
+                }
+                let Some(input) = body["input"]
+                    .as_str()
+                    .and_then(|input| STANDARD.decode(input).ok())
+                else {
+                    return error(400, "invalid input");
+                };
+
+                let context = match body["context"].as_str().map(|c| STANDARD.decode(c)) {
+                    Some(Ok(context)) => Some(context),
+                    Some(Err(_)) => return error(400, "invalid context"),
+                    None => None,
+                };
+                let Some(keypair) = key.keypair(version, context.as_deref()) else {
+                    return error(400, "missing 'context' for key derivation");
+                };
+
+                let signature = keypair.sign_message(&input);
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "data": {
+                        "signature": format!("vault:v{version}:{}", STANDARD.encode(signature))
+                    }
+                }))
+            }
+            _ => error(404, "unsupported path"),
+        }
+    }
+}
+
+impl Respond for Transit {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let token = request
+            .headers
+            .get("X-Vault-Token")
+            .and_then(|value| value.to_str().ok());
+        if token != Some(FakeVault::TOKEN) {
+            return error(403, "permission denied");
+        }
+
+        let Some(path) = request.url.path().strip_prefix("/v1/transit/") else {
+            return error(404, "unsupported path");
+        };
+        let path: Vec<&str> = path.split('/').collect();
+        let context = request
+            .url
+            .query_pairs()
+            .find(|(name, _)| name == "context")
+            .and_then(|(_, context)| STANDARD.decode(context.as_bytes()).ok());
+        let body = serde_json::from_slice(&request.body).unwrap_or(Value::Null);
+
+        self.handle(request.method.as_str(), &path, context, &body)
+    }
+}
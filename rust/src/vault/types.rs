@@ -0,0 +1,235 @@
+//! Vault transit API types
+
+use crate::error::{ResponseError, SignerError};
+use crate::response;
+use crate::sdk_adapter::{Pubkey, Signature};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// Response of GET /v1/{mount}/keys/{name}
+#[derive(Deserialize)]
+pub struct TransitKeyResponse {
+    pub data: TransitKey,
+}
+
+impl TransitKeyResponse {
+    pub fn parse(body: &str) -> Result<Self, ResponseError> {
+        response::parse_json(body, "Vault key response")
+    }
+}
+
+#[derive(Deserialize)]
+pub struct TransitKey {
+    #[serde(rename = "type")]
+    pub key_type: String,
+    pub latest_version: u32,
+    #[serde(default)]
+    pub supports_signing: bool,
+    /// Whether signing keys are derived from a per-request context
+    #[serde(default)]
+    pub derived: bool,
+    /// Key versions by version number
+    #[serde(default)]
+    pub keys: HashMap<String, TransitKeyVersion>,
+}
+
+#[derive(Deserialize)]
+pub struct TransitKeyVersion {
+    /// Base64-encoded public key, for asymmetric keys
+    pub public_key: Option<String>,
+}
+
+impl TransitKey {
+    /// Check that the key can produce Trezoa signatures
+    pub fn ensure_signing_key(&self) -> Result<(), SignerError> {
+        if self.key_type != "ed25519" {
+            return Err(SignerError::ConfigError(format!(
+                "Vault key type is {}, expected ed25519",
+                self.key_type
+            )));
+        }
+        if !self.supports_signing {
+            return Err(SignerError::ConfigError(
+                "Vault key does not support signing".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that a context is given exactly when the key is derived
+    pub fn ensure_context(&self, context: Option<&[u8]>) -> Result<(), SignerError> {
+        match (self.derived, context) {
+            (true, None) => Err(SignerError::ConfigError(
+                "Vault key is derived and requires a context".to_string(),
+            )),
+            (false, Some(_)) => Err(SignerError::ConfigError(
+                "Vault key is not derived and does not take a context".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Public key of `version`, or of the latest version
+    pub fn pubkey(&self, version: Option<u32>) -> Result<Pubkey, SignerError> {
+        let version = version.unwrap_or(self.latest_version);
+        let public_key = self
+            .keys
+            .get(&version.to_string())
+            .and_then(|key| key.public_key.as_deref())
+            .ok_or_else(|| {
+                SignerError::ConfigError(format!(
+                    "Vault key has no public key for version {version}"
+                ))
+            })?;
+
+        let bytes = response::decode_base64(public_key, "public_key")?;
+        Ok(response::pubkey(bytes, "public_key")?)
+    }
+}
+
+// Response of POST /v1/{mount}/sign/{name}
+#[derive(Deserialize)]
+pub struct SignResponse {
+    pub data: SignData,
+}
+
+#[derive(Deserialize)]
+pub struct SignData {
+    /// `vault:v{version}:{base64 signature}`
+    pub signature: String,
+}
+
+impl SignResponse {
+    pub fn parse(body: &str) -> Result<Self, ResponseError> {
+        response::parse_json(body, "Vault sign response")
+    }
+
+    pub fn signature(&self) -> Result<Signature, ResponseError> {
+        let bytes = response::decode_base64(
+            super::split_version_prefix(&self.data.signature).1,
+            "signature",
+        )?;
+        response::signature(bytes, "signature")
+    }
+
+    /// Key version that produced the signature, if Vault reported one
+    pub fn key_version(&self) -> Option<u32> {
+        super::split_version_prefix(&self.data.signature).0
+    }
+}
+
+// Response of LIST /v1/{mount}/keys
+#[derive(Deserialize)]
+pub struct ListKeysResponse {
+    pub data: ListKeysData,
+}
+
+#[derive(Deserialize)]
+pub struct ListKeysData {
+    pub keys: Vec<String>,
+}
+
+impl ListKeysResponse {
+    pub fn parse(body: &str) -> Result<Self, ResponseError> {
+        response::parse_json(body, "Vault key list")
+    }
+}
+
+// Response of GET /v1/{mount}/wrapping_key
+#[derive(Deserialize)]
+pub struct WrappingKeyResponse {
+    pub data: WrappingKeyData,
+}
+
+#[derive(Deserialize)]
+pub struct WrappingKeyData {
+    /// PEM-encoded RSA public key
+    pub public_key: String,
+}
+
+impl WrappingKeyResponse {
+    pub fn parse(body: &str) -> Result<Self, ResponseError> {
+        response::parse_json(body, "Vault wrapping key")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{arb_encoded_string, arb_json};
+    use proptest::prelude::*;
+    use serde_json::json;
+
+    const FIELDS: &[&str] = &[
+        "data",
+        "type",
+        "latest_version",
+        "supports_signing",
+        "derived",
+        "keys",
+        "1",
+        "2",
+        "public_key",
+        "signature",
+    ];
+
+    fn parse_all(body: &str) {
+        if let Ok(response) = TransitKeyResponse::parse(body) {
+            let key = response.data;
+            let _ = key.ensure_signing_key();
+            let _ = key.ensure_context(None);
+            let _ = key.pubkey(None);
+            let _ = key.pubkey(Some(1));
+        }
+        if let Ok(response) = SignResponse::parse(body) {
+            let _ = response.signature();
+            let _ = response.key_version();
+        }
+        let _ = ListKeysResponse::parse(body);
+        let _ = WrappingKeyResponse::parse(body);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parsers_never_panic(value in arb_json(FIELDS), raw in ".*") {
+            parse_all(&value.to_string());
+            parse_all(&raw);
+        }
+
+        #[test]
+        fn test_field_decoding_never_panics(
+            value in arb_encoded_string(),
+            version in any::<u32>(),
+        ) {
+            parse_all(&json!({
+                "data": {
+                    "type": "ed25519",
+                    "latest_version": version,
+                    "supports_signing": true,
+                    "keys": { version.to_string(): { "public_key": value }, "1": { "public_key": value } }
+                }
+            }).to_string());
+            parse_all(&json!({ "data": { "signature": value } }).to_string());
+            parse_all(&json!({ "data": { "signature": format!("vault:v{version}:{value}") } }).to_string());
+        }
+    }
+
+    #[test]
+    fn test_signature_errors() {
+        let response = SignResponse::parse(r#"{"data":{"signature":"vault:v1:AAAA"}}"#).unwrap();
+        assert_eq!(
+            response.signature(),
+            Err(ResponseError::InvalidLength {
+                field: "signature",
+                expected: 64,
+                actual: 3
+            })
+        );
+
+        let response = SignResponse::parse(r#"{"data":{"signature":"vault:v1:!!"}}"#).unwrap();
+        assert!(matches!(
+            response.signature(),
+            Err(ResponseError::InvalidEncoding { .. })
+        ));
+    }
+}
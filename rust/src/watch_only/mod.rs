@@ -0,0 +1,179 @@
+//! Watch-only signer that holds a public key but no private key
+//!
+//! Useful for fee estimation, simulation with sigverify disabled and testing
+//! transaction builders. Signature slots are filled with a deterministic
+//! placeholder (see [`TransactionUtil::placeholder_signature`]) that never
+//! verifies, so the result can't be broadcast by mistake.
+//!
+//! The signer always reports itself available, as it can always produce a
+//! placeholder. Check [`crate::Signer::is_watch_only`] or
+//! [`TransactionUtil::has_placeholder_signatures`] to tell its output apart
+//! from a real signature; the send paths in this crate refuse placeholders.
+
+use crate::{
+    error::SignerError,
+    sdk_adapter::{Pubkey, Signature, Transaction, VersionedTransaction},
+    traits::{SignedTransaction, TrezoaSigner},
+    transaction_util::TransactionUtil,
+};
+use std::str::FromStr;
+
+/// A signer that only knows its public key and produces placeholder signatures
+#[derive(Debug, Clone)]
+pub struct WatchOnlySigner {
+    pubkey: Pubkey,
+}
+
+impl WatchOnlySigner {
+    /// Creates a new watch-only signer for the given public key
+    pub fn new(pubkey: Pubkey) -> Self {
+        Self { pubkey }
+    }
+
+    /// Creates a new watch-only signer from a base58-encoded public key
+    pub fn from_pubkey_string(pubkey: &str) -> Result<Self, SignerError> {
+        let pubkey = Pubkey::from_str(pubkey)
+            .map_err(|e| SignerError::InvalidPublicKey(format!("Invalid public key: {e}")))?;
+        Ok(Self::new(pubkey))
+    }
+
+    fn fill_placeholder(&self, tx: &mut Transaction) -> Result<SignedTransaction, SignerError> {
+        let signature = TransactionUtil::placeholder_signature(&self.pubkey);
+
+        TransactionUtil::add_signature_to_transaction(tx, &self.pubkey, signature)?;
+
+        Ok((TransactionUtil::serialize_transaction(tx)?, signature))
+    }
+
+    fn fill_versioned_placeholder(
+        &self,
+        tx: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        let signature = TransactionUtil::placeholder_signature(&self.pubkey);
+
+        TransactionUtil::add_signature_to_versioned_transaction(tx, &self.pubkey, signature)?;
+
+        Ok((
+            TransactionUtil::serialize_versioned_transaction(tx)?,
+            signature,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl TrezoaSigner for WatchOnlySigner {
+    fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut Transaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        self.fill_placeholder(tx)
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        TransactionUtil::ensure_not_transaction_message(message, &self.pubkey)?;
+        Ok(TransactionUtil::placeholder_signature(&self.pubkey))
+    }
+
+    async fn sign_message_unchecked(&self, _message: &[u8]) -> Result<Signature, SignerError> {
+        Ok(TransactionUtil::placeholder_signature(&self.pubkey))
+    }
+
+    async fn sign_partial_transaction(
+        &self,
+        tx: &mut Transaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        self.fill_placeholder(tx)
+    }
+
+    async fn sign_versioned_transaction(
+        &self,
+        tx: &mut VersionedTransaction,
+    ) -> Result<SignedTransaction, SignerError> {
+        self.fill_versioned_placeholder(tx)
+    }
+
+    async fn is_available(&self) -> bool {
+        // Nothing to reach; placeholders need only the public key
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_test_transaction;
+
+    #[tokio::test]
+    async fn test_sign_transaction_fills_placeholder() {
+        let signer = WatchOnlySigner::new(Pubkey::new_unique());
+        let mut tx = create_test_transaction(&signer.pubkey());
+
+        let (serialized, signature) = signer.sign_transaction(&mut tx).await.unwrap();
+
+        assert_eq!(tx.signatures[0], signature);
+        assert!(TransactionUtil::is_placeholder_signature(&signature));
+        assert!(TransactionUtil::has_placeholder_signatures(&tx));
+        assert!(!signature.verify(signer.pubkey().as_ref(), &tx.message_data()));
+        assert_eq!(
+            TransactionUtil::deserialize_transaction(&serialized)
+                .unwrap()
+                .signatures[0],
+            signature
+        );
+    }
+
+    #[tokio::test]
+    async fn test_placeholder_is_deterministic() {
+        let pubkey = Pubkey::new_unique();
+        let signer = WatchOnlySigner::new(pubkey);
+
+        let first = signer.sign_message(b"first").await.unwrap();
+        let second = signer.sign_message(b"second").await.unwrap();
+        let other = WatchOnlySigner::new(Pubkey::new_unique())
+            .sign_message(b"first")
+            .await
+            .unwrap();
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[tokio::test]
+    async fn test_sign_message_rejects_transaction_message() {
+        let signer = WatchOnlySigner::new(Pubkey::new_unique());
+        let message = create_test_transaction(&signer.pubkey()).message_data();
+
+        let result = signer.sign_message(&message).await;
+        let unchecked = signer.sign_message_unchecked(&message).await.unwrap();
+
+        assert!(result.is_err());
+        assert!(TransactionUtil::is_placeholder_signature(&unchecked));
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction_rejects_non_signer() {
+        let signer = WatchOnlySigner::new(Pubkey::new_unique());
+        let mut tx = create_test_transaction(&Pubkey::new_unique());
+
+        let result = signer.sign_partial_transaction(&mut tx).await;
+
+        assert!(matches!(result, Err(SignerError::SigningFailed(_))));
+    }
+
+    #[test]
+    fn test_from_pubkey_string() {
+        let pubkey = Pubkey::new_unique();
+
+        let signer = WatchOnlySigner::from_pubkey_string(&pubkey.to_string()).unwrap();
+
+        assert_eq!(signer.pubkey(), pubkey);
+        assert!(matches!(
+            WatchOnlySigner::from_pubkey_string("not-a-pubkey"),
+            Err(SignerError::InvalidPublicKey(_))
+        ));
+    }
+}